@@ -4,12 +4,18 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct AngustConfiguration {
     pub pathing_config: PathingConfiguration,
+    #[serde(default, rename = "window")]
+    pub window_config: WindowConfiguration,
+    #[serde(default, rename = "mouse")]
+    pub mouse_config: MouseConfiguration,
 }
 
 impl Default for AngustConfiguration {
     fn default() -> Self {
         AngustConfiguration {
             pathing_config: PathingConfiguration::default(),
+            window_config: WindowConfiguration::default(),
+            mouse_config: MouseConfiguration::default(),
         }
     }
 }
@@ -35,4 +41,108 @@ impl Default for PathingConfiguration {
             index_html_path: String::from("src/index.html"),
         }
     }
-}
\ No newline at end of file
+}
+
+/*
+ * Initial window options. Sizes are in logical pixels; min/max sizes apply only when both dimensions are set.
+ * Missing fields fall back to the defaults, so existing configuration files remain valid.
+ * Without a title, the one passed to Application::new is used.
+ */
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct WindowConfiguration {
+    pub title: Option<String>,
+    pub width: f64,
+    pub height: f64,
+    pub min_width: Option<f64>,
+    pub min_height: Option<f64>,
+    pub max_width: Option<f64>,
+    pub max_height: Option<f64>,
+    pub resizable: bool,
+    pub decorations: bool,
+    pub transparent: bool,
+    pub maximized: bool,
+    pub fullscreen: bool,
+    pub msaa_samples: Option<u8>, // Preferred MSAA sample count, None for the lowest available
+}
+
+impl Default for WindowConfiguration {
+    fn default() -> Self {
+        WindowConfiguration {
+            title: None,
+            width: 1200.0,
+            height: 800.0,
+            min_width: None,
+            min_height: None,
+            max_width: None,
+            max_height: None,
+            resizable: true,
+            decorations: true,
+            transparent: false,
+            maximized: false,
+            fullscreen: false,
+            msaa_samples: None,
+        }
+    }
+}
+
+/*
+ * Mouse options. Consecutive clicks form a double (or triple...) click when each comes within 
+ * multi_click_interval_ms of the previous one, at most multi_click_distance logical pixels away.
+ */
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct MouseConfiguration {
+    pub multi_click_interval_ms: u64,
+    pub multi_click_distance: f32,
+}
+
+impl Default for MouseConfiguration {
+    fn default() -> Self {
+        MouseConfiguration {
+            multi_click_interval_ms: 500,
+            multi_click_distance: 4.0,
+        }
+    }
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_window_section_with_defaults_for_missing_keys() {
+        // Arrange
+        let config_content = r#"{
+            "pathing_config": {
+                "angust_config_path": "angust.config.json",
+                "app_dir_path": "src/app",
+                "assets_dir_path": "src/assets",
+                "styles_dir_path": "src/styles",
+                "main_rs_path": "src/main.rs",
+                "index_html_path": "src/index.html"
+            },
+            "window": {
+                "title": "Chain Optim",
+                "width": 800,
+                "min_width": 400,
+                "resizable": false
+            }
+        }"#;
+
+        // Act
+        let angust_config: AngustConfiguration = serde_json::from_str(config_content).unwrap();
+
+        // Assert
+        let window_config = angust_config.window_config;
+        assert_eq!(window_config.title, Some(String::from("Chain Optim")));
+        assert_eq!(window_config.width, 800.0);
+        assert_eq!(window_config.height, 800.0);
+        assert_eq!(window_config.min_width, Some(400.0));
+        assert_eq!(window_config.min_height, None);
+        assert!(!window_config.resizable);
+        assert!(window_config.decorations);
+    }
+}
@@ -2,30 +2,40 @@ use skia_safe::{gpu::gl::FramebufferInfo, Point};
 use winit::{
     application::ApplicationHandler, 
     dpi::PhysicalSize, 
-    event::{ElementState, KeyEvent, Modifiers, MouseButton, MouseScrollDelta, WindowEvent}, 
+    event::{ElementState, KeyEvent, Modifiers, WindowEvent}, 
     event_loop::{ActiveEventLoop, ControlFlow, EventLoop}
 };
 use gl_rs as gl;
 use glutin::{config::GlConfig, display::GetGlDisplay, prelude::GlDisplay, surface::GlSurface};
-use std::{ffi::CString, num::NonZeroU32};
+use std::{ffi::CString, num::NonZeroU32, time::Instant};
 
 use crate::{
-    parsing::{
-        css::stylesheet_parser::{self, Stylesheet}, 
-        html::html_parser::{self, ParsingContext}
-    }, 
+    parsing::css::stylesheet_parser::Stylesheet, 
     rendering::{
-        elements::{component::state::reflectivity::NoState, element::EventType}, 
-        renderer::Renderer,
+        elements::{
+            common_types::Position, 
+            element::EventType, 
+            mouse_event::{MouseButton as EventMouseButton, MouseEvent}, 
+            styles::Cursor
+        }, 
+        renderer::Renderer
     }, 
     window::WindowingSystem
 };
 
 use super::{
-    angust_configuration::AngustConfiguration, 
+    angust_configuration::{AngustConfiguration, WindowConfiguration}, 
+    application_builder::ApplicationBuilder, 
+    event_handling::{
+        click_counter::ClickCounter, 
+        cursor_icon_converter::to_cursor_icon, 
+        keyboard_event_converter::{to_key_modifiers, to_keyboard_event}, 
+        mouse_button_converter::to_mouse_button, 
+        wheel_event_converter::to_wheel_event_type
+    }, 
     event_loop_proxy::{set_event_loop_proxy, ApplicationEvent}, 
-    resource_loader::configuration_loader::load_angust_configuration, 
-    ui_initializer::load_resources
+    frame_scheduler::FrameScheduler, 
+    ui_initializer::load_ui
 };
 
 
@@ -41,31 +51,39 @@ pub struct Application<State> {
     fb_info: FramebufferInfo,
     event_loop: Option<EventLoop<ApplicationEvent>>,
     modifiers: Modifiers,
+    frame_scheduler: FrameScheduler,
 
     mouse_position: Option<Point>,
     is_mouse_pressed: bool,
+    click_counter: ClickCounter,
+    cursor: Cursor,
 }
 
 impl<State> Application<State> {
     // Initialization
+    // The title of the configuration's window section, if any, takes precedence over app_title
     pub fn new(initial_state: State, app_title: String) -> Self {
+        ApplicationBuilder::new(initial_state)
+            .default_title(app_title)
+            .build()
+    }
+
+    pub fn builder(initial_state: State) -> ApplicationBuilder<State> {
+        ApplicationBuilder::new(initial_state)
+    }
+
+    pub(crate) fn from_configuration(initial_state: State, angust_config: AngustConfiguration) -> Self {
         let event_loop = EventLoop::<ApplicationEvent>::with_user_event().build()
             .expect("Failed to create event loop");
         let event_loop_proxy = event_loop.create_proxy();
         set_event_loop_proxy(event_loop_proxy);
 
-        let mut windowing_system = Self::init_windowing_system(&event_loop, app_title);
+        let mut windowing_system = Self::init_windowing_system(&event_loop, &angust_config.window_config);
     
         let fb_info = Self::init_framebuffer_info();
         
         // Load UI
-        let angust_config = load_angust_configuration();
-        let (dom, stylesheets) = load_resources(&angust_config);
-        let stylesheet = stylesheet_parser::parse_stylesheet(&stylesheets);
-        let mut parsing_context: ParsingContext<NoState> = ParsingContext::new(Some(angust_config.clone()), Some(stylesheet.clone()), None, None, None, None, None, None);
-        
-        let ui_body = html_parser::map_dom_to_elements::<NoState>(&dom, None, &mut parsing_context)
-            .unwrap_or_else(|e| panic!("Failed to map DOM to elements: {:?}", e));
+        let (ui_body, stylesheet) = load_ui(&angust_config);
 
         // Initialize renderer and layout
         let mut renderer = Renderer::new(
@@ -78,6 +96,8 @@ impl<State> Application<State> {
         );
         renderer.layout();
 
+        let click_counter = ClickCounter::new(&angust_config.mouse_config);
+
         Self {
             state: initial_state,
             angust_config,
@@ -86,14 +106,17 @@ impl<State> Application<State> {
             fb_info,
             event_loop: Some(event_loop),
             modifiers: Modifiers::default(),
+            frame_scheduler: FrameScheduler::default(),
             mouse_position: None,
             is_mouse_pressed: false,
+            click_counter,
+            cursor: Cursor::default(),
             renderer,
         }
     }
     
-    fn init_windowing_system(event_loop: &EventLoop<ApplicationEvent>, app_title: String) -> WindowingSystem {
-        let windowing_system = WindowingSystem::new(event_loop, app_title);
+    fn init_windowing_system(event_loop: &EventLoop<ApplicationEvent>, window_config: &WindowConfiguration) -> WindowingSystem {
+        let windowing_system = WindowingSystem::new(event_loop, window_config);
     
         gl::load_with(|s| windowing_system
             .gl_config
@@ -138,11 +161,97 @@ impl<State> Application<State> {
         );
     
         self.renderer.resize_surface(&self.windowing_system.window, &mut self.windowing_system.gr_context, self.fb_info, self.windowing_system.gl_config.num_samples() as usize, self.windowing_system.gl_config.stencil_size() as usize);
+        self.renderer.layout(); // In logical pixels, so the UI keeps its size across displays
+        self.windowing_system.window.request_redraw();
+    }
+
+    fn handle_keyboard_input(&mut self, key_event: &KeyEvent) {
+        let keyboard_event = to_keyboard_event(key_event, &self.modifiers);
+        let event_type = match key_event.state {
+            ElementState::Pressed => EventType::KeyDown(keyboard_event),
+            ElementState::Released => EventType::KeyUp(keyboard_event),
+        };
+
+        self.renderer.dispatch_keyboard_event(&event_type);
+        self.renderer.layout(); // Moving the focus with Tab may restyle elements
+    }
+
+    /*
+     * Clicks are handled on press. The left button focuses the element and runs its default actions,
+     * the right one fires contextmenu and the middle one auxclick (like the right one).
+     */
+    fn handle_mouse_press(&mut self, mouse_position: Point, button: EventMouseButton) {
+        if button == EventMouseButton::Left {
+            self.is_mouse_pressed = true;
+            // An open popup takes the click, which then doesn't reach the elements under it
+            if self.renderer.propagate_overlay_event(mouse_position, &EventType::MouseClick) {
+                return;
+            }
+        }
+        let click_count = self.click_counter.register_click(Instant::now(), mouse_position, button);
+
+        let is_default_prevented = self.dispatch_mouse_event(mouse_position, EventType::MouseDown, Some(button));
+        match button {
+            EventMouseButton::Left => {
+                if !is_default_prevented {
+                    self.renderer.focus_at(mouse_position);
+                    self.renderer.handle_event(mouse_position, EventType::MouseDown);
+                }
+                if !self.dispatch_mouse_event(mouse_position, EventType::MouseClick, Some(button)) {
+                    self.renderer.propagate_event(mouse_position, &EventType::MouseClick); // Default actions, eg. toggling a checkbox
+                }
+                if click_count == 2 {
+                    self.dispatch_mouse_event(mouse_position, EventType::MouseDoubleClick, Some(button));
+                }
+            },
+            EventMouseButton::Right => {
+                self.dispatch_mouse_event(mouse_position, EventType::MouseAuxClick, Some(button));
+                self.dispatch_mouse_event(mouse_position, EventType::ContextMenu, Some(button));
+            },
+            EventMouseButton::Middle => {
+                self.dispatch_mouse_event(mouse_position, EventType::MouseAuxClick, Some(button));
+            },
+        }
+    }
+
+    fn handle_mouse_release(&mut self, mouse_position: Point, button: EventMouseButton) {
+        if button == EventMouseButton::Left {
+            self.is_mouse_pressed = false;
+            self.renderer.handle_event(mouse_position, EventType::MouseUp);
+            self.renderer.propagate_event(mouse_position, &EventType::MouseUp); // Ends dragging (eg. of a range's thumb)
+        }
+        self.dispatch_mouse_event(mouse_position, EventType::MouseUp, Some(button));
+    }
+
+    // Runs the @on<event> handlers of the elements under the cursor, returning whether one prevented the default action
+    fn dispatch_mouse_event(&mut self, mouse_position: Point, event_type: EventType, button: Option<EventMouseButton>) -> bool {
+        let position = Position { x: mouse_position.x, y: mouse_position.y };
+        let mut mouse_event = MouseEvent::new(event_type, position, button, to_key_modifiers(&self.modifiers));
+        if !matches!(mouse_event.event_type, EventType::MouseMove | EventType::MouseDrag | EventType::MouseRoll(_) | EventType::MouseRollHorizontal(_)) {
+            mouse_event.click_count = self.click_counter.get_click_count();
+        }
+
+        self.renderer.dispatch_mouse_event(&mouse_event)
+    }
+
+    // Shows the cursor requested by the hovered element's cursor style, when it changed
+    fn update_cursor(&mut self) {
+        let cursor = self.renderer.get_cursor();
+        if cursor == self.cursor {
+            return;
+        }
+        self.windowing_system.window.set_cursor(to_cursor_icon(cursor));
+        self.cursor = cursor;
+    }
+
+    fn handle_scale_factor_change(&mut self, scale_factor: f64) {
+        self.renderer.set_scale_factor(scale_factor as f32);
+        self.renderer.layout();
         self.windowing_system.window.request_redraw();
     }
 
     fn handle_redraw_requested(&mut self) {
-        self.renderer.render_frame(&mut self.windowing_system.gr_context);
+        self.renderer.render_frame();
         self.windowing_system.gr_context.flush_and_submit();
     
         self.windowing_system
@@ -172,6 +281,9 @@ impl<State> ApplicationHandler<ApplicationEvent> for Application<State> {
             WindowEvent::Resized(physical_size) => {
                 self.handle_window_resize(physical_size);
             },
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                self.handle_scale_factor_change(scale_factor);
+            },
             WindowEvent::ModifiersChanged(new_modifiers) => {
                 self.modifiers = new_modifiers;
             },
@@ -181,67 +293,87 @@ impl<State> ApplicationHandler<ApplicationEvent> for Application<State> {
 
             // Mouse and keyboard events
             WindowEvent::MouseInput { state, button, .. } => {
-                match (state, button) {
-                    (ElementState::Pressed, MouseButton::Left) => {
-                        self.is_mouse_pressed = true;
-                        if let Some(mouse_position) = self.mouse_position {
-                            self.renderer.handle_event(mouse_position, EventType::MouseDown);
-                            self.renderer.propagate_event(mouse_position, &EventType::MouseClick);
-                            
-                            self.windowing_system.window.request_redraw();
-                        }
-                    },
-                    (ElementState::Released, MouseButton::Left) => {
-                        self.is_mouse_pressed = false;
-                        if let Some(mouse_position) = self.mouse_position {
-                            self.renderer.handle_event(mouse_position, EventType::MouseUp);
-                            self.windowing_system.window.request_redraw();
-                        }
-                    },
-                    _ => ()
+                let (mouse_position, button) = match (self.mouse_position, to_mouse_button(button)) {
+                    (Some(mouse_position), Some(button)) => (mouse_position, button),
+                    _ => return,
+                };
+
+                match state {
+                    ElementState::Pressed => self.handle_mouse_press(mouse_position, button),
+                    ElementState::Released => self.handle_mouse_release(mouse_position, button),
                 }
+                self.renderer.layout(); // :focus and :active rules may change the layout
+                self.windowing_system.window.request_redraw();
             },
             WindowEvent::CursorMoved { position, .. } => {
-                self.mouse_position = Some(Point::new(position.x as f32, position.y as f32));
+                let physical_position = Point::new(position.x as f32, position.y as f32);
+                self.mouse_position = Some(self.renderer.to_logical_point(physical_position));
 
-                if self.is_mouse_pressed {
-                    if let Some(mouse_position) = self.mouse_position {
+                if let Some(mouse_position) = self.mouse_position {
+                    if self.is_mouse_pressed {
                         self.renderer.handle_event(mouse_position, EventType::MouseDrag);
-                        self.windowing_system.window.request_redraw();
+                        self.renderer.propagate_event(mouse_position, &EventType::MouseDrag);
+                        self.dispatch_mouse_event(mouse_position, EventType::MouseDrag, Some(EventMouseButton::Left));
+                    } else {
+                        self.dispatch_mouse_event(mouse_position, EventType::MouseMove, None); // Also fires @onmouseenter and @onmouseleave
                     }
+                    self.renderer.layout(); // :hover rules may change the layout; only restyled elements are redrawn
+                    self.update_cursor();
+                    self.windowing_system.window.request_redraw();
                 }
             },
             WindowEvent::MouseWheel { delta, .. } => {
-                let scroll_delta = match delta {
-                    MouseScrollDelta::LineDelta(_, y) => y * 5.0,
-                    MouseScrollDelta::PixelDelta(pos) => pos.y as f32,
-                };
+                let event_type = to_wheel_event_type(delta, self.renderer.get_scale_factor(), self.modifiers.state().shift_key());
             
                 if let Some(mouse_position) = self.mouse_position {
-                    self.renderer.handle_event(mouse_position, EventType::MouseRoll(scroll_delta));
+                    if !self.renderer.propagate_overlay_event(mouse_position, &event_type) {
+                        if !self.dispatch_mouse_event(mouse_position, event_type.clone(), None) {
+                            self.renderer.handle_event(mouse_position, event_type); // Scrolling
+                        }
+                    }
                     self.windowing_system.window.request_redraw();
                 }
             },
-            WindowEvent::KeyboardInput {
-                event: KeyEvent { logical_key, .. }, 
-                ..
-            } => {
-                if self.modifiers.state().super_key() && logical_key == "q" {
+            WindowEvent::KeyboardInput { event: key_event, .. } => {
+                if self.modifiers.state().super_key() && key_event.logical_key == "q" {
                     event_loop.exit();
+                    return;
                 }
+                self.handle_keyboard_input(&key_event);
                 self.windowing_system.window.request_redraw();
             }
             _ => (),
         }
+    }
+
+    /*
+     * Called once the pending events are handled. While animation frames are requested,
+     * the tree is updated at the frame rate; otherwise the event loop sleeps until the next event.
+     */
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        if !self.renderer.needs_animation_frame() {
+            self.frame_scheduler.stop();
+            event_loop.set_control_flow(ControlFlow::Wait);
+            return;
+        }
 
-        event_loop.set_control_flow(ControlFlow::Wait);
+        let now = Instant::now();
+        if self.frame_scheduler.is_frame_due(now) {
+            let dt = self.frame_scheduler.tick(now);
+            self.renderer.update(dt);
+            self.renderer.layout();
+            self.windowing_system.window.request_redraw();
+        }
+
+        event_loop.set_control_flow(self.frame_scheduler.get_control_flow(Instant::now()));
     }
 
     fn user_event(&mut self, _: &ActiveEventLoop, event: ApplicationEvent) {
         match event {
             ApplicationEvent::StateChange(component_id) => {
                 self.renderer.react_to_state_change(component_id);
-                self.renderer.layout(); // Naive approach for now, should be optimized
+                self.renderer.layout(); // Only the changed component's subtree and affected ancestors are laid out again
+                self.update_cursor();
                 self.windowing_system.window.request_redraw();
             },
             ApplicationEvent::ExecuteTask(executor) => {
@@ -250,7 +382,21 @@ impl<State> ApplicationHandler<ApplicationEvent> for Application<State> {
             ApplicationEvent::RouteChange(route, component_name) => {
                 self.renderer.handle_route_change(&route, &component_name);
                 self.renderer.layout();
+                self.update_cursor();
                 self.windowing_system.window.request_redraw();
+            },
+            ApplicationEvent::FocusChange(element_id) => {
+                match element_id {
+                    Some(element_id) => { self.renderer.focus_element(&element_id); },
+                    None => self.renderer.blur(),
+                }
+                self.renderer.layout(); // :focus rules may change the layout
+                self.windowing_system.window.request_redraw();
+            },
+            ApplicationEvent::ScrollRequest(request) => {
+                self.renderer.handle_scroll_request(&request);
+                self.renderer.layout();
+                self.windowing_system.window.request_redraw(); // Smooth scrolling then requests animation frames
             }
         }
     }
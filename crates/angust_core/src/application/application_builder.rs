@@ -0,0 +1,169 @@
+use super::{
+    angust_configuration::{AngustConfiguration, MouseConfiguration, WindowConfiguration}, 
+    application::Application, 
+    resource_loader::configuration_loader::load_angust_configuration
+};
+
+
+/*
+ * Builder for Application, covering the window and mouse options.
+ * The window and mouse sections of the Angust configuration file are loaded first; 
+ * options set explicitly on the builder take precedence over it.
+ */
+pub struct ApplicationBuilder<State> {
+    initial_state: State,
+
+    title: Option<String>,
+    default_title: Option<String>,
+    inner_size: Option<(f64, f64)>,
+    min_inner_size: Option<(f64, f64)>,
+    max_inner_size: Option<(f64, f64)>,
+    resizable: Option<bool>,
+    decorations: Option<bool>,
+    transparent: Option<bool>,
+    maximized: Option<bool>,
+    fullscreen: Option<bool>,
+    msaa_samples: Option<u8>,
+    multi_click_interval_ms: Option<u64>,
+}
+
+impl<State> ApplicationBuilder<State> {
+    pub fn new(initial_state: State) -> Self {
+        Self {
+            initial_state,
+            title: None,
+            default_title: None,
+            inner_size: None,
+            min_inner_size: None,
+            max_inner_size: None,
+            resizable: None,
+            decorations: None,
+            transparent: None,
+            maximized: None,
+            fullscreen: None,
+            msaa_samples: None,
+            multi_click_interval_ms: None,
+        }
+    }
+
+    pub fn title(mut self, title: String) -> Self {
+        self.title = Some(title);
+        self
+    }
+
+    // Title used when neither the builder nor the configuration file sets one
+    pub(crate) fn default_title(mut self, default_title: String) -> Self {
+        self.default_title = Some(default_title);
+        self
+    }
+
+    pub fn inner_size(mut self, width: f64, height: f64) -> Self {
+        self.inner_size = Some((width, height));
+        self
+    }
+
+    pub fn min_inner_size(mut self, width: f64, height: f64) -> Self {
+        self.min_inner_size = Some((width, height));
+        self
+    }
+
+    pub fn max_inner_size(mut self, width: f64, height: f64) -> Self {
+        self.max_inner_size = Some((width, height));
+        self
+    }
+
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = Some(resizable);
+        self
+    }
+
+    pub fn decorations(mut self, decorations: bool) -> Self {
+        self.decorations = Some(decorations);
+        self
+    }
+
+    pub fn transparent(mut self, transparent: bool) -> Self {
+        self.transparent = Some(transparent);
+        self
+    }
+
+    pub fn maximized(mut self, maximized: bool) -> Self {
+        self.maximized = Some(maximized);
+        self
+    }
+
+    pub fn fullscreen(mut self, fullscreen: bool) -> Self {
+        self.fullscreen = Some(fullscreen);
+        self
+    }
+
+    pub fn msaa_samples(mut self, msaa_samples: u8) -> Self {
+        self.msaa_samples = Some(msaa_samples);
+        self
+    }
+
+    // Maximum delay between the clicks of a double click, in milliseconds
+    pub fn multi_click_interval(mut self, multi_click_interval_ms: u64) -> Self {
+        self.multi_click_interval_ms = Some(multi_click_interval_ms);
+        self
+    }
+
+    pub fn build(self) -> Application<State> {
+        let mut angust_config = load_angust_configuration();
+        self.apply_window_options(&mut angust_config.window_config);
+        self.apply_mouse_options(&mut angust_config.mouse_config);
+
+        Application::from_configuration(self.initial_state, angust_config)
+    }
+
+    pub fn build_with_configuration(self, mut angust_config: AngustConfiguration) -> Application<State> {
+        self.apply_window_options(&mut angust_config.window_config);
+        self.apply_mouse_options(&mut angust_config.mouse_config);
+
+        Application::from_configuration(self.initial_state, angust_config)
+    }
+
+    fn apply_window_options(&self, window_config: &mut WindowConfiguration) {
+        if let Some(title) = &self.title {
+            window_config.title = Some(title.clone());
+        } else if window_config.title.is_none() {
+            window_config.title = self.default_title.clone();
+        }
+        if let Some((width, height)) = self.inner_size {
+            window_config.width = width;
+            window_config.height = height;
+        }
+        if let Some((min_width, min_height)) = self.min_inner_size {
+            window_config.min_width = Some(min_width);
+            window_config.min_height = Some(min_height);
+        }
+        if let Some((max_width, max_height)) = self.max_inner_size {
+            window_config.max_width = Some(max_width);
+            window_config.max_height = Some(max_height);
+        }
+        if let Some(resizable) = self.resizable {
+            window_config.resizable = resizable;
+        }
+        if let Some(decorations) = self.decorations {
+            window_config.decorations = decorations;
+        }
+        if let Some(transparent) = self.transparent {
+            window_config.transparent = transparent;
+        }
+        if let Some(maximized) = self.maximized {
+            window_config.maximized = maximized;
+        }
+        if let Some(fullscreen) = self.fullscreen {
+            window_config.fullscreen = fullscreen;
+        }
+        if self.msaa_samples.is_some() {
+            window_config.msaa_samples = self.msaa_samples;
+        }
+    }
+
+    fn apply_mouse_options(&self, mouse_config: &mut MouseConfiguration) {
+        if let Some(multi_click_interval_ms) = self.multi_click_interval_ms {
+            mouse_config.multi_click_interval_ms = multi_click_interval_ms;
+        }
+    }
+}
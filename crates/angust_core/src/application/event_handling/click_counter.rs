@@ -0,0 +1,97 @@
+use std::time::{Duration, Instant};
+
+use skia_safe::Point;
+
+use crate::{application::angust_configuration::MouseConfiguration, rendering::elements::mouse_event::MouseButton};
+
+
+/*
+ * Counts consecutive clicks, to detect double and triple clicks.
+ * A click continues the sequence if it comes soon enough after the previous one, with the same button, at about the same position.
+ */
+pub struct ClickCounter {
+    multi_click_interval: Duration,
+    multi_click_distance: f32, // In logical pixels
+    last_click: Option<(Instant, Point, MouseButton)>,
+    click_count: u32,
+}
+
+impl Default for ClickCounter {
+    fn default() -> Self {
+        Self::new(&MouseConfiguration::default())
+    }
+}
+
+impl ClickCounter {
+    pub fn new(mouse_config: &MouseConfiguration) -> Self {
+        Self {
+            multi_click_interval: Duration::from_millis(mouse_config.multi_click_interval_ms),
+            multi_click_distance: mouse_config.multi_click_distance,
+            last_click: None,
+            click_count: 0,
+        }
+    }
+
+    // Returns the number of clicks in the sequence, including this one
+    pub fn register_click(&mut self, now: Instant, position: Point, button: MouseButton) -> u32 {
+        let continues_sequence = self.last_click.map_or(false, |(last_time, last_position, last_button)| {
+            now.saturating_duration_since(last_time) <= self.multi_click_interval
+                && (position - last_position).length() <= self.multi_click_distance
+                && button == last_button
+        });
+
+        self.click_count = if continues_sequence { self.click_count + 1 } else { 1 };
+        self.last_click = Some((now, position, button));
+
+        self.click_count
+    }
+
+    pub fn get_click_count(&self) -> u32 {
+        self.click_count
+    }
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_click_counts_consecutive_clicks() {
+        // Arrange
+        let mut click_counter = ClickCounter::default();
+        let now = Instant::now();
+
+        // Act
+        let first_count = click_counter.register_click(now, Point::new(10.0, 10.0), MouseButton::Left);
+        let second_count = click_counter.register_click(now + Duration::from_millis(200), Point::new(11.0, 10.0), MouseButton::Left);
+        let late_count = click_counter.register_click(now + Duration::from_millis(1000), Point::new(11.0, 10.0), MouseButton::Left);
+
+        // Assert
+        assert_eq!(first_count, 1);
+        assert_eq!(second_count, 2);
+        assert_eq!(late_count, 1);
+    }
+
+    #[test]
+    fn test_register_click_counts_triple_clicks_of_one_button() {
+        // Arrange
+        let mouse_config = MouseConfiguration { multi_click_interval_ms: 300, ..Default::default() };
+        let mut click_counter = ClickCounter::new(&mouse_config);
+        let now = Instant::now();
+        let position = Point::new(10.0, 10.0);
+
+        // Act
+        click_counter.register_click(now, position, MouseButton::Left);
+        click_counter.register_click(now + Duration::from_millis(250), position, MouseButton::Left);
+        let triple_count = click_counter.register_click(now + Duration::from_millis(500), position, MouseButton::Left);
+        let right_count = click_counter.register_click(now + Duration::from_millis(600), position, MouseButton::Right);
+        let slow_count = click_counter.register_click(now + Duration::from_millis(950), position, MouseButton::Right);
+
+        // Assert
+        assert_eq!(triple_count, 3);
+        assert_eq!(right_count, 1);
+        assert_eq!(slow_count, 1);
+    }
+}
@@ -0,0 +1,43 @@
+use winit::{
+    event::{KeyEvent, Modifiers},
+    keyboard::{Key, NamedKey, PhysicalKey}
+};
+
+use crate::rendering::elements::element::{KeyModifiers, KeyboardEvent};
+
+
+// Converts winit's keyboard input into the platform independent payload of keyboard events
+pub fn to_keyboard_event(key_event: &KeyEvent, modifiers: &Modifiers) -> KeyboardEvent {
+    KeyboardEvent {
+        key: to_key_name(&key_event.logical_key),
+        code: match key_event.physical_key {
+            PhysicalKey::Code(key_code) => format!("{:?}", key_code), // Named as in the DOM, eg. "KeyA"
+            PhysicalKey::Unidentified(_) => "Unidentified".to_string(),
+        },
+        text: key_event.text.as_ref().map(|text| text.to_string()),
+        modifiers: to_key_modifiers(modifiers),
+        repeat: key_event.repeat,
+    }
+}
+
+// The modifier keys held, also given with mouse events
+pub fn to_key_modifiers(modifiers: &Modifiers) -> KeyModifiers {
+    let modifiers_state = modifiers.state();
+
+    KeyModifiers {
+        shift: modifiers_state.shift_key(),
+        ctrl: modifiers_state.control_key(),
+        alt: modifiers_state.alt_key(),
+        meta: modifiers_state.super_key(),
+    }
+}
+
+fn to_key_name(logical_key: &Key) -> String {
+    match logical_key {
+        Key::Character(character) => character.to_string(),
+        Key::Named(NamedKey::Space) => " ".to_string(), // As in the DOM
+        Key::Named(named_key) => format!("{:?}", named_key),
+        Key::Dead(_) => "Dead".to_string(),
+        Key::Unidentified(_) => "Unidentified".to_string(),
+    }
+}
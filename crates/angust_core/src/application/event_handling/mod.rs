@@ -1 +1,6 @@
 pub mod scrollbar_movement_handler;
+pub mod keyboard_event_converter;
+pub mod click_counter;
+pub mod cursor_icon_converter;
+pub mod mouse_button_converter;
+pub mod wheel_event_converter;
@@ -0,0 +1,14 @@
+use winit::event::MouseButton;
+
+use crate::rendering::elements::mouse_event::MouseButton as EventMouseButton;
+
+
+// Converts winit's mouse buttons into the buttons of mouse events, the back and forward buttons being ignored
+pub fn to_mouse_button(button: MouseButton) -> Option<EventMouseButton> {
+    match button {
+        MouseButton::Left => Some(EventMouseButton::Left),
+        MouseButton::Right => Some(EventMouseButton::Right),
+        MouseButton::Middle => Some(EventMouseButton::Middle),
+        _ => None,
+    }
+}
@@ -1,73 +1,195 @@
 use skia_safe::{Contains, Point, Rect};
 
-use crate::rendering::elements::{common_types::Position, container::Container, element::{Element, EventType}};
+use crate::rendering::elements::{common_types::{Position, ScrollbarAxis, ScrollbarState, Size}, container::Container, element::{Element, EventType}};
 
 
+/*
+ * Scrolls an overflowing container by dragging the thumb of one of its scrollbars, clicking their tracks or rolling the wheel.
+ * The wheel scrolls vertically, falling back to the horizontal axis when only that one overflows,
+ * and horizontal wheel events (eg. shift+wheel) scroll horizontally.
+ */
 pub fn handle_scrollbar_movement(
     container: &mut Container,
     cursor_position: Point,
     event_type: &EventType,
 ) {
-    if !container.scrollbar_state.is_overflowing.horizontal {
-        return; // Implement vertical scrollbar handling later
+    let is_overflowing = container.scrollbar_state.is_overflowing;
+    if !is_overflowing.horizontal && !is_overflowing.vertical {
+        return;
     }
-    
-    let thumb_width = container.get_size().width * container.scrollbar_state.thumb_scrollbar_width_ratio;
-    let track_width = container.get_size().width;
 
     let container_rect = Rect::from_point_and_size(
         Point::new(container.get_position().x, container.get_position().y),
         (container.get_size().width, container.get_size().height)
     );
-    let scrollbar_rect = Rect::from_point_and_size(
-        Point::new(container.get_position().x, container.get_position().y + container.get_size().height - container.scrollbar_state.scrollbar_thickness),
-        (container.get_size().width, container.scrollbar_state.scrollbar_thickness)
-    );
-    
-    let thumb_position_x = container.get_position().x + container.scrollbar_state.current_scroll_position.x * (track_width - thumb_width);
-    let thumb_rect = Rect::from_point_and_size(
-        Point::new(thumb_position_x, container.get_position().y + container.get_size().height - container.scrollbar_state.scrollbar_thickness),
-        (thumb_width, container.scrollbar_state.scrollbar_thickness)
-    );
 
     match event_type {
         EventType::MouseDown => {
-            if thumb_rect.contains(cursor_position) {
-                container.scrollbar_state.is_dragging = true;
-                container.scrollbar_state.drag_start_position = Position {
-                    x: cursor_position.x - thumb_position_x,
-                    y: cursor_position.y
-                };
-            } else if scrollbar_rect.contains(cursor_position) {
-                // Calculate new thumb position based on where the user clicked
-                let new_thumb_center = cursor_position.x - container.get_position().x;
-                let new_position = (new_thumb_center - thumb_width / 2.0) / (track_width - thumb_width);
-                container.scrollbar_state.current_scroll_position.x = new_position.clamp(0.0, 1.0);
-
-                container.allocate_space(container.get_position(), container.get_size());
+            for axis in [ScrollbarAxis::Vertical, ScrollbarAxis::Horizontal] {
+                if handle_scrollbar_press(container, cursor_position, axis) {
+                    break;
+                }
             }
         }
         EventType::MouseDrag => {
-            if container.scrollbar_state.is_dragging {
-                let adjusted_cursor_x = cursor_position.x - container.scrollbar_state.drag_start_position.x;
-                let new_x = adjusted_cursor_x / (track_width - thumb_width);
-                container.scrollbar_state.current_scroll_position.x = new_x.clamp(0.0, 1.0);
-    
-                container.allocate_space(container.get_position(), container.get_size());
+            if let Some(axis) = container.scrollbar_state.dragged_axis {
+                let (track_rect, thumb_rect) = get_scrollbar_rects(container.get_position(), container.get_size(), &container.scrollbar_state, axis);
+                let drag_start_position = container.scrollbar_state.drag_start_position;
+                let thumb_start = get_axis_value(cursor_position.x, cursor_position.y, axis) - get_axis_value(drag_start_position.x, drag_start_position.y, axis);
+                let new_position = compute_scroll_position(track_rect, thumb_rect, thumb_start, axis);
+                set_scroll_position(container, axis, new_position);
             }
         }
         EventType::MouseUp => {
-            container.scrollbar_state.is_dragging = false;
+            container.scrollbar_state.dragged_axis = None;
         }
         EventType::MouseRoll(delta) => {
             if container_rect.contains(cursor_position) {
-                let adjustment_factor = 0.01;
-                let new_position = container.scrollbar_state.current_scroll_position.x - delta * adjustment_factor;
-                container.scrollbar_state.current_scroll_position.x = new_position.clamp(0.0, 1.0);
-                
-                container.allocate_space(container.get_position(), container.get_size());
+                let axis = if is_overflowing.vertical { ScrollbarAxis::Vertical } else { ScrollbarAxis::Horizontal };
+                scroll_by_wheel(container, axis, *delta);
+            }
+        }
+        EventType::MouseRollHorizontal(delta) => {
+            if container_rect.contains(cursor_position) && is_overflowing.horizontal {
+                scroll_by_wheel(container, ScrollbarAxis::Horizontal, *delta);
             }
         }
         _ => {}
     }
-}
\ No newline at end of file
+}
+
+// Starts dragging the thumb, or jumps to the clicked point of the track. Returns whether the scrollbar was hit
+fn handle_scrollbar_press(container: &mut Container, cursor_position: Point, axis: ScrollbarAxis) -> bool {
+    if !is_scrollbar_visible(&container.scrollbar_state, axis) {
+        return false;
+    }
+
+    let (track_rect, thumb_rect) = get_scrollbar_rects(container.get_position(), container.get_size(), &container.scrollbar_state, axis);
+
+    if thumb_rect.contains(cursor_position) {
+        container.scrollbar_state.dragged_axis = Some(axis);
+        container.scrollbar_state.drag_start_position = Position {
+            x: cursor_position.x - thumb_rect.left,
+            y: cursor_position.y - thumb_rect.top,
+        };
+        container.scrollbar_state.drag_start_scroll_position = container.scrollbar_state.current_scroll_position;
+        return true;
+    }
+    if track_rect.contains(cursor_position) {
+        // Center the thumb on the clicked point
+        let thumb_length = get_axis_value(thumb_rect.width(), thumb_rect.height(), axis);
+        let thumb_start = get_axis_value(cursor_position.x, cursor_position.y, axis) - thumb_length / 2.0;
+        let new_position = compute_scroll_position(track_rect, thumb_rect, thumb_start, axis);
+        set_scroll_position(container, axis, new_position);
+        return true;
+    }
+
+    false
+}
+
+fn scroll_by_wheel(container: &mut Container, axis: ScrollbarAxis, delta: f32) {
+    let adjustment_factor = 0.01;
+    let scroll_position = container.scrollbar_state.current_scroll_position;
+    let current_position = get_axis_value(scroll_position.x, scroll_position.y, axis);
+    set_scroll_position(container, axis, current_position - delta * adjustment_factor);
+}
+
+fn set_scroll_position(container: &mut Container, axis: ScrollbarAxis, new_position: f32) {
+    let mut scroll_position = container.scrollbar_state.current_scroll_position;
+    match axis {
+        ScrollbarAxis::Horizontal => scroll_position.x = new_position,
+        ScrollbarAxis::Vertical => scroll_position.y = new_position,
+    }
+
+    container.set_scroll_position(scroll_position);
+}
+
+/*
+ * Track and thumb of the container's horizontal (bottom edge) or vertical (right edge) scrollbar.
+ * When both axes overflow, the tracks stop short of the bottom-right corner so that they don't overlap.
+ */
+pub fn get_scrollbar_rects(position: Position, size: Size, scrollbar_state: &ScrollbarState, axis: ScrollbarAxis) -> (Rect, Rect) {
+    let thickness = scrollbar_state.scrollbar_thickness;
+    let scroll_position = scrollbar_state.current_scroll_position;
+
+    match axis {
+        ScrollbarAxis::Horizontal => {
+            let corner = if scrollbar_state.is_overflowing.vertical { thickness } else { 0.0 };
+            let track_width = (size.width - corner).max(0.0);
+            let thumb_width = track_width * scrollbar_state.thumb_scrollbar_width_ratio.min(1.0);
+            let track_top = position.y + size.height - thickness;
+
+            let track_rect = Rect::from_xywh(position.x, track_top, track_width, thickness);
+            let thumb_rect = Rect::from_xywh(position.x + scroll_position.x * (track_width - thumb_width), track_top, thumb_width, thickness);
+            (track_rect, thumb_rect)
+        }
+        ScrollbarAxis::Vertical => {
+            let corner = if scrollbar_state.is_overflowing.horizontal { thickness } else { 0.0 };
+            let track_height = (size.height - corner).max(0.0);
+            let thumb_height = track_height * scrollbar_state.thumb_scrollbar_height_ratio.min(1.0);
+            let track_left = position.x + size.width - thickness;
+
+            let track_rect = Rect::from_xywh(track_left, position.y, thickness, track_height);
+            let thumb_rect = Rect::from_xywh(track_left, position.y + scroll_position.y * (track_height - thumb_height), thickness, thumb_height);
+            (track_rect, thumb_rect)
+        }
+    }
+}
+
+pub fn is_scrollbar_visible(scrollbar_state: &ScrollbarState, axis: ScrollbarAxis) -> bool {
+    match axis {
+        ScrollbarAxis::Horizontal => scrollbar_state.is_overflowing.horizontal && scrollbar_state.thumb_scrollbar_width_ratio < 1.0,
+        ScrollbarAxis::Vertical => scrollbar_state.is_overflowing.vertical && scrollbar_state.thumb_scrollbar_height_ratio < 1.0,
+    }
+}
+
+// Scroll position (between 0.0 and 1.0) placing the thumb's start at thumb_start
+fn compute_scroll_position(track_rect: Rect, thumb_rect: Rect, thumb_start: f32, axis: ScrollbarAxis) -> f32 {
+    let track_start = get_axis_value(track_rect.left, track_rect.top, axis);
+    let free_length = get_axis_value(track_rect.width(), track_rect.height(), axis) - get_axis_value(thumb_rect.width(), thumb_rect.height(), axis);
+    if free_length <= 0.0 {
+        return 0.0;
+    }
+
+    (thumb_start - track_start) / free_length
+}
+
+fn get_axis_value(x: f32, y: f32, axis: ScrollbarAxis) -> f32 {
+    match axis {
+        ScrollbarAxis::Horizontal => x,
+        ScrollbarAxis::Vertical => y,
+    }
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use crate::rendering::elements::styles::Directions;
+
+    use super::*;
+
+    #[test]
+    fn test_scrollbar_rects_leave_corner_when_both_axes_overflow() {
+        // Arrange
+        let scrollbar_state = ScrollbarState {
+            thumb_scrollbar_width_ratio: 0.5,
+            thumb_scrollbar_height_ratio: 0.25,
+            is_overflowing: Directions { horizontal: true, vertical: true },
+            current_scroll_position: Position { x: 0.0, y: 1.0 },
+            ..Default::default()
+        };
+        let position = Position { x: 10.0, y: 20.0 };
+        let size = Size { width: 210.0, height: 410.0 };
+
+        // Act
+        let (horizontal_track, horizontal_thumb) = get_scrollbar_rects(position, size, &scrollbar_state, ScrollbarAxis::Horizontal);
+        let (vertical_track, vertical_thumb) = get_scrollbar_rects(position, size, &scrollbar_state, ScrollbarAxis::Vertical);
+
+        // Assert
+        assert_eq!(horizontal_track, Rect::from_xywh(10.0, 420.0, 200.0, 10.0));
+        assert_eq!(horizontal_thumb, Rect::from_xywh(10.0, 420.0, 100.0, 10.0));
+        assert_eq!(vertical_track, Rect::from_xywh(210.0, 20.0, 10.0, 400.0));
+        assert_eq!(vertical_thumb, Rect::from_xywh(210.0, 320.0, 10.0, 100.0));
+    }
+}
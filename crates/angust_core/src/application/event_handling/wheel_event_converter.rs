@@ -0,0 +1,47 @@
+use winit::event::MouseScrollDelta;
+
+use crate::rendering::elements::element::EventType;
+
+
+/*
+ * Converts winit's wheel deltas (lines or physical pixels) into a wheel event scrolling along the dominant axis, in logical pixels.
+ * Holding shift turns vertical wheel rolls into horizontal ones.
+ */
+pub fn to_wheel_event_type(delta: MouseScrollDelta, scale_factor: f32, is_shift_pressed: bool) -> EventType {
+    let (mut delta_x, mut delta_y) = match delta {
+        MouseScrollDelta::LineDelta(x, y) => (x * 5.0, y * 5.0),
+        MouseScrollDelta::PixelDelta(position) => (position.x as f32 / scale_factor, position.y as f32 / scale_factor),
+    };
+
+    if is_shift_pressed && delta_x == 0.0 {
+        delta_x = delta_y;
+        delta_y = 0.0;
+    }
+
+    if delta_x.abs() > delta_y.abs() {
+        EventType::MouseRollHorizontal(delta_x)
+    } else {
+        EventType::MouseRoll(delta_y)
+    }
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shift_turns_vertical_roll_horizontal() {
+        // Arrange
+        let delta = MouseScrollDelta::LineDelta(0.0, -2.0);
+
+        // Act
+        let vertical_event_type = to_wheel_event_type(delta, 1.0, false);
+        let horizontal_event_type = to_wheel_event_type(delta, 1.0, true);
+
+        // Assert
+        assert_eq!(vertical_event_type, EventType::MouseRoll(-10.0));
+        assert_eq!(horizontal_event_type, EventType::MouseRollHorizontal(-10.0));
+    }
+}
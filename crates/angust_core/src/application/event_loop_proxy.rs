@@ -3,7 +3,7 @@ use std::sync::Mutex;
 use once_cell::sync::Lazy;
 use winit::event_loop::EventLoopProxy;
 
-use crate::rendering::elements::service::async_manager::ClosureExecutor;
+use crate::rendering::{elements::service::async_manager::ClosureExecutor, scrolling::scroll_manager::ScrollRequest};
 
 
 static GLOBAL_PROXY: Lazy<Mutex<Option<EventLoopProxy<ApplicationEvent>>>> = Lazy::new(|| Mutex::new(None));
@@ -24,4 +24,6 @@ pub enum ApplicationEvent {
     StateChange(String),
     ExecuteTask(ClosureExecutor),
     RouteChange(String, String),
+    FocusChange(Option<String>), // Element to focus, None to blur
+    ScrollRequest(ScrollRequest),
 }
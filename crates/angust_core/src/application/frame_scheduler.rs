@@ -0,0 +1,82 @@
+use std::time::{Duration, Instant};
+
+use winit::event_loop::ControlFlow;
+
+
+/*
+ * Paces animation frames while some element or component requests them.
+ * Frames are scheduled at the target frame rate with ControlFlow::WaitUntil 
+ * (ControlFlow::Poll when running late); once nothing animates, the event loop goes back to ControlFlow::Wait.
+ */
+pub struct FrameScheduler {
+    frame_duration: Duration,
+    last_frame_time: Option<Instant>,
+}
+
+impl Default for FrameScheduler {
+    fn default() -> Self {
+        Self::new(60)
+    }
+}
+
+impl FrameScheduler {
+    pub fn new(frame_rate: u32) -> Self {
+        Self {
+            frame_duration: Duration::from_secs_f64(1.0 / frame_rate.max(1) as f64),
+            last_frame_time: None,
+        }
+    }
+
+    pub fn is_frame_due(&self, now: Instant) -> bool {
+        self.last_frame_time.map_or(true, |last_frame_time| now >= last_frame_time + self.frame_duration)
+    }
+
+    // Returns the seconds elapsed since the previous frame (0 for the first frame of an animation)
+    pub fn tick(&mut self, now: Instant) -> f32 {
+        let dt = self.last_frame_time
+            .map_or(0.0, |last_frame_time| now.saturating_duration_since(last_frame_time).as_secs_f32());
+        self.last_frame_time = Some(now);
+
+        dt
+    }
+
+    // Forgets the previous frame, so that an idle period doesn't show up as a huge dt
+    pub fn stop(&mut self) {
+        self.last_frame_time = None;
+    }
+
+    pub fn get_control_flow(&self, now: Instant) -> ControlFlow {
+        match self.last_frame_time {
+            Some(last_frame_time) if now < last_frame_time + self.frame_duration => {
+                ControlFlow::WaitUntil(last_frame_time + self.frame_duration)
+            },
+            _ => ControlFlow::Poll,
+        }
+    }
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frames_are_paced_at_frame_rate() {
+        // Arrange
+        let mut frame_scheduler = FrameScheduler::new(50);
+        let start = Instant::now();
+
+        // Act
+        let first_dt = frame_scheduler.tick(start);
+        let is_due_early = frame_scheduler.is_frame_due(start + Duration::from_millis(10));
+        let control_flow = frame_scheduler.get_control_flow(start + Duration::from_millis(10));
+        let second_dt = frame_scheduler.tick(start + Duration::from_millis(20));
+
+        // Assert
+        assert_eq!(first_dt, 0.0);
+        assert!(!is_due_early);
+        assert_eq!(control_flow, ControlFlow::WaitUntil(start + Duration::from_millis(20)));
+        assert!((second_dt - 0.02).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,165 @@
+use skia_safe::Point;
+
+use crate::{
+    parsing::css::stylesheet_parser::Stylesheet, 
+    rendering::{
+        elements::{
+            button::EventPropagationData, 
+            common_types::Size, 
+            element::{Element, EventType}, 
+            mouse_event::MouseEvent, 
+            styles::Cursor
+        }, 
+        renderer::Renderer, 
+        scrolling::scroll_manager::ScrollRequest
+    }
+};
+
+use super::{
+    angust_configuration::AngustConfiguration, 
+    resource_loader::configuration_loader::load_angust_configuration, 
+    ui_initializer::load_ui
+};
+
+
+/*
+ * Windowless counterpart of Application, rendering the UI onto a CPU raster surface.
+ * Goes through the same parsing, layout and element rendering code as the windowed application,
+ * but needs no display, GL context or event loop, which makes it usable in CI.
+ * Since there is no event loop, state changes are not picked up automatically:
+ * call react_to_state_change (followed by layout) before rendering the next frame.
+ */
+pub struct HeadlessApplication<State> {
+    pub state: State,
+
+    pub angust_config: AngustConfiguration,
+    pub stylesheet: Stylesheet,
+
+    pub renderer: Renderer,
+}
+
+impl<State> HeadlessApplication<State> {
+    // Initialization
+    pub fn new(initial_state: State, viewport_size: Size) -> Self {
+        let angust_config = load_angust_configuration();
+        let (ui_body, stylesheet) = load_ui(&angust_config);
+
+        Self::from_element(initial_state, angust_config, stylesheet, ui_body, viewport_size)
+    }
+
+    pub fn from_element(
+        initial_state: State, 
+        angust_config: AngustConfiguration, 
+        stylesheet: Stylesheet, 
+        ui_body: Box<dyn Element>, 
+        viewport_size: Size
+    ) -> Self {
+        let mut renderer = Renderer::new_headless(viewport_size, ui_body);
+        renderer.layout();
+
+        Self {
+            state: initial_state,
+            angust_config,
+            stylesheet,
+            renderer,
+        }
+    }
+
+    // Rendering
+    pub fn render_frame(&mut self) {
+        self.renderer.render_frame();
+    }
+
+    pub fn resize(&mut self, viewport_size: Size) {
+        self.renderer.resize_headless_surface(viewport_size);
+        self.renderer.layout();
+    }
+
+    // The viewport keeps its logical size, the rendered pixels are scaled
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        self.renderer.set_headless_scale_factor(scale_factor);
+    }
+
+    pub fn read_pixels(&mut self) -> Option<Vec<u8>> {
+        self.renderer.read_pixels()
+    }
+
+    pub fn encode_png(&mut self) -> Option<Vec<u8>> {
+        self.renderer.encode_png()
+    }
+
+    // Animation
+    /*
+     * Advances animations by dt seconds, as the windowed application does on each frame.
+     * Returns whether more frames are requested.
+     */
+    pub fn update(&mut self, dt: f32) -> bool {
+        self.renderer.update(dt);
+        self.renderer.layout();
+
+        self.renderer.needs_animation_frame()
+    }
+
+    // Event handling
+    pub fn handle_event(&mut self, cursor_position: Point, event_type: EventType) {
+        self.renderer.handle_event(cursor_position, event_type);
+    }
+
+    pub fn propagate_event(&mut self, cursor_position: Point, event_type: &EventType) -> Vec<EventPropagationData> {
+        self.renderer.propagate_event(cursor_position, event_type)
+    }
+
+    // Open overlays (eg. a select's popup) take the mouse events before the elements under them
+    pub fn propagate_overlay_event(&mut self, cursor_position: Point, event_type: &EventType) -> bool {
+        self.renderer.propagate_overlay_event(cursor_position, event_type)
+    }
+
+    // Runs the @on<event> handlers along the path to the element under the cursor, returning whether one prevented the default action
+    pub fn dispatch_mouse_event(&mut self, mouse_event: &MouseEvent) -> bool {
+        self.renderer.dispatch_mouse_event(mouse_event)
+    }
+
+    // Cursor shape requested by the element under the mouse, which a windowed application shows
+    pub fn get_cursor(&mut self) -> Cursor {
+        self.renderer.get_cursor()
+    }
+
+    pub fn dispatch_keyboard_event(&mut self, event_type: &EventType) {
+        self.renderer.dispatch_keyboard_event(event_type);
+    }
+
+    // Scrolling
+    /*
+     * Applies a scroll request (as sent by the scroll proxy in a windowed application), returning whether the element was found.
+     * Smooth scrolling advances with update.
+     */
+    pub fn scroll(&mut self, request: ScrollRequest) -> bool {
+        let is_found = self.renderer.handle_scroll_request(&request);
+        self.renderer.layout();
+        is_found
+    }
+
+    // Focus
+    pub fn get_focused_element_id(&self) -> Option<&String> {
+        self.renderer.get_focused_element_id()
+    }
+
+    // Focuses the element with the given id attribute, as FocusProxy::focus does
+    pub fn focus(&mut self, template_id: &str) -> bool {
+        self.renderer.focus_element(&template_id.to_string())
+    }
+
+    pub fn blur(&mut self) {
+        self.renderer.blur();
+    }
+
+    pub fn react_to_state_change(&mut self, component_id: String) {
+        self.renderer.react_to_state_change(component_id);
+        self.renderer.layout();
+    }
+
+    pub fn handle_route_change(&mut self, route: &String, component_name: &String) {
+        self.renderer.handle_route_change(route, component_name);
+        self.renderer.layout();
+    }
+}
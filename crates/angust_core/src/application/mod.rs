@@ -1,6 +1,9 @@
 pub mod application;
+pub mod application_builder;
+pub mod headless_application;
 pub mod angust_configuration;
 pub mod ui_initializer;
 pub mod resource_loader;
 pub mod event_handling;
-pub mod event_loop_proxy;
\ No newline at end of file
+pub mod event_loop_proxy;
+pub mod frame_scheduler;
\ No newline at end of file
@@ -1,7 +1,12 @@
-
 use kuchiki::NodeRef;
 
-use crate::parsing::html::html_parser::parse_html_content;
+use crate::{
+    parsing::{
+        css::{css_parser, stylesheet_parser::{self, Stylesheet}}, 
+        html::html_parser::{self, parse_html_content, ParsingContext}
+    }, 
+    rendering::elements::{component::state::reflectivity::NoState, element::Element}
+};
 
 use super::{angust_configuration::AngustConfiguration, resource_loader::{html_loader, stylesheet_loader}};
 
@@ -23,4 +28,20 @@ pub fn load_resources(angust_config: &AngustConfiguration) -> (NodeRef, String)
 
 
     (dom, stylesheets)
-}
\ No newline at end of file
+}
+
+/*
+ * Loads index.html and the global stylesheet and maps them to the Angust element tree.
+ * Shared by the windowed and headless applications, so both go through the same parsing path.
+ */
+pub fn load_ui(angust_config: &AngustConfiguration) -> (Box<dyn Element>, Stylesheet) {
+    let (dom, stylesheets) = load_resources(angust_config);
+    let stylesheet = stylesheet_parser::parse_stylesheet(&stylesheets);
+    css_parser::register_keyframes(&stylesheet);
+    let mut parsing_context: ParsingContext<NoState> = ParsingContext::new(Some(angust_config.clone()), Some(stylesheet.clone()), None, None, None, None, None, None);
+    
+    let ui_body = html_parser::map_dom_to_elements::<NoState>(&dom, None, &mut parsing_context)
+        .unwrap_or_else(|e| panic!("Failed to map DOM to elements: {:?}", e));
+
+    (ui_body, stylesheet)
+}
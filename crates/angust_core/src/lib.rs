@@ -4,4 +4,5 @@
 pub mod application;
 pub mod rendering;
 pub mod window;
-pub mod parsing;
\ No newline at end of file
+pub mod parsing;
+pub mod testing;
\ No newline at end of file
@@ -0,0 +1,314 @@
+use crate::rendering::{
+    animation::keyframes_registry::get_or_create_keyframes_id, 
+    elements::styles::{
+        AnimatableProperty, Animation, AnimationDirection, AnimationFillMode, Dimension, IterationCount, 
+        Styles, TimingFunction, Transform, Transition, TransitionProperty, Transitions
+    }
+};
+
+use super::dimension_parser::parse_dimension;
+
+
+pub fn update_animation_style(styles: &mut Styles, key: &str, value: &str) {
+    match key {
+        "transform" => styles.transform = parse_transform(value),
+        "transition" => styles.transitions = parse_transitions(value),
+        "animation" => styles.animation = parse_animation(value),
+        _ => println!("Unhandled animation property: {}", key),
+    }
+}
+
+/*
+ * Parses e.g. "translate(10px, 20%) scale(1.5) rotate(45deg)".
+ * The functions are combined as translate, then rotate, then scale, around the element's center.
+ */
+pub fn parse_transform(value: &str) -> Option<Transform> {
+    let value = value.trim();
+    if value == "none" {
+        return None;
+    }
+
+    let mut transform = Transform::default();
+    for function in split_outside_parentheses(value, ' ') {
+        let (name, arguments) = parse_function(&function)?;
+
+        match (name.as_str(), arguments.as_slice()) {
+            ("translate", [x]) => transform.translate_x = parse_dimension(x)?,
+            ("translate", [x, y]) => {
+                transform.translate_x = parse_dimension(x)?;
+                transform.translate_y = parse_dimension(y)?;
+            },
+            ("translateX", [x]) => transform.translate_x = parse_dimension(x)?,
+            ("translateY", [y]) => transform.translate_y = parse_dimension(y)?,
+            ("scale", [scale]) => {
+                transform.scale_x = scale.parse().ok()?;
+                transform.scale_y = transform.scale_x;
+            },
+            ("scale", [scale_x, scale_y]) => {
+                transform.scale_x = scale_x.parse().ok()?;
+                transform.scale_y = scale_y.parse().ok()?;
+            },
+            ("scaleX", [scale_x]) => transform.scale_x = scale_x.parse().ok()?,
+            ("scaleY", [scale_y]) => transform.scale_y = scale_y.parse().ok()?,
+            ("rotate", [angle]) => transform.rotate = parse_angle(angle)?,
+            _ => {
+                println!("Unhandled transform function: {}", function);
+                return None;
+            },
+        }
+    }
+
+    Some(transform)
+}
+
+// Parses e.g. "background-color 200ms ease-in-out, transform 0.3s linear 100ms"
+pub fn parse_transitions(value: &str) -> Option<Transitions> {
+    let value = value.trim();
+    if value == "none" {
+        return None;
+    }
+
+    let mut transitions = Transitions::default();
+    for transition_value in split_outside_parentheses(value, ',') {
+        let mut transition = Transition::default();
+        let mut times = Vec::new();
+
+        for token in split_outside_parentheses(&transition_value, ' ') {
+            if let Some(time) = parse_time(&token) {
+                times.push(time);
+            } else if let Some(timing_function) = parse_timing_function(&token) {
+                transition.timing_function = timing_function;
+            } else if token == "all" {
+                transition.property = TransitionProperty::All;
+            } else if let Some(property) = AnimatableProperty::from_css_name(&token) {
+                transition.property = TransitionProperty::Property(property);
+            } else {
+                println!("Unhandled transition value: {}", token);
+            }
+        }
+
+        transition.duration = times.get(0).copied().unwrap_or(0.0);
+        transition.delay = times.get(1).copied().unwrap_or(0.0);
+        transitions.push(transition).ok()?; // Too many transitions
+    }
+
+    Some(transitions)
+}
+
+// Parses e.g. "spin 1s linear infinite" (the first time being the duration, the second the delay)
+pub fn parse_animation(value: &str) -> Option<Animation> {
+    let value = value.trim();
+    if value == "none" {
+        return None;
+    }
+
+    let mut name = None;
+    let mut times = Vec::new();
+    let mut timing_function = TimingFunction::default();
+    let mut iteration_count = IterationCount::default();
+    let mut direction = AnimationDirection::default();
+    let mut fill_mode = AnimationFillMode::default();
+
+    for token in split_outside_parentheses(value, ' ') {
+        if let Some(time) = parse_time(&token) {
+            times.push(time);
+        } else if let Some(parsed_timing_function) = parse_timing_function(&token) {
+            timing_function = parsed_timing_function;
+        } else if token == "infinite" {
+            iteration_count = IterationCount::Infinite;
+        } else if let Ok(count) = token.parse::<f32>() {
+            iteration_count = IterationCount::Finite(count.max(0.0));
+        } else if let Some(parsed_direction) = parse_animation_direction(&token) {
+            direction = parsed_direction;
+        } else if let Some(parsed_fill_mode) = parse_animation_fill_mode(&token) {
+            fill_mode = parsed_fill_mode;
+        } else {
+            name = Some(token);
+        }
+    }
+
+    Some(Animation {
+        keyframes_id: get_or_create_keyframes_id(&name?),
+        duration: times.get(0).copied().unwrap_or(0.0),
+        timing_function,
+        delay: times.get(1).copied().unwrap_or(0.0),
+        iteration_count,
+        direction,
+        fill_mode,
+    })
+}
+
+// Parses keyframe selectors, e.g. "from", "50%" or "0%, 100%"
+pub fn parse_keyframe_offsets(selector: &str) -> Vec<f32> {
+    selector.split(',')
+        .filter_map(|offset| match offset.trim() {
+            "from" => Some(0.0),
+            "to" => Some(1.0),
+            percentage => percentage.strip_suffix('%')
+                .and_then(|number| number.trim().parse::<f32>().ok())
+                .map(|number| (number / 100.0).clamp(0.0, 1.0)),
+        })
+        .collect()
+}
+
+// Values
+fn parse_time(value: &str) -> Option<f32> {
+    if let Some(milliseconds) = value.strip_suffix("ms") {
+        return milliseconds.parse::<f32>().ok().map(|milliseconds| milliseconds / 1000.0);
+    }
+
+    value.strip_suffix('s')?.parse::<f32>().ok()
+}
+
+fn parse_angle(value: &str) -> Option<f32> {
+    if let Some(degrees) = value.strip_suffix("deg") {
+        return degrees.parse().ok();
+    }
+    if let Some(radians) = value.strip_suffix("rad") {
+        return radians.parse::<f32>().ok().map(f32::to_degrees);
+    }
+    if let Some(turns) = value.strip_suffix("turn") {
+        return turns.parse::<f32>().ok().map(|turns| turns * 360.0);
+    }
+
+    value.parse::<f32>().ok().filter(|angle| *angle == 0.0)
+}
+
+fn parse_timing_function(value: &str) -> Option<TimingFunction> {
+    match value {
+        "linear" => return Some(TimingFunction::Linear),
+        "ease" => return Some(TimingFunction::Ease),
+        "ease-in" => return Some(TimingFunction::EaseIn),
+        "ease-out" => return Some(TimingFunction::EaseOut),
+        "ease-in-out" => return Some(TimingFunction::EaseInOut),
+        _ => {}
+    }
+
+    let (name, arguments) = parse_function(value)?;
+    match (name.as_str(), arguments.as_slice()) {
+        ("cubic-bezier", [x1, y1, x2, y2]) => Some(TimingFunction::CubicBezier(
+            x1.parse::<f32>().ok()?.clamp(0.0, 1.0), y1.parse().ok()?, 
+            x2.parse::<f32>().ok()?.clamp(0.0, 1.0), y2.parse().ok()?
+        )),
+        ("steps", [steps]) => Some(TimingFunction::Steps(steps.parse().ok()?)),
+        _ => None,
+    }
+}
+
+fn parse_animation_direction(value: &str) -> Option<AnimationDirection> {
+    match value {
+        "normal" => Some(AnimationDirection::Normal),
+        "reverse" => Some(AnimationDirection::Reverse),
+        "alternate" => Some(AnimationDirection::Alternate),
+        "alternate-reverse" => Some(AnimationDirection::AlternateReverse),
+        _ => None,
+    }
+}
+
+fn parse_animation_fill_mode(value: &str) -> Option<AnimationFillMode> {
+    match value {
+        "none" => Some(AnimationFillMode::None),
+        "forwards" => Some(AnimationFillMode::Forwards),
+        "backwards" => Some(AnimationFillMode::Backwards),
+        "both" => Some(AnimationFillMode::Both),
+        _ => None,
+    }
+}
+
+// Utils
+// Splits e.g. "name(a, b)" into ("name", ["a", "b"])
+pub fn parse_function(value: &str) -> Option<(String, Vec<String>)> {
+    let arguments_start = value.find('(')?;
+    let arguments = value[arguments_start + 1..].strip_suffix(')')?;
+
+    Some((
+        value[..arguments_start].trim().to_string(),
+        arguments.split(',').map(|argument| argument.trim().to_string()).collect(),
+    ))
+}
+
+// Splits on the separator, ignoring the separators within parentheses (e.g. in cubic-bezier(...))
+pub fn split_outside_parentheses(value: &str, separator: char) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current_part = String::new();
+    let mut depth = 0;
+
+    for character in value.chars() {
+        match character {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+
+        if character == separator && depth == 0 {
+            parts.push(current_part.trim().to_string());
+            current_part.clear();
+        } else {
+            current_part.push(character);
+        }
+    }
+    parts.push(current_part.trim().to_string());
+
+    parts.into_iter().filter(|part| !part.is_empty()).collect()
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use crate::rendering::elements::styles::MAX_TRANSITIONS;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_transitions() {
+        // Arrange
+        let value = "background-color 200ms ease-in-out, transform 0.3s cubic-bezier(0.1, 0.7, 1.0, 0.1) 50ms";
+
+        // Act
+        let transitions = parse_transitions(value).unwrap();
+
+        // Assert
+        let transitions: Vec<&Transition> = transitions.iter().collect();
+        assert_eq!(transitions.len(), 2);
+        assert_eq!(transitions[0], &Transition {
+            property: TransitionProperty::Property(AnimatableProperty::BackgroundColor),
+            duration: 0.2,
+            timing_function: TimingFunction::EaseInOut,
+            delay: 0.0,
+        });
+        assert_eq!(transitions[1], &Transition {
+            property: TransitionProperty::Property(AnimatableProperty::Transform),
+            duration: 0.3,
+            timing_function: TimingFunction::CubicBezier(0.1, 0.7, 1.0, 0.1),
+            delay: 0.05,
+        });
+    }
+
+    #[test]
+    fn test_parse_transitions_rejects_too_many() {
+        // Arrange
+        let value = vec!["opacity 1s"; MAX_TRANSITIONS + 1].join(", ");
+
+        // Act
+        let transitions = parse_transitions(&value);
+
+        // Assert
+        assert!(transitions.is_none());
+    }
+
+    #[test]
+    fn test_parse_transform() {
+        // Arrange
+        let value = "translate(10px, 20%) scale(1.5) rotate(0.25turn)";
+
+        // Act
+        let transform = parse_transform(value).unwrap();
+
+        // Assert
+        assert_eq!(transform.translate_x, parse_dimension("10px").unwrap());
+        assert_eq!(transform.translate_y, parse_dimension("20%").unwrap());
+        assert_eq!((transform.scale_x, transform.scale_y), (1.5, 1.5));
+        assert_eq!(transform.rotate, 90.0);
+    }
+}
@@ -1,28 +1,209 @@
 use skia_safe::Color;
 
-use crate::rendering::elements::styles::{Border, Styles};
+use crate::rendering::elements::styles::{Border, BorderRadius, BorderSide, BorderStyle, BoxShadow, BoxShadows, Cursor, Dimension, Filter, Filters, Styles, Unit};
 
-use super::dimension_parser::parse_dimension;
+use super::{animation_parser::{parse_function, split_outside_parentheses}, dimension_parser::parse_dimension};
 
 
 pub fn update_appearance_style(styles: &mut Styles, key: &str, value: &str) {
     match key {
         "background-color" => styles.background_color = parse_color(value),
         "color" => styles.text_color = parse_color(value),
-        "border-width" => styles.border = Some(Border {
-            width: parse_dimension(value).unwrap_or_default(),
-            color: styles.border.unwrap_or_default().color,
-            radius: styles.border.unwrap_or_default().radius,
-        }),
-        "border-color" => styles.border = Some(Border {
-            width: styles.border.unwrap_or_default().width,
-            color: parse_color(value).unwrap_or(Color::BLACK),
-            radius: styles.border.unwrap_or_default().radius,
-        }),
+        "border" | "border-top" | "border-right" | "border-bottom" | "border-left"
+        | "border-width" | "border-color" | "border-style" | "border-radius" => update_border(styles, key, value),
+        "box-shadow" => styles.box_shadows = parse_box_shadows(value),
+        "opacity" => styles.opacity = value.trim().parse::<f32>().ok().map(|opacity| opacity.clamp(0.0, 1.0)),
+        "filter" => styles.filters = parse_filters(value),
+        "backdrop-filter" => styles.backdrop_filters = parse_filters(value),
+        "cursor" => styles.cursor = parse_cursor(value),
         _ => println!("Unhandled color property: {}", key),
     }
 }
 
+/*
+ * Updates the border with a shorthand (e.g. "border-top: 2px dashed rgb(255, 0, 0)") or one of its properties,
+ * the latter taking one to four values, in top, right, bottom, left order (top-left, top-right, bottom-right, bottom-left for the radius).
+ * Invalid values leave the border unchanged.
+ */
+fn update_border(styles: &mut Styles, key: &str, value: &str) {
+    let mut border = styles.border.unwrap_or_default();
+
+    match key {
+        "border" => if let Some(side) = parse_border_side(value) {
+            border = Border { radius: border.radius, ..Border::uniform(side) };
+        },
+        "border-top" => border.top = parse_border_side(value).unwrap_or(border.top),
+        "border-right" => border.right = parse_border_side(value).unwrap_or(border.right),
+        "border-bottom" => border.bottom = parse_border_side(value).unwrap_or(border.bottom),
+        "border-left" => border.left = parse_border_side(value).unwrap_or(border.left),
+        "border-width" => if let Some(widths) = parse_side_values(value, parse_dimension) {
+            border.get_sides_mut().into_iter().zip(widths).for_each(|(side, width)| side.width = width);
+        },
+        "border-color" => if let Some(colors) = parse_side_values(value, parse_color) {
+            border.get_sides_mut().into_iter().zip(colors).for_each(|(side, color)| side.color = Some(color));
+        },
+        "border-style" => if let Some(border_styles) = parse_side_values(value, parse_border_style) {
+            border.get_sides_mut().into_iter().zip(border_styles).for_each(|(side, style)| side.style = style);
+        },
+        "border-radius" => if let Some([top_left, top_right, bottom_right, bottom_left]) = parse_side_values(value, parse_dimension) {
+            border.radius = BorderRadius { top_left, top_right, bottom_right, bottom_left };
+        },
+        _ => {}
+    }
+
+    styles.border = Some(border);
+}
+
+/*
+ * Width, style and color in any order, e.g. "1px solid rgb(0, 0, 0)". As in CSS, the width defaults to 3px and the color to the text color,
+ * which is resolved when drawing, once the element's styles are final.
+ */
+fn parse_border_side(value: &str) -> Option<BorderSide> {
+    let mut side = BorderSide {
+        width: Dimension { value: 3.0, unit: Unit::Px },
+        color: None,
+        style: BorderStyle::Solid,
+    };
+
+    for token in split_outside_parentheses(value, ' ') {
+        if let Some(style) = parse_border_style(&token) {
+            side.style = style;
+        } else if let Some(width) = parse_dimension(&token) {
+            side.width = width;
+        } else if let Some(color) = parse_color(&token) {
+            side.color = Some(color);
+        } else {
+            return None;
+        }
+    }
+
+    Some(side)
+}
+
+fn parse_border_style(value: &str) -> Option<BorderStyle> {
+    match value.trim() {
+        "none" | "hidden" => Some(BorderStyle::None),
+        "solid" => Some(BorderStyle::Solid),
+        "dashed" => Some(BorderStyle::Dashed),
+        "dotted" => Some(BorderStyle::Dotted),
+        _ => None,
+    }
+}
+
+// Expands one to four values to the four sides, as for margins: "a b" is a for top and bottom, b for right and left
+fn parse_side_values<T: Copy>(value: &str, parse: impl Fn(&str) -> Option<T>) -> Option<[T; 4]> {
+    let values = split_outside_parentheses(value, ' ').iter()
+        .map(|token| parse(token))
+        .collect::<Option<Vec<T>>>()?;
+
+    match values.as_slice() {
+        [all] => Some([*all; 4]),
+        [vertical, horizontal] => Some([*vertical, *horizontal, *vertical, *horizontal]),
+        [top, horizontal, bottom] => Some([*top, *horizontal, *bottom, *horizontal]),
+        [top, right, bottom, left] => Some([*top, *right, *bottom, *left]),
+        _ => None,
+    }
+}
+
+/*
+ * Parses a comma-separated list of shadows, e.g. "0px 2px 4px rgba(0, 0, 0, 0.2), inset 0px 0px 0px 1px rgb(255, 255, 255)".
+ * Each one has two to four lengths (offsets, blur and spread radii), and optionally a color (the text color by default) and the inset keyword.
+ * The text color is resolved when drawing, once the element's styles are final.
+ */
+fn parse_box_shadows(value: &str) -> Option<BoxShadows> {
+    if value.trim() == "none" {
+        return None;
+    }
+
+    let mut box_shadows = BoxShadows::default();
+    for shadow_value in split_outside_parentheses(value, ',') {
+        let mut box_shadow = BoxShadow::default();
+        let mut lengths = Vec::new();
+
+        for token in split_outside_parentheses(&shadow_value, ' ') {
+            if token == "inset" {
+                box_shadow.inset = true;
+            } else if let Some(length) = parse_dimension(&token) {
+                lengths.push(length);
+            } else if let Some(color) = parse_color(&token) {
+                box_shadow.color = Some(color);
+            } else {
+                return None;
+            }
+        }
+
+        if lengths.len() < 2 || lengths.len() > 4 {
+            return None;
+        }
+        box_shadow.offset_x = lengths[0];
+        box_shadow.offset_y = lengths[1];
+        box_shadow.blur_radius = lengths.get(2).cloned().unwrap_or_default();
+        box_shadow.spread_radius = lengths.get(3).cloned().unwrap_or_default();
+
+        box_shadows.push(box_shadow).ok()?; // Too many shadows
+    }
+
+    Some(box_shadows)
+}
+
+// Parses e.g. "blur(4px) grayscale(50%) brightness(1.2)", the filters being applied in order
+fn parse_filters(value: &str) -> Option<Filters> {
+    if value.trim() == "none" {
+        return None;
+    }
+
+    let mut filters = Filters::default();
+    for function in split_outside_parentheses(value, ' ') {
+        let (name, arguments) = parse_function(&function)?;
+        let argument = arguments.first().map(String::as_str).unwrap_or_default();
+
+        // As in CSS, a missing argument applies the filter fully
+        let filter = match name.as_str() {
+            "blur" if argument.is_empty() => Filter::Blur(Dimension::default()),
+            "blur" => Filter::Blur(parse_dimension(argument)?),
+            "grayscale" => Filter::Grayscale(parse_amount(argument).unwrap_or(1.0).clamp(0.0, 1.0)),
+            "brightness" => Filter::Brightness(parse_amount(argument).unwrap_or(1.0).max(0.0)),
+            _ => return None,
+        };
+        filters.push(filter).ok()?; // Too many filters
+    }
+
+    Some(filters)
+}
+
+// Number or percentage, e.g. "0.5" or "50%"
+fn parse_amount(value: &str) -> Option<f32> {
+    match value.trim().strip_suffix('%') {
+        Some(percentage) => percentage.trim().parse::<f32>().ok().map(|percentage| percentage / 100.0),
+        None => value.trim().parse::<f32>().ok(),
+    }
+}
+
+fn parse_cursor(value: &str) -> Option<Cursor> {
+    match value.trim() {
+        "auto" | "default" => Some(Cursor::Default),
+        "pointer" => Some(Cursor::Pointer),
+        "text" => Some(Cursor::Text),
+        "move" => Some(Cursor::Move),
+        "grab" => Some(Cursor::Grab),
+        "grabbing" => Some(Cursor::Grabbing),
+        "not-allowed" => Some(Cursor::NotAllowed),
+        "wait" => Some(Cursor::Wait),
+        "progress" => Some(Cursor::Progress),
+        "help" => Some(Cursor::Help),
+        "crosshair" => Some(Cursor::Crosshair),
+        "ew-resize" => Some(Cursor::EwResize),
+        "ns-resize" => Some(Cursor::NsResize),
+        "nesw-resize" => Some(Cursor::NeswResize),
+        "nwse-resize" => Some(Cursor::NwseResize),
+        "col-resize" => Some(Cursor::ColResize),
+        "row-resize" => Some(Cursor::RowResize),
+        "zoom-in" => Some(Cursor::ZoomIn),
+        "zoom-out" => Some(Cursor::ZoomOut),
+        _ => None,
+    }
+}
+
 pub fn parse_color(value: &str) -> Option<Color> {
     // Handle rgb() and rgba() formats
     let trimmed_value = value.trim();
@@ -68,4 +249,88 @@ fn parse_rgba_color(value: &str) -> Option<Color> {
     } else {
         None
     }
-}
\ No newline at end of file
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use crate::rendering::elements::styles::MAX_FILTERS;
+
+    use super::*;
+
+    #[test]
+    fn test_update_border_with_shorthands_and_side_values() {
+        // Arrange
+        let mut styles = Styles::default();
+
+        // Act
+        update_appearance_style(&mut styles, "border", "2px dashed rgb(255, 0, 0)");
+        update_appearance_style(&mut styles, "border-left", "dotted 4px");
+        update_appearance_style(&mut styles, "border-color", "rgb(0, 0, 255) rgb(0, 255, 0)");
+        update_appearance_style(&mut styles, "border-radius", "10px 50%");
+
+        // Assert
+        let border = styles.border.unwrap();
+        assert_eq!(border.top, BorderSide { width: Dimension { value: 2.0, unit: Unit::Px }, color: Some(Color::BLUE), style: BorderStyle::Dashed });
+        assert_eq!(border.right, BorderSide { width: Dimension { value: 2.0, unit: Unit::Px }, color: Some(Color::GREEN), style: BorderStyle::Dashed });
+        assert_eq!(border.left, BorderSide { width: Dimension { value: 4.0, unit: Unit::Px }, color: Some(Color::GREEN), style: BorderStyle::Dotted });
+        assert_eq!(border.radius.top_left, Dimension { value: 10.0, unit: Unit::Px });
+        assert_eq!(border.radius.top_right, Dimension { value: 50.0, unit: Unit::Percent });
+        assert_eq!(border.radius.bottom_right, Dimension { value: 10.0, unit: Unit::Px });
+    }
+
+    #[test]
+    fn test_border_without_color_keeps_text_color_unresolved() {
+        // Arrange
+        let mut styles = Styles::default();
+
+        // Act
+        update_appearance_style(&mut styles, "border", "1px solid");
+        update_appearance_style(&mut styles, "color", "rgb(255, 0, 0)");
+
+        // Assert
+        assert_eq!(styles.border.unwrap().top.color, None); // Drawn with the final text color
+    }
+
+    #[test]
+    fn test_parse_box_shadows_and_filters() {
+        // Arrange
+        let mut styles = Styles::default();
+
+        // Act
+        update_appearance_style(&mut styles, "box-shadow", "0px 2px 4px rgba(0, 0, 0, 0.5), inset 1px 1px rgb(255, 0, 0)");
+        update_appearance_style(&mut styles, "filter", "blur(3px) grayscale(50%) brightness()");
+        update_appearance_style(&mut styles, "backdrop-filter", "blur(8px)");
+
+        // Assert
+        let box_shadows: Vec<&BoxShadow> = styles.box_shadows.as_ref().unwrap().iter().collect();
+        let px = |value: f32| Dimension { value, unit: Unit::Px };
+        assert_eq!(box_shadows, [
+            &BoxShadow {
+                offset_x: px(0.0), offset_y: px(2.0), blur_radius: px(4.0), spread_radius: Dimension::default(),
+                color: Some(Color::from_argb(127, 0, 0, 0)), inset: false,
+            },
+            &BoxShadow {
+                offset_x: px(1.0), offset_y: px(1.0), blur_radius: Dimension::default(), spread_radius: Dimension::default(),
+                color: Some(Color::RED), inset: true,
+            },
+        ]);
+        let filters: Vec<&Filter> = styles.filters.as_ref().unwrap().iter().collect();
+        assert_eq!(filters, [&Filter::Blur(px(3.0)), &Filter::Grayscale(0.5), &Filter::Brightness(1.0)]);
+        let backdrop_filters: Vec<&Filter> = styles.backdrop_filters.as_ref().unwrap().iter().collect();
+        assert_eq!(backdrop_filters, [&Filter::Blur(px(8.0))]);
+    }
+
+    #[test]
+    fn test_parse_filters_rejects_too_many() {
+        // Arrange
+        let value = vec!["grayscale(10%)"; MAX_FILTERS + 1].join(" ");
+
+        // Act
+        let filters = parse_filters(&value);
+
+        // Assert
+        assert!(filters.is_none());
+    }
+}
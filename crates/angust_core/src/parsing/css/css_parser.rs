@@ -1,8 +1,14 @@
 use kuchiki::Attributes;
 
-use crate::rendering::elements::styles::Styles;
+use crate::rendering::{
+    animation::keyframes_registry::{self, Keyframe, Keyframes}, 
+    elements::{pseudo_class_styles::{PseudoClass, PseudoClassStyles}, styles::{AnimatableProperty, Styles}}
+};
 
-use super::{appearance_parser::update_appearance_style, dimension_parser::update_dimension_style, layout_parser::update_layout_style, stylesheet_parser::Stylesheet, text_parser::update_text_style};
+use super::{
+    animation_parser::update_animation_style, appearance_parser::update_appearance_style, dimension_parser::update_dimension_style, 
+    layout_parser::update_layout_style, stylesheet_parser::{KeyframesRule, Property, Stylesheet}, text_parser::update_text_style
+};
 
 
 pub fn parse_styles(
@@ -10,14 +16,24 @@ pub fn parse_styles(
     parent_styles: Option<&Styles>, 
     stylesheet: &Option<Stylesheet>
 ) -> Styles {
-    let mut styles = Styles::default();
+    parse_styles_with_defaults(attributes, parent_styles, stylesheet, Styles::default())
+}
+
+// For elements with their own default appearance (eg. inputs), which classes and then inline styles override
+pub fn parse_styles_with_defaults(
+    attributes: &Attributes, 
+    parent_styles: Option<&Styles>, 
+    stylesheet: &Option<Stylesheet>,
+    default_styles: Styles,
+) -> Styles {
+    let mut styles = default_styles;
     
     if let Some(class_names) = attributes.get("class") {
-        styles = parse_class_styles(class_names, stylesheet);
+        styles = parse_class_styles(class_names, stylesheet, default_styles);
     }
 
     if let Some(style_attr) = attributes.get("style") {
-        styles = parse_inline_styles(style_attr);
+        styles = parse_inline_styles(style_attr, styles); // On top of the class styles
     }
 
     if let Some(parent) = parent_styles {
@@ -27,13 +43,13 @@ pub fn parse_styles(
     styles
 }
 
-fn parse_class_styles(class_names: &str, stylesheet_option: &Option<Stylesheet>) -> Styles {
+fn parse_class_styles(class_names: &str, stylesheet_option: &Option<Stylesheet>, default_styles: Styles) -> Styles {
     if stylesheet_option.is_none() {
-        return Styles::default();
+        return default_styles;
     }
     let stylesheet = stylesheet_option.as_ref().unwrap();
 
-    let mut styles = Styles::default();
+    let mut styles = default_styles;
 
     class_names.split_whitespace().for_each(|class_name| {
         if let Some(class_styles) = stylesheet.classes.iter()
@@ -48,8 +64,45 @@ fn parse_class_styles(class_names: &str, stylesheet_option: &Option<Stylesheet>)
     styles
 }
 
-fn parse_inline_styles(style_str: &str) -> Styles {
-    let mut styles = Styles::default();
+/*
+ * Gathers the rules of the element's classes that apply in a given interaction state, 
+ * eg. ".nav-button:focus" for class="nav-button".
+ */
+pub fn parse_pseudo_class_styles(attributes: &Attributes, stylesheet_option: &Option<Stylesheet>) -> PseudoClassStyles {
+    let mut pseudo_class_styles = PseudoClassStyles::default();
+
+    if attributes.get("class").is_none() || stylesheet_option.is_none() {
+        return pseudo_class_styles;
+    }
+    let class_names: Vec<&str> = attributes.get("class").unwrap().split_whitespace().collect();
+    let stylesheet = stylesheet_option.as_ref().unwrap();
+
+    for class in &stylesheet.classes {
+        let (class_name, pseudo_class_name) = match class.name.split_once(':') {
+            Some(names) => names,
+            None => continue,
+        };
+        if !class_names.contains(&class_name) {
+            continue;
+        }
+
+        match PseudoClass::from_css_name(pseudo_class_name) {
+            Some(pseudo_class) => pseudo_class_styles.add_rule(pseudo_class, class.properties.clone()),
+            None => println!("Unknown pseudo-class: {}", pseudo_class_name),
+        }
+    }
+
+    pseudo_class_styles
+}
+
+pub fn apply_properties(styles: &mut Styles, properties: &Vec<Property>) {
+    for property in properties {
+        dispatch_by_key_and_update_style(styles, &property.name, &property.value);
+    }
+}
+
+fn parse_inline_styles(style_str: &str, default_styles: Styles) -> Styles {
+    let mut styles = default_styles;
 
     // Split the style string by semicolons to get "key: value" pairs
     style_str.split(';').filter_map(|item| {
@@ -68,8 +121,9 @@ fn parse_inline_styles(style_str: &str) -> Styles {
 
 static LAYOUT_PROPERTIES: [&str; 11] = ["display", "flex-direction", "flex-wrap", "justify-content", "align-items", "flex-grow", "flex-shrink", "margin", "padding", "spacing", "overflow"];
 static DIMENSION_PROPERTIES: [&str; 6] = ["width", "height", "min-width", "max-width", "min-height", "max-height"];
-static APPEARANCE_PROPERTIES: [&str; 5] = ["background-color", "color", "border-width", "border-color", "border-radius"];
+static APPEARANCE_PROPERTIES: [&str; 16] = ["background-color", "color", "border", "border-top", "border-right", "border-bottom", "border-left", "border-width", "border-color", "border-style", "border-radius", "box-shadow", "opacity", "filter", "backdrop-filter", "cursor"];
 static TEXT_PROPERTIES: [&str; 6] = ["white-space", "font-size", "font-weight", "font-family", "font-style", "text-align"];
+static ANIMATION_PROPERTIES: [&str; 3] = ["transform", "transition", "animation"];
 
 fn dispatch_by_key_and_update_style(styles: &mut Styles, key: &str, value: &str) {
     if LAYOUT_PROPERTIES.contains(&key) {
@@ -80,11 +134,45 @@ fn dispatch_by_key_and_update_style(styles: &mut Styles, key: &str, value: &str)
         update_appearance_style(styles, key, value);
     } else if TEXT_PROPERTIES.contains(&key) {
         update_text_style(styles, key, value);
+    } else if ANIMATION_PROPERTIES.contains(&key) {
+        update_animation_style(styles, key, value);
     } else {
         println!("Unknown style key: {}", key);
     }
 }
 
+/*
+ * Registers the stylesheet's @keyframes rules, so that elements can look them up when their animation starts.
+ * Keyframe properties are parsed like any other style; only the animatable ones are interpolated.
+ */
+pub fn register_keyframes(stylesheet: &Stylesheet) {
+    for keyframes_rule in &stylesheet.keyframes {
+        keyframes_registry::register_keyframes(&keyframes_rule.name, parse_keyframes(keyframes_rule));
+    }
+}
+
+fn parse_keyframes(keyframes_rule: &KeyframesRule) -> Keyframes {
+    let mut frames = Vec::new();
+
+    for keyframe_rule in &keyframes_rule.keyframes {
+        let mut styles = Styles::default();
+        let mut properties = Vec::new();
+
+        for property in &keyframe_rule.properties {
+            dispatch_by_key_and_update_style(&mut styles, &property.name, &property.value);
+            if let Some(animatable_property) = AnimatableProperty::from_css_name(&property.name) {
+                properties.push(animatable_property);
+            }
+        }
+
+        for offset in &keyframe_rule.offsets {
+            frames.push(Keyframe { offset: *offset, styles, properties: properties.clone() });
+        }
+    }
+
+    Keyframes::new(frames)
+}
+
 // Function to merge parent styles with current element styles
 pub fn merge_styles(parent_styles: &Styles, child_styles: &mut Styles) {
     if child_styles.white_space.is_none() {
@@ -105,4 +193,37 @@ pub fn merge_styles(parent_styles: &Styles, child_styles: &mut Styles) {
     if child_styles.text_color.is_none() {
         child_styles.text_color = parent_styles.text_color;
     }
+    if child_styles.cursor.is_none() {
+        child_styles.cursor = parent_styles.cursor;
+    }
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use skia_safe::Color;
+
+    use crate::{
+        parsing::{css::stylesheet_parser::parse_stylesheet, html::html_parser::parse_html_content},
+        rendering::elements::styles::Cursor,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_inline_styles_apply_on_top_of_class_styles() {
+        // Arrange
+        let stylesheet = parse_stylesheet(&String::from(".nav-button { color: rgb(255, 0, 0); cursor: pointer; }"));
+        let dom = parse_html_content(r#"<button class="nav-button" style="color: rgb(0, 0, 255)"></button>"#);
+        let button = dom.select_first("button").unwrap();
+        let attributes = button.attributes.borrow();
+
+        // Act
+        let styles = parse_styles(&attributes, None, &Some(stylesheet));
+
+        // Assert
+        assert_eq!(styles.text_color, Some(Color::BLUE));
+        assert_eq!(styles.cursor, Some(Cursor::Pointer));
+    }
 }
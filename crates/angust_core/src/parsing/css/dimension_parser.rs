@@ -21,7 +21,7 @@ pub fn update_dimension_style(styles: &mut Styles, key: &str, value: &str) {
 
 pub fn parse_dimension(value: &str) -> Option<Dimension> {
     let value = value.trim();
-    let unit_start = value.find(|c: char| !c.is_digit(10) && c != '.').unwrap_or(value.len());
+    let unit_start = value.find(|c: char| !c.is_digit(10) && c != '.' && c != '-').unwrap_or(value.len()); // Negative values for e.g. translate()
     let (numeric_part, unit_part) = value.split_at(unit_start);
     let unit_part = unit_part.trim();
 
@@ -5,4 +5,5 @@ pub mod stylesheet_parser;
 mod layout_parser;
 mod dimension_parser;
 mod appearance_parser;
-mod text_parser;
\ No newline at end of file
+mod text_parser;
+mod animation_parser;
\ No newline at end of file
@@ -1,13 +1,17 @@
+use super::animation_parser::parse_keyframe_offsets;
+
 
 #[derive(Debug, Clone)]
 pub struct Stylesheet {
     pub classes: Vec<StyleClass>,
+    pub keyframes: Vec<KeyframesRule>,
 }
 
 impl Default for Stylesheet {
     fn default() -> Self {
         Stylesheet {
             classes: Vec::new(),
+            keyframes: Vec::new(),
         }
     }
 }
@@ -24,13 +28,48 @@ pub struct Property {
     pub value: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct KeyframesRule {
+    pub name: String,
+    pub keyframes: Vec<KeyframeRule>,
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyframeRule {
+    pub offsets: Vec<f32>, // From 0 to 1
+    pub properties: Vec<Property>,
+}
+
 pub fn parse_stylesheet(stylesheet: &String) -> Stylesheet {
     let mut classes = Vec::new();
+    let mut keyframes = Vec::new();
     let mut current_class = None;
+    let mut current_keyframes: Option<(String, i32)> = None; // Block content and brace depth
 
     for line in stylesheet.lines() {
         let line = line.trim();
-        if line.starts_with(".") {
+
+        // @keyframes blocks nest braces, so they are collected whole and parsed separately
+        if let Some((mut block, depth)) = current_keyframes.take() {
+            block.push_str(line);
+            block.push('\n');
+            let depth = depth + count_braces(line);
+
+            if depth > 0 {
+                current_keyframes = Some((block, depth));
+            } else if let Some(keyframes_rule) = parse_keyframes_block(&block) {
+                keyframes.push(keyframes_rule);
+            }
+            continue;
+        }
+
+        if line.starts_with("@keyframes") {
+            if let Some(class) = current_class.take() {
+                classes.push(class);
+            }
+
+            current_keyframes = Some((format!("{}\n", line), count_braces(line)));
+        } else if line.starts_with(".") {
             if let Some(class) = current_class.take() {
                 classes.push(class);
             }
@@ -64,6 +103,67 @@ pub fn parse_stylesheet(stylesheet: &String) -> Stylesheet {
 
     Stylesheet {
         classes,
+        keyframes,
     }
 
 }
+
+fn count_braces(line: &str) -> i32 {
+    line.matches('{').count() as i32 - line.matches('}').count() as i32
+}
+
+// Parses e.g. "@keyframes fade-in { from { opacity: 0; } to { opacity: 1; } }" (on any number of lines)
+fn parse_keyframes_block(block: &str) -> Option<KeyframesRule> {
+    let block_start = block.find('{')?;
+    let name = block["@keyframes".len()..block_start].trim().to_string();
+    let mut content = block[block_start + 1..].trim_end().strip_suffix('}')?;
+
+    let mut keyframe_rules = Vec::new();
+    while let Some(selector_end) = content.find('{') {
+        let body_end = content[selector_end..].find('}')? + selector_end;
+        let selector = content[..selector_end].trim();
+        let body = &content[selector_end + 1..body_end];
+
+        let properties = body.split(';')
+            .filter_map(|declaration| {
+                let (property_name, value) = declaration.split_once(':')?;
+                Some(Property { name: property_name.trim().to_string(), value: value.trim().to_string() })
+            })
+            .collect();
+
+        keyframe_rules.push(KeyframeRule { offsets: parse_keyframe_offsets(selector), properties });
+        content = &content[body_end + 1..];
+    }
+
+    Some(KeyframesRule { name, keyframes: keyframe_rules })
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stylesheet_with_keyframes() {
+        // Arrange
+        let stylesheet = String::from(
+            ".box {\n    animation: pulse 1s infinite;\n}\n\n@keyframes pulse {\n    from { opacity: 1; }\n    50% {\n        opacity: 0.5;\n        transform: scale(1.2);\n    }\n    to { opacity: 1; }\n}\n\n.title {\n    color: rgb(0, 0, 0);\n}"
+        );
+
+        // Act
+        let stylesheet = parse_stylesheet(&stylesheet);
+
+        // Assert
+        assert_eq!(stylesheet.classes.len(), 2);
+        assert_eq!(stylesheet.classes[1].properties.len(), 1);
+        assert_eq!(stylesheet.keyframes.len(), 1);
+
+        let keyframes_rule = &stylesheet.keyframes[0];
+        assert_eq!(keyframes_rule.name, "pulse");
+        assert_eq!(keyframes_rule.keyframes.len(), 3);
+        assert_eq!(keyframes_rule.keyframes[1].offsets, vec![0.5]);
+        assert_eq!(keyframes_rule.keyframes[1].properties.len(), 2);
+        assert_eq!(keyframes_rule.keyframes[1].properties[1].value, "scale(1.2)");
+    }
+}
@@ -0,0 +1,40 @@
+use crate::{
+    parsing::{
+        expression::{ast, ast_evaluator}, 
+        html::{error::ParsingError, html_parser::ParsingContext}
+    }, 
+    rendering::elements::component::state::reactivity::ReactiveState
+};
+
+
+/*
+ * Disabled state of buttons and form controls: the disabled attribute, or [disabled]="expression".
+ * The expression is evaluated again whenever the template is reloaded.
+ */
+pub fn parse_disabled<State: ReactiveState>(
+    attributes: &kuchiki::Attributes,
+    context: &mut ParsingContext<State>,
+) -> Result<bool, ParsingError> {
+    if attributes.contains("disabled") {
+        return Ok(true);
+    }
+    let disabled_expression = match attributes.get("[disabled]") {
+        Some(expression) => expression.trim().to_string(),
+        None => return Ok(false),
+    };
+
+    let ast = ast::parse_string_to_ast(disabled_expression)
+        .map_err(|e| ParsingError::ASTParsingError(format!("{:?}", e)))?;
+    ParsingContext::add_template_expression_ast(context, ast.clone());
+
+    let state = context.component_state.expect("Could not get component state");
+    let component_functions = context.component_functions.expect("Could not get component functions");
+    let for_loop_contexts = context.for_loop_contexts.clone().unwrap_or(vec![]);
+    let evaluation_result = ast_evaluator::evaluate_ast::<State>(&ast, state, component_functions, &for_loop_contexts)?;
+
+    let is_disabled = evaluation_result
+        .downcast_ref::<bool>()
+        .ok_or_else(|| ParsingError::ASTEvaluationError(String::from("Disabled expression did not evaluate to a boolean")))?;
+
+    Ok(*is_disabled)
+}
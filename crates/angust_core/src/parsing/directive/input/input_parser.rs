@@ -7,7 +7,7 @@ pub fn parse_input_attributes(
     attributes: &kuchiki::Attributes,
 ) -> HashMap<String, String> {
     let mut inputs = HashMap::new();
-    let re = Regex::new(r"\[\s*(\w+)\s*\]").unwrap(); // Regex to capture [property]
+    let re = Regex::new(r"\[\(?\s*(\w+)\s*\)?\]").unwrap(); // Regex to capture [property] (and two-way [(property)])
     
     for (key, value) in attributes.map.iter() {
         let key_local = key.local.clone().to_string();
@@ -30,7 +30,7 @@ fn scan_dom_for_inputs(
     match dom.data() {
         NodeData::Element(ref elem_data) => {
             match elem_data.name.local.as_ref() {
-                "div" | "button" | "img" => scan_node_children_for_inputs(dom, inputs_map),
+                "div" | "button" | "img" | "input" | "textarea" | "select" | "option" => scan_node_children_for_inputs(dom, inputs_map),
                 component_name => scan_component_inputs(component_name, elem_data, dom, inputs_map),
             }
         }
@@ -1,6 +1,8 @@
 pub mod if_parser;
 pub mod for_parser;
 pub mod placeholder_parser;
-pub mod on_click_parser;
+pub mod on_event_parser;
+pub mod model_parser;
+pub mod disabled_parser;
 pub mod input;
 mod id_generator;
\ No newline at end of file
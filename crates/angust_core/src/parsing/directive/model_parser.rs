@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+
+use crate::{
+    parsing::{
+        expression::ast::ASTNode,
+        html::{error::ParsingError, html_parser::ParsingContext}
+    },
+    rendering::elements::component::state::{
+        nested_reflectivity::{access_field, get_nested_field},
+        reactivity::ReactiveState,
+        reflectivity::ReflectiveState
+    }
+};
+
+use super::{for_parser::ForLoopContext, id_generator::IDGenerator};
+
+
+pub const MODEL_ATTRIBUTE: &str = "[(model)]";
+
+pub struct ModelBinding {
+    pub handler_name: String, // Registered as a template event handler, to be added to the element's event handlers
+    pub value: Box<dyn ReflectiveState>, // Current value of the bound field
+}
+
+/*
+ * Two-way binding [(model)]="field_path" on form elements, eg. [(model)]="user.name".
+ * The element displays the field's value and writes user edits back to it (see Component::write_model_binding).
+ * Edits are written to the component state only, so @for loop variables (eg. "item.name") can't be bound.
+ */
+pub fn parse_model_binding<State: ReactiveState>(
+    attributes: &kuchiki::Attributes,
+    context: &mut ParsingContext<State>,
+) -> Result<Option<ModelBinding>, ParsingError> {
+    let field_path = match attributes.get(MODEL_ATTRIBUTE) {
+        Some(field_path) => parse_field_path(field_path)?,
+        None => return Ok(None),
+    };
+    let state = match context.component_state {
+        Some(state) => state,
+        None => return Err(ParsingError::InvalidDirective(format!("No component state to bind {} to", field_path))),
+    };
+
+    if is_loop_variable_path(state, &field_path, &context.for_loop_contexts) {
+        return Err(ParsingError::InvalidDirective(format!("Cannot bind {} to a loop variable, bind a state field instead", field_path)));
+    }
+
+    let value = access_field(state, &field_path, &context.for_loop_contexts)?;
+
+    let handler_name = format!("model_{}", IDGenerator::get());
+    context.add_template_event_handler_ast(handler_name.clone(), ASTNode::Identifier(field_path));
+
+    Ok(Some(ModelBinding { handler_name, value }))
+}
+
+// Two-way bindings of custom components [(property)]="field_path": property -> field path in the parent's state
+pub fn parse_component_model_bindings(
+    attributes: &kuchiki::Attributes,
+) -> Result<HashMap<String, String>, ParsingError> {
+    let mut model_bindings = HashMap::new();
+    let re = Regex::new(r"^\[\(\s*(\w+)\s*\)\]$").unwrap();
+
+    for (key, value) in attributes.map.iter() {
+        let key_local = key.local.to_string();
+        let property_name = match re.captures(&key_local).and_then(|captures| captures.get(1)) {
+            Some(property_name) => property_name.as_str().to_string(),
+            None => continue,
+        };
+
+        model_bindings.insert(property_name, parse_field_path(&value.value)?);
+    }
+
+    Ok(model_bindings)
+}
+
+// Only fields can be written to, not arbitrary expressions
+fn parse_field_path(expression: &str) -> Result<String, ParsingError> {
+    let re = Regex::new(r"^[A-Za-z_]\w*(\.\w+)*$").unwrap();
+    let field_path = expression.trim();
+    if !re.is_match(field_path) {
+        return Err(ParsingError::InvalidDirective(format!("Invalid model binding, expected a field: {}", expression)));
+    }
+
+    Ok(field_path.to_string())
+}
+
+// Paths not found in the state starting with a loop variable, which access_field would read through the loop context
+fn is_loop_variable_path(state: &dyn ReflectiveState, field_path: &str, for_loop_contexts: &Option<Vec<ForLoopContext>>) -> bool {
+    let path: Vec<&str> = field_path.split('.').collect();
+    if get_nested_field(state, &path).is_some() {
+        return false;
+    }
+
+    for_loop_contexts.as_ref().map_or(false, |for_loop_contexts| {
+        for_loop_contexts.iter().any(|for_loop_context| for_loop_context.loop_variable == path[0])
+    })
+}
+
+// Text displayed by inputs and text areas for the bound value
+pub fn get_model_text(value: &dyn ReflectiveState) -> String {
+    let value = value.as_any();
+    if let Some(val) = value.downcast_ref::<String>() {
+        val.clone()
+    } else if let Some(val) = value.downcast_ref::<f64>() {
+        val.to_string()
+    } else if let Some(val) = value.downcast_ref::<u32>() {
+        val.to_string()
+    } else if let Some(val) = value.downcast_ref::<usize>() {
+        val.to_string()
+    } else if let Some(val) = value.downcast_ref::<bool>() {
+        val.to_string()
+    } else {
+        String::new()
+    }
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use crate::{parsing::html::html_parser::parse_html_content, rendering::elements::component::state::reflectivity::NoState};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_field_path() {
+        // Arrange
+        let valid_expression = " user.name ";
+        let invalid_expression = "get_name()";
+
+        // Act
+        let valid_result = parse_field_path(valid_expression);
+        let invalid_result = parse_field_path(invalid_expression);
+
+        // Assert
+        assert_eq!(valid_result.unwrap(), "user.name");
+        assert!(matches!(invalid_result, Err(ParsingError::InvalidDirective(_))));
+    }
+
+    #[test]
+    fn test_parse_model_binding_rejects_loop_variable() {
+        // Arrange
+        let dom = parse_html_content(r#"<input [(model)]="item.name">"#);
+        let input = dom.select_first("input").unwrap();
+        let attributes = input.attributes.borrow();
+
+        let state = NoState;
+        let for_loop_context = ForLoopContext { loop_variable: String::from("item"), array_access_path: String::from("items"), ..Default::default() };
+        let mut context = ParsingContext { component_state: Some(&state), for_loop_contexts: Some(vec![for_loop_context]), ..Default::default() };
+
+        // Act
+        let result = parse_model_binding(&attributes, &mut context);
+
+        // Assert
+        assert!(matches!(result, Err(ParsingError::InvalidDirective(_))));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{
+    parsing::{
+        expression::ast::{self, ASTNode},
+        html::{error::ParsingError, html_parser::ParsingContext}
+    },
+    rendering::elements::{component::state::reactivity::ReactiveState, event_handlers::EventHandlers}
+};
+
+use super::id_generator::IDGenerator;
+
+
+pub const ELEMENT_EVENTS: [&str; 4] = ["keydown", "keyup", "focus", "blur"];
+pub const INPUT_EVENTS: [&str; 2] = ["input", "change"];
+pub const MOUSE_EVENTS: [&str; 10] = ["click", "dblclick", "auxclick", "contextmenu", "mousedown", "mouseup", "mouseenter", "mouseleave", "mousemove", "wheel"];
+pub const SCROLL_EVENTS: [&str; 1] = ["scroll"]; // Of overflowing containers
+
+// Event handler attributes @on<event>="event_handler_name(params)", eg. @onkeydown="on_key($event)",
+// and @on<event>.capture for mouse event handlers running in the capture phase
+pub fn parse_event_handlers<State: ReactiveState>(
+    attributes: &kuchiki::Attributes,
+    event_names: &[&str],
+    context: &mut ParsingContext<State>,
+) -> Result<EventHandlers, ParsingError> {
+    let mut event_handlers = EventHandlers::new(context.for_loop_contexts.clone().unwrap_or(vec![]));
+
+    for event_name in event_names {
+        if let Some(attribute_value) = attributes.get(format!("@on{}", event_name)) {
+            let (handler_name, handler_ast) = parse_event_handler_expression(attribute_value)?;
+            context.add_template_event_handler_ast(handler_name.clone(), handler_ast);
+            event_handlers.add_handler(event_name.to_string(), handler_name);
+        }
+
+        if !MOUSE_EVENTS.contains(event_name) {
+            continue;
+        }
+        if let Some(attribute_value) = attributes.get(format!("@on{}.capture", event_name)) {
+            let (handler_name, handler_ast) = parse_event_handler_expression(attribute_value)?;
+            context.add_template_event_handler_ast(handler_name.clone(), handler_ast);
+            event_handlers.add_capture_handler(event_name.to_string(), handler_name);
+        }
+    }
+
+    Ok(event_handlers)
+}
+
+fn parse_event_handler_expression(expression: &str) -> Result<(String, ASTNode), ParsingError> {
+    let ast = ast::parse_string_to_ast(expression.to_string())
+        .map_err(|e| ParsingError::ASTParsingError(format!("Error parsing event handler expression: {:?}", e)))?;
+
+    // Get root function name
+    let root_function_name = match ast.clone() {
+        ASTNode::FunctionCall(function_name, _) => function_name,
+        _ => return Err(ParsingError::InvalidDirective(format!("Invalid event handler expression: {}", expression))),
+    };
+    let unique_id = IDGenerator::get();
+
+    Ok((format!("{}_{}", root_function_name, unique_id), ast))
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_event_handler_expression_with_event_payload() {
+        // Arrange
+        let expression = "on_key(item, $event)";
+
+        // Act
+        let (handler_name, ast) = parse_event_handler_expression(expression).unwrap();
+
+        // Assert
+        assert!(handler_name.starts_with("on_key_id_"));
+        assert_eq!(ast, ASTNode::FunctionCall(
+            "on_key".to_string(),
+            vec![ASTNode::Identifier("item".to_string()), ASTNode::Identifier(ast::EVENT_IDENTIFIER.to_string())]
+        ));
+    }
+}
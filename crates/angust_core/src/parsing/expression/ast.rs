@@ -1,6 +1,9 @@
 use super::expression_parser::{parse_expression, Rule};
 use pest::iterators::Pair;
 
+// Reserved identifier for the payload of the event being handled, eg. @onkeydown="on_key($event)"
+pub const EVENT_IDENTIFIER: &str = "$event";
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ASTNode {
     Number(f64),
@@ -108,7 +111,8 @@ fn parse_function_call(
         if arg_list_pair.as_rule() == Rule::expression_list {
             arg_list_pair.into_inner().map(parse_pair_to_ast).collect()
         } else {
-            vec![parse_pair_to_ast(arg_list_pair)]
+            // The argument list is silent, so each argument comes as a separate pair
+            std::iter::once(arg_list_pair).chain(inner_pairs).map(parse_pair_to_ast).collect()
         }
     } else {
         vec![]
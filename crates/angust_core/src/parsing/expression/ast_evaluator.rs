@@ -5,13 +5,16 @@ use crate::{
         directive::for_parser::ForLoopContext, 
         html::error::ParsingError
     }, 
-    rendering::elements::component::{
-        functions::component_functions::ComponentFunctions, 
-        state::{nested_reflectivity::access_field, reactivity::ReactiveState}
+    rendering::elements::{
+        component::{
+            functions::component_functions::ComponentFunctions, 
+            state::{nested_reflectivity::access_field, reactivity::ReactiveState}
+        },
+        element::EventPayload
     }
 };
 
-use super::ast::{ASTNode, Operator};
+use super::ast::{ASTNode, Operator, EVENT_IDENTIFIER};
 
 
 pub fn evaluate_ast<State: ReactiveState>(
@@ -43,6 +46,24 @@ pub fn evaluate_ast<State: ReactiveState>(
     }
 }
 
+// Evaluates a parameter of an event handler, in which the reserved $event identifier resolves to the payload of the event
+pub fn evaluate_handler_param<State: ReactiveState>(
+    node: &ASTNode,
+    state: &State,
+    component_functions: &ComponentFunctions<State>,
+    for_loop_contexts: &Vec<ForLoopContext>,
+    event_payload: Option<&EventPayload>,
+) -> Result<Box<dyn Any>, ParsingError> {
+    if *node != ASTNode::Identifier(EVENT_IDENTIFIER.to_string()) {
+        return evaluate_ast(node, state, component_functions, for_loop_contexts);
+    }
+
+    match event_payload {
+        Some(event_payload) => Ok(event_payload.to_any()),
+        None => Err(ParsingError::ASTEvaluationError(format!("No {} payload available for this event", EVENT_IDENTIFIER))),
+    }
+}
+
 fn evaluate_identifier<State: ReactiveState>(
     name: &str,
     state: &State,
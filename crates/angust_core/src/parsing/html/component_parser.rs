@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use crate::{
-    parsing::{css::css_parser, directive::input::input_evaluator}, 
+    parsing::{css::css_parser, directive::{input::input_evaluator, model_parser, on_event_parser::{self, MOUSE_EVENTS}}}, 
     rendering::{
         elements::{
             component::{component_factory_registry::create_component, state::reactivity::ReactiveState},
@@ -44,6 +44,15 @@ pub fn process_custom_component<State : ReactiveState>(
     // Compute inputs using parent state and functions *before* initializing the component (i.e. parsing its template)
     let input_values = input_evaluator::compute_inputs_from_parent_component(&component, context)?;
     
+    // Two-way bindings: the bound properties are also passed as inputs, and written back when the component changes them
+    let model_bindings = model_parser::parse_component_model_bindings(&attributes)?;
+    // The host element's handlers are functions of the parent component, registered in its context
+    let host_event_handlers = on_event_parser::parse_event_handlers(&attributes, &MOUSE_EVENTS, context)?;
+    if let Some(component_interface) = component.get_component_interface() {
+        component_interface.set_model_bindings(model_bindings);
+        component_interface.set_host_event_handlers(host_event_handlers);
+    }
+
     component.initialize(input_values);
 
     Ok(component)
@@ -2,14 +2,19 @@ use kuchiki::NodeRef;
 
 use crate::{
     parsing::{
-        directive::{for_parser, for_parser::ForLoopContext, if_parser, on_click_parser},
+        directive::{disabled_parser, for_parser, for_parser::ForLoopContext, if_parser, model_parser, on_event_parser::{self, ELEMENT_EVENTS, INPUT_EVENTS, MOUSE_EVENTS, SCROLL_EVENTS}},
         css::css_parser,
     },
     rendering::elements::{
         container::Container,
         button::Button,
+        checkbox::Checkbox,
         element::Element,
         image::Image,
+        input::{Input, InputType},
+        range::Range,
+        select::{Select, SelectOption},
+        textarea::Textarea,
         styles::Styles,
         component::state::reactivity::ReactiveState,
     }
@@ -32,6 +37,9 @@ pub fn dispatch_element_processing<State : ReactiveState>(
         "div" => process_div_element::<State>(elem_data, node, parent_styles, context),
         "button" => process_button_element::<State>(elem_data, node, parent_styles, context),
         "img" => process_image_element::<State>(elem_data, parent_styles, context),
+        "input" => process_input_element::<State>(elem_data, parent_styles, context),
+        "textarea" => process_textarea_element::<State>(elem_data, node, parent_styles, context),
+        "select" => process_select_element::<State>(elem_data, node, parent_styles, context),
         component_name => process_custom_component::<State>(component_name, elem_data, node, parent_styles, context),
     }
 }
@@ -57,8 +65,12 @@ fn process_div_element<State : ReactiveState>(
     let array_length = for_loop_context.array_length;
 
     if for_loop_context.is_for_loop {
-        parse_for_loop(node, &mut container, context, &for_loop_context, array_length, &styles);
+        parse_for_loop(node, &attributes, &mut container, context, &for_loop_context, array_length, &styles)?;
     } else {
+        container.event_handlers = on_event_parser::parse_event_handlers(&attributes, &[&ELEMENT_EVENTS[..], &MOUSE_EVENTS[..], &SCROLL_EVENTS[..]].concat(), context)?;
+        container.tab_index = parse_tab_index(&attributes);
+        container.pseudo_class_styles = css_parser::parse_pseudo_class_styles(&attributes, &context.stylesheet);
+        container.template_id = attributes.get("id").map(|id| id.to_string());
         map_dom_children_to_elements(node, &mut container, context, &styles)?;
     }
 
@@ -67,17 +79,21 @@ fn process_div_element<State : ReactiveState>(
 
 fn parse_for_loop<State: ReactiveState>(
     node: &NodeRef, 
+    attributes: &kuchiki::Attributes,
     container: &mut Container,
     context: &mut ParsingContext<State>,
     for_loop_context: &ForLoopContext,
     array_length: usize,
     styles: &Styles,
-) {
+) -> Result<(), ParsingError> {
     context.add_for_loop_context(for_loop_context.clone());
 
     for _ in 0..array_length {
         let mut child = Container::new();
         child.set_styles(styles.clone());
+        child.event_handlers = on_event_parser::parse_event_handlers(attributes, &[&ELEMENT_EVENTS[..], &MOUSE_EVENTS[..], &SCROLL_EVENTS[..]].concat(), context)?; // Bound to the item
+        child.tab_index = parse_tab_index(attributes);
+        child.pseudo_class_styles = css_parser::parse_pseudo_class_styles(attributes, &context.stylesheet);
 
         map_dom_children_to_elements::<State>(node, &mut child, context, styles).unwrap();
         container.add_child(Box::new(child));
@@ -86,6 +102,8 @@ fn parse_for_loop<State: ReactiveState>(
     }
 
     context.remove_loop_context(&for_loop_context.context_id);
+
+    Ok(())
 }
 
 fn map_dom_children_to_elements<State : ReactiveState>(
@@ -115,20 +133,28 @@ fn process_button_element<State : ReactiveState>(
     let attributes = elem_data.attributes.borrow();
     let styles = css_parser::parse_styles(&attributes, parent_styles, &context.stylesheet);
 
-    // Parse on_click event handler into context AST
-    let (on_click_handler_name, handler_ast) = on_click_parser::parse_on_click_expression(&attributes, context)?;
-    context.add_template_event_handler_ast(on_click_handler_name.clone(), handler_ast);
+    let event_handlers = on_event_parser::parse_event_handlers(&attributes, &[&ELEMENT_EVENTS[..], &MOUSE_EVENTS[..]].concat(), context)?;
     
     // Parse children
     let mut child_container = Container::new();
     map_dom_children_to_elements::<State>(node, &mut child_container, context, &styles).unwrap();
         
-    let mut button = Button::new(Some(on_click_handler_name), context.for_loop_contexts.clone(), None, Some(styles));
+    let mut button = Button::new(None, context.for_loop_contexts.clone(), None, Some(styles));
     button.add_child(Box::new(child_container));
+    button.event_handlers = event_handlers;
+    button.tab_index = parse_tab_index(&attributes).or(button.tab_index);
+    button.pseudo_class_styles = css_parser::parse_pseudo_class_styles(&attributes, &context.stylesheet);
+    button.set_disabled(disabled_parser::parse_disabled(&attributes, context)?);
+    button.template_id = attributes.get("id").map(|id| id.to_string());
 
     Ok(Box::new(button))
 }
 
+// tabindex="0" makes an element reachable with Tab, while "-1" makes it focusable on click or from code only
+fn parse_tab_index(attributes: &kuchiki::Attributes) -> Option<i32> {
+    attributes.get("tabindex").and_then(|tab_index| tab_index.trim().parse::<i32>().ok())
+}
+
 fn process_image_element<State : ReactiveState>(
     elem_data: &kuchiki::ElementData, 
     parent_styles: Option<&Styles>, 
@@ -140,9 +166,201 @@ fn process_image_element<State : ReactiveState>(
 
     let relative_path = context.angust_config.clone().unwrap_or_default().pathing_config.assets_dir_path.to_string() + "/img";
 
-    let image = Image::new(
+    let mut image = Image::new(
         relative_path, src.to_string(), Some(styles)
     );
+    image.event_handlers = on_event_parser::parse_event_handlers(&attributes, &MOUSE_EVENTS, context)?;
 
     Ok(Box::new(image))
 }
+
+fn process_input_element<State : ReactiveState>(
+    elem_data: &kuchiki::ElementData, 
+    parent_styles: Option<&Styles>, 
+    context: &mut ParsingContext<State>,
+) -> Result<Box<dyn Element>, ParsingError> {
+    let attributes = elem_data.attributes.borrow();
+
+    let input_type_attribute = attributes.get("type").unwrap_or("text");
+    let input_type = match InputType::from_attribute(input_type_attribute) {
+        Some(input_type) => input_type,
+        None => {
+            println!("Unsupported input type: {}, falling back to text", input_type_attribute);
+            InputType::Text
+        }
+    };
+    match input_type {
+        InputType::Checkbox | InputType::Radio => return process_checkbox_element(input_type, &attributes, parent_styles, context),
+        InputType::Range => return process_range_element(&attributes, parent_styles, context),
+        _ => {}
+    }
+
+    let styles = css_parser::parse_styles_with_defaults(&attributes, parent_styles, &context.stylesheet, Input::get_default_styles());
+    let model_binding = model_parser::parse_model_binding(&attributes, context)?;
+    let value = match &model_binding {
+        Some(model_binding) => model_parser::get_model_text(model_binding.value.as_ref()),
+        None => attributes.get("value").unwrap_or_default().to_string(),
+    };
+
+    let mut input = Input::new(input_type, value, Some(styles));
+    input.placeholder = attributes.get("placeholder").unwrap_or_default().to_string();
+    input.event_handlers = on_event_parser::parse_event_handlers(&attributes, &[&ELEMENT_EVENTS[..], &MOUSE_EVENTS[..], &INPUT_EVENTS[..]].concat(), context)?;
+    if let Some(model_binding) = model_binding {
+        input.event_handlers.add_model_binding("input".to_string(), model_binding.handler_name);
+    }
+    input.tab_index = parse_tab_index(&attributes).or(input.tab_index);
+    input.pseudo_class_styles = css_parser::parse_pseudo_class_styles(&attributes, &context.stylesheet);
+    input.set_disabled(disabled_parser::parse_disabled(&attributes, context)?);
+    input.template_id = attributes.get("id").map(|id| id.to_string());
+
+    Ok(Box::new(input))
+}
+
+/*
+ * Checkbox or radio button. With [(model)], a checkbox is bound to a bool field,
+ * while radio buttons are bound to the field holding the value of the checked one.
+ */
+fn process_checkbox_element<State : ReactiveState>(
+    input_type: InputType,
+    attributes: &kuchiki::Attributes,
+    parent_styles: Option<&Styles>,
+    context: &mut ParsingContext<State>,
+) -> Result<Box<dyn Element>, ParsingError> {
+    let styles = css_parser::parse_styles_with_defaults(attributes, parent_styles, &context.stylesheet, Checkbox::get_default_styles());
+
+    let value = attributes.get("value").unwrap_or("on").to_string();
+    let model_binding = model_parser::parse_model_binding(attributes, context)?;
+    let is_checked = match &model_binding {
+        Some(model_binding) if input_type == InputType::Radio => model_parser::get_model_text(model_binding.value.as_ref()) == value,
+        Some(model_binding) => model_parser::get_model_text(model_binding.value.as_ref()) == "true",
+        None => attributes.contains("checked"),
+    };
+
+    let mut checkbox = Checkbox::new(input_type, is_checked, Some(styles));
+    checkbox.name = attributes.get("name").map(|name| name.to_string());
+    checkbox.value = value;
+    checkbox.event_handlers = on_event_parser::parse_event_handlers(attributes, &[&ELEMENT_EVENTS[..], &MOUSE_EVENTS[..], &INPUT_EVENTS[..]].concat(), context)?;
+    if let Some(model_binding) = model_binding {
+        checkbox.event_handlers.add_model_binding("change".to_string(), model_binding.handler_name);
+    }
+    checkbox.tab_index = parse_tab_index(attributes).or(checkbox.tab_index);
+    checkbox.pseudo_class_styles = css_parser::parse_pseudo_class_styles(attributes, &context.stylesheet);
+    checkbox.set_disabled(disabled_parser::parse_disabled(attributes, context)?);
+    checkbox.template_id = attributes.get("id").map(|id| id.to_string());
+
+    Ok(Box::new(checkbox))
+}
+
+// Range between min and max (0 and 100 by default), by increments of step (1 by default)
+fn process_range_element<State : ReactiveState>(
+    attributes: &kuchiki::Attributes,
+    parent_styles: Option<&Styles>,
+    context: &mut ParsingContext<State>,
+) -> Result<Box<dyn Element>, ParsingError> {
+    let styles = css_parser::parse_styles_with_defaults(attributes, parent_styles, &context.stylesheet, Range::get_default_styles());
+
+    let parse_number = |name: &str| attributes.get(name).and_then(|value| value.trim().parse::<f64>().ok());
+    let min = parse_number("min").unwrap_or(0.0);
+    let max = parse_number("max").unwrap_or(100.0);
+    let step = parse_number("step").unwrap_or(1.0);
+
+    let model_binding = model_parser::parse_model_binding(attributes, context)?;
+    let value = match &model_binding {
+        Some(model_binding) => model_parser::get_model_text(model_binding.value.as_ref()).parse::<f64>().ok(),
+        None => parse_number("value"),
+    };
+
+    // As in the DOM, the default value is halfway between min and max
+    let mut range = Range::new(min, max, step, value.unwrap_or(min + (max - min) / 2.0), Some(styles));
+    range.event_handlers = on_event_parser::parse_event_handlers(attributes, &[&ELEMENT_EVENTS[..], &MOUSE_EVENTS[..], &INPUT_EVENTS[..]].concat(), context)?;
+    if let Some(model_binding) = model_binding {
+        range.event_handlers.add_model_binding("change".to_string(), model_binding.handler_name);
+    }
+    range.tab_index = parse_tab_index(attributes).or(range.tab_index);
+    range.pseudo_class_styles = css_parser::parse_pseudo_class_styles(attributes, &context.stylesheet);
+    range.set_disabled(disabled_parser::parse_disabled(attributes, context)?);
+    range.template_id = attributes.get("id").map(|id| id.to_string());
+
+    Ok(Box::new(range))
+}
+
+// The initial value is the element's text content, unless bound with [(model)]
+fn process_textarea_element<State : ReactiveState>(
+    elem_data: &kuchiki::ElementData, 
+    node: &NodeRef, 
+    parent_styles: Option<&Styles>, 
+    context: &mut ParsingContext<State>,
+) -> Result<Box<dyn Element>, ParsingError> {
+    let attributes = elem_data.attributes.borrow();
+    let styles = css_parser::parse_styles_with_defaults(&attributes, parent_styles, &context.stylesheet, Textarea::get_default_styles());
+
+    let model_binding = model_parser::parse_model_binding(&attributes, context)?;
+    let value = match &model_binding {
+        Some(model_binding) => model_parser::get_model_text(model_binding.value.as_ref()),
+        None => node.text_contents(),
+    };
+
+    let mut textarea = Textarea::new(value, Some(styles));
+    textarea.placeholder = attributes.get("placeholder").unwrap_or_default().to_string();
+    if let Some(rows) = attributes.get("rows").and_then(|rows| rows.trim().parse::<usize>().ok()) {
+        textarea.rows = rows;
+    }
+    textarea.event_handlers = on_event_parser::parse_event_handlers(&attributes, &[&ELEMENT_EVENTS[..], &MOUSE_EVENTS[..], &INPUT_EVENTS[..]].concat(), context)?;
+    if let Some(model_binding) = model_binding {
+        textarea.event_handlers.add_model_binding("input".to_string(), model_binding.handler_name);
+    }
+    textarea.tab_index = parse_tab_index(&attributes).or(textarea.tab_index);
+    textarea.pseudo_class_styles = css_parser::parse_pseudo_class_styles(&attributes, &context.stylesheet);
+    textarea.set_disabled(disabled_parser::parse_disabled(&attributes, context)?);
+    textarea.template_id = attributes.get("id").map(|id| id.to_string());
+
+    Ok(Box::new(textarea))
+}
+
+/*
+ * Dropdown list of its <option> children, whose label is their text content and value their value attribute (the label by default).
+ * The selected option is the one bound with [(model)], the one with the selected attribute, or the first one.
+ */
+fn process_select_element<State : ReactiveState>(
+    elem_data: &kuchiki::ElementData,
+    node: &NodeRef,
+    parent_styles: Option<&Styles>,
+    context: &mut ParsingContext<State>,
+) -> Result<Box<dyn Element>, ParsingError> {
+    let attributes = elem_data.attributes.borrow();
+    let styles = css_parser::parse_styles_with_defaults(&attributes, parent_styles, &context.stylesheet, Select::get_default_styles());
+
+    let mut options = Vec::new();
+    let mut selected_value = None;
+    for child in node.children() {
+        let option_data = match child.as_element() {
+            Some(option_data) if option_data.name.local.as_ref() == "option" => option_data,
+            _ => continue,
+        };
+        let option_attributes = option_data.attributes.borrow();
+        let label = child.text_contents().trim().to_string();
+        let value = option_attributes.get("value").map(|value| value.to_string()).unwrap_or_else(|| label.clone());
+
+        if option_attributes.contains("selected") {
+            selected_value = Some(value.clone());
+        }
+        options.push(SelectOption { value, label });
+    }
+
+    let model_binding = model_parser::parse_model_binding(&attributes, context)?;
+    if let Some(model_binding) = &model_binding {
+        selected_value = Some(model_parser::get_model_text(model_binding.value.as_ref()));
+    }
+
+    let mut select = Select::new(options, selected_value, Some(styles));
+    select.event_handlers = on_event_parser::parse_event_handlers(&attributes, &[&ELEMENT_EVENTS[..], &MOUSE_EVENTS[..], &INPUT_EVENTS[..]].concat(), context)?;
+    if let Some(model_binding) = model_binding {
+        select.event_handlers.add_model_binding("change".to_string(), model_binding.handler_name);
+    }
+    select.tab_index = parse_tab_index(&attributes).or(select.tab_index);
+    select.pseudo_class_styles = css_parser::parse_pseudo_class_styles(&attributes, &context.stylesheet);
+    select.set_disabled(disabled_parser::parse_disabled(&attributes, context)?);
+    select.template_id = attributes.get("id").map(|id| id.to_string());
+
+    Ok(Box::new(select))
+}
@@ -86,6 +86,7 @@ fn process_text_element<State : ReactiveState>(
     if let Some(styles) = parent_styles {
         let mut element_styles = Styles::default();
         merge_styles(styles, &mut element_styles);
+        element_styles.transitions = styles.transitions; // So that inherited text colors transition along with the parent's
         text_element.set_styles(element_styles);
     }
     
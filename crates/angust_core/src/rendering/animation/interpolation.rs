@@ -0,0 +1,151 @@
+use skia_safe::Color;
+
+use crate::rendering::elements::styles::{AnimatableProperty, Dimension, SizingPolicy, Styles, Transform};
+
+
+/*
+ * Writes the value of the property at the given progress between two styles into the output styles.
+ * Values that cannot be interpolated (missing on one side, dimensions with different units) switch halfway, as in CSS.
+ */
+pub fn interpolate_property(property: AnimatableProperty, from: &Styles, to: &Styles, progress: f32, styles: &mut Styles) {
+    match property {
+        AnimatableProperty::BackgroundColor => {
+            styles.background_color = interpolate_option(from.background_color, to.background_color, progress, interpolate_color);
+        },
+        AnimatableProperty::Color => {
+            styles.text_color = interpolate_option(from.text_color, to.text_color, progress, interpolate_color);
+        },
+        AnimatableProperty::BorderColor => {
+            let (from_sides, to_sides) = (from.border.unwrap_or_default().get_sides(), to.border.unwrap_or_default().get_sides());
+            let mut border = styles.border.unwrap_or_default();
+            for (index, side) in border.get_sides_mut().into_iter().enumerate() {
+                side.color = interpolate_option(from_sides[index].color, to_sides[index].color, progress, interpolate_color);
+            }
+            styles.border = Some(border);
+        },
+        AnimatableProperty::BorderWidth => {
+            let (from_sides, to_sides) = (from.border.unwrap_or_default().get_sides(), to.border.unwrap_or_default().get_sides());
+            let mut border = styles.border.unwrap_or_default();
+            for (index, side) in border.get_sides_mut().into_iter().enumerate() {
+                side.width = interpolate_dimension(from_sides[index].width, to_sides[index].width, progress);
+            }
+            styles.border = Some(border);
+        },
+        AnimatableProperty::Width | AnimatableProperty::Height 
+        | AnimatableProperty::MinWidth | AnimatableProperty::MaxWidth 
+        | AnimatableProperty::MinHeight | AnimatableProperty::MaxHeight => {
+            let from_dimension = get_sizing_dimension(property, &from.sizing_policy.unwrap_or_default());
+            let to_dimension = get_sizing_dimension(property, &to.sizing_policy.unwrap_or_default());
+
+            let mut sizing_policy = styles.sizing_policy.unwrap_or_default();
+            set_sizing_dimension(property, &mut sizing_policy, interpolate_option(from_dimension, to_dimension, progress, interpolate_dimension));
+            styles.sizing_policy = Some(sizing_policy);
+        },
+        AnimatableProperty::Opacity => {
+            styles.opacity = interpolate_option(from.opacity, to.opacity, progress, interpolate_number);
+        },
+        AnimatableProperty::Transform => {
+            // A missing transform interpolates as the identity
+            if from.transform.is_none() && to.transform.is_none() {
+                styles.transform = None;
+                return;
+            }
+            styles.transform = Some(interpolate_transform(from.transform.unwrap_or_default(), to.transform.unwrap_or_default(), progress));
+        },
+    }
+}
+
+pub fn is_property_equal(property: AnimatableProperty, first: &Styles, second: &Styles) -> bool {
+    match property {
+        AnimatableProperty::BackgroundColor => first.background_color == second.background_color,
+        AnimatableProperty::Color => first.text_color == second.text_color,
+        AnimatableProperty::BorderColor => {
+            first.border.unwrap_or_default().get_sides().map(|side| side.color) 
+                == second.border.unwrap_or_default().get_sides().map(|side| side.color)
+        },
+        AnimatableProperty::BorderWidth => {
+            first.border.unwrap_or_default().get_sides().map(|side| side.width) 
+                == second.border.unwrap_or_default().get_sides().map(|side| side.width)
+        },
+        AnimatableProperty::Width | AnimatableProperty::Height 
+        | AnimatableProperty::MinWidth | AnimatableProperty::MaxWidth 
+        | AnimatableProperty::MinHeight | AnimatableProperty::MaxHeight => {
+            get_sizing_dimension(property, &first.sizing_policy.unwrap_or_default()) 
+                == get_sizing_dimension(property, &second.sizing_policy.unwrap_or_default())
+        },
+        AnimatableProperty::Opacity => first.opacity == second.opacity,
+        AnimatableProperty::Transform => first.transform == second.transform,
+    }
+}
+
+// Whether going from the first styles to the second requires a new layout
+pub fn affects_layout(first: &Styles, second: &Styles) -> bool {
+    AnimatableProperty::ALL.iter()
+        .any(|property| property.affects_layout() && !is_property_equal(*property, first, second))
+}
+
+// Value interpolation
+pub fn interpolate_number(from: f32, to: f32, progress: f32) -> f32 {
+    from + (to - from) * progress
+}
+
+pub fn interpolate_color(from: Color, to: Color, progress: f32) -> Color {
+    let interpolate_channel = |from: u8, to: u8| interpolate_number(from as f32, to as f32, progress).round().clamp(0.0, 255.0) as u8;
+
+    Color::from_argb(
+        interpolate_channel(from.a(), to.a()),
+        interpolate_channel(from.r(), to.r()),
+        interpolate_channel(from.g(), to.g()),
+        interpolate_channel(from.b(), to.b()),
+    )
+}
+
+pub fn interpolate_dimension(from: Dimension, to: Dimension, progress: f32) -> Dimension {
+    if from.unit != to.unit {
+        return if progress < 0.5 { from } else { to };
+    }
+
+    Dimension { value: interpolate_number(from.value, to.value, progress), unit: to.unit }
+}
+
+pub fn interpolate_transform(from: Transform, to: Transform, progress: f32) -> Transform {
+    Transform {
+        translate_x: interpolate_dimension(from.translate_x, to.translate_x, progress),
+        translate_y: interpolate_dimension(from.translate_y, to.translate_y, progress),
+        scale_x: interpolate_number(from.scale_x, to.scale_x, progress),
+        scale_y: interpolate_number(from.scale_y, to.scale_y, progress),
+        rotate: interpolate_number(from.rotate, to.rotate, progress),
+    }
+}
+
+fn interpolate_option<T: Copy>(from: Option<T>, to: Option<T>, progress: f32, interpolate: impl Fn(T, T, f32) -> T) -> Option<T> {
+    match (from, to) {
+        (Some(from), Some(to)) => Some(interpolate(from, to, progress)),
+        _ => if progress < 0.5 { from } else { to },
+    }
+}
+
+// Utils
+fn get_sizing_dimension(property: AnimatableProperty, sizing_policy: &SizingPolicy) -> Option<Dimension> {
+    match property {
+        AnimatableProperty::Width => sizing_policy.width,
+        AnimatableProperty::Height => sizing_policy.height,
+        AnimatableProperty::MinWidth => sizing_policy.min_width,
+        AnimatableProperty::MaxWidth => sizing_policy.max_width,
+        AnimatableProperty::MinHeight => sizing_policy.min_height,
+        AnimatableProperty::MaxHeight => sizing_policy.max_height,
+        _ => None,
+    }
+}
+
+fn set_sizing_dimension(property: AnimatableProperty, sizing_policy: &mut SizingPolicy, dimension: Option<Dimension>) {
+    match property {
+        AnimatableProperty::Width => sizing_policy.width = dimension,
+        AnimatableProperty::Height => sizing_policy.height = dimension,
+        AnimatableProperty::MinWidth => sizing_policy.min_width = dimension,
+        AnimatableProperty::MaxWidth => sizing_policy.max_width = dimension,
+        AnimatableProperty::MinHeight => sizing_policy.min_height = dimension,
+        AnimatableProperty::MaxHeight => sizing_policy.max_height = dimension,
+        _ => {},
+    }
+}
@@ -0,0 +1,75 @@
+use std::{collections::HashMap, sync::{Arc, Mutex}};
+
+use once_cell::sync::Lazy;
+
+use crate::rendering::elements::styles::{AnimatableProperty, Styles};
+
+
+/*
+ * Global registry of the @keyframes rules of the stylesheet.
+ * Styles refer to keyframes by id (keeping Styles Copy), and ids are handed out by name,
+ * so that an `animation:` property can be parsed before its @keyframes rule.
+ */
+static KEYFRAMES_REGISTRY: Lazy<Mutex<KeyframesRegistry>> = Lazy::new(|| Mutex::new(KeyframesRegistry::default()));
+
+#[derive(Default)]
+struct KeyframesRegistry {
+    ids: HashMap<String, usize>,
+    keyframes: Vec<Option<Arc<Keyframes>>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Keyframes {
+    pub frames: Vec<Keyframe>, // Sorted by offset
+}
+
+#[derive(Clone, Debug)]
+pub struct Keyframe {
+    pub offset: f32, // 0 (from) to 1 (to)
+    pub styles: Styles,
+    pub properties: Vec<AnimatableProperty>, // The properties specified by the keyframe
+}
+
+impl Keyframes {
+    pub fn new(mut frames: Vec<Keyframe>) -> Self {
+        frames.sort_by(|first, second| first.offset.total_cmp(&second.offset));
+
+        Self { frames }
+    }
+
+    pub fn get_animated_properties(&self) -> Vec<AnimatableProperty> {
+        let mut properties: Vec<AnimatableProperty> = Vec::new();
+        for property in self.frames.iter().flat_map(|frame| frame.properties.iter()) {
+            if !properties.contains(property) {
+                properties.push(*property);
+            }
+        }
+
+        properties
+    }
+}
+
+pub fn get_or_create_keyframes_id(name: &str) -> usize {
+    let mut registry = KEYFRAMES_REGISTRY.lock().unwrap();
+    if let Some(id) = registry.ids.get(name) {
+        return *id;
+    }
+
+    let id = registry.keyframes.len();
+    registry.keyframes.push(None);
+    registry.ids.insert(name.to_string(), id);
+
+    id
+}
+
+pub fn register_keyframes(name: &str, keyframes: Keyframes) {
+    let id = get_or_create_keyframes_id(name);
+
+    let mut registry = KEYFRAMES_REGISTRY.lock().unwrap();
+    registry.keyframes[id] = Some(Arc::new(keyframes));
+}
+
+pub fn get_keyframes(id: usize) -> Option<Arc<Keyframes>> {
+    let registry = KEYFRAMES_REGISTRY.lock().unwrap();
+    registry.keyframes.get(id).cloned().flatten()
+}
@@ -0,0 +1,12 @@
+/*
+ * CSS transitions and @keyframes animations.
+ * Each element owns a StyleAnimator, which interpolates its displayed styles towards the target ones
+ * on every animation frame (see Element::update), while the Styles struct holds the parsed specifications.
+ * Scrollable containers similarly own a ScrollAnimator for smooth scrolling.
+ */
+
+pub mod timing_function;
+pub mod interpolation;
+pub mod keyframes_registry;
+pub mod style_animator;
+pub mod scroll_animator;
@@ -0,0 +1,68 @@
+use crate::rendering::elements::{common_types::Position, styles::TimingFunction};
+
+use super::interpolation::interpolate_number;
+
+
+const SMOOTH_SCROLL_DURATION: f32 = 0.3; // Seconds
+
+/*
+ * Driver of smooth scrolling (ScrollBehavior::Smooth), easing a container's scroll offset towards the target one.
+ * Offsets are in logical pixels, so that the animation keeps its course if the content's size changes meanwhile.
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct ScrollAnimator {
+    from: Position,
+    to: Position,
+    elapsed: f32,
+}
+
+impl ScrollAnimator {
+    pub fn new(from: Position, to: Position) -> Self {
+        Self {
+            from,
+            to,
+            elapsed: 0.0,
+        }
+    }
+
+    // Advances the animation, returning the offset to scroll to
+    pub fn update(&mut self, dt: f32) -> Position {
+        self.elapsed += dt;
+        let progress = TimingFunction::EaseInOut.evaluate(self.elapsed / SMOOTH_SCROLL_DURATION);
+
+        Position {
+            x: interpolate_number(self.from.x, self.to.x, progress),
+            y: interpolate_number(self.from.y, self.to.y, progress),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= SMOOTH_SCROLL_DURATION
+    }
+
+    pub fn get_target_offset(&self) -> Position {
+        self.to
+    }
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll_animator_reaches_target_offset() {
+        // Arrange
+        let mut scroll_animator = ScrollAnimator::new(Position { x: 0.0, y: 100.0 }, Position { x: 0.0, y: 300.0 });
+
+        // Act
+        let halfway_offset = scroll_animator.update(SMOOTH_SCROLL_DURATION / 2.0);
+        let final_offset = scroll_animator.update(SMOOTH_SCROLL_DURATION);
+
+        // Assert
+        assert!((halfway_offset.y - 200.0).abs() < 1.0);
+        assert_eq!(final_offset, Position { x: 0.0, y: 300.0 });
+        assert!(scroll_animator.is_finished());
+    }
+}
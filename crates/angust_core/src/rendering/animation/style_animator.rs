@@ -0,0 +1,300 @@
+use std::sync::Arc;
+
+use crate::rendering::elements::{
+    styles::{AnimatableProperty, Animation, AnimationDirection, AnimationFillMode, IterationCount, Styles, TimingFunction, Transition}
+};
+
+use super::{interpolation::{interpolate_property, is_property_equal}, keyframes_registry::{get_keyframes, Keyframes}};
+
+
+/*
+ * Per-element driver of transitions and keyframes animations.
+ * The element keeps displaying the styles computed here: the target styles with the running
+ * transitions and animation applied on top (transitions first, so that animations override them).
+ * The first styles an element gets are applied without transitions, as in CSS.
+ */
+#[derive(Clone, Default)]
+pub struct StyleAnimator {
+    target_styles: Option<Styles>,
+    transitions: Vec<RunningTransition>,
+    animation: Option<RunningAnimation>,
+}
+
+#[derive(Clone)]
+struct RunningTransition {
+    property: AnimatableProperty,
+    from: Styles,
+    transition: Transition,
+    elapsed: f32,
+}
+
+#[derive(Clone)]
+struct RunningAnimation {
+    animation: Animation,
+    keyframes: Option<Arc<Keyframes>>,
+    elapsed: f32,
+}
+
+impl StyleAnimator {
+    /*
+     * Sets new target styles, starting transitions for the changed properties from the displayed styles.
+     * Returns the styles to display right away.
+     */
+    pub fn set_styles(&mut self, displayed_styles: &Styles, target_styles: Styles) -> Styles {
+        if let Some(previous_target_styles) = self.target_styles.replace(target_styles) {
+            self.start_transitions(displayed_styles, &previous_target_styles, &target_styles);
+        }
+
+        let is_same_animation = self.animation.as_ref()
+            .map_or(false, |running_animation| Some(running_animation.animation) == target_styles.animation);
+        if !is_same_animation {
+            self.animation = target_styles.animation.map(RunningAnimation::new);
+        }
+
+        self.compute_styles()
+    }
+
+    /*
+     * Takes over the transitions and animation of the element this one replaces
+     * (e.g. when a component reloads its template), continuing them towards the current target styles.
+     */
+    pub fn inherit(&mut self, previous_animator: &StyleAnimator, previous_displayed_styles: &Styles) -> Option<Styles> {
+        let target_styles = self.target_styles?;
+        if previous_animator.target_styles.is_none() {
+            return None;
+        }
+
+        self.target_styles = previous_animator.target_styles;
+        self.transitions = previous_animator.transitions.clone();
+        self.animation = previous_animator.animation.clone();
+
+        Some(self.set_styles(previous_displayed_styles, target_styles))
+    }
+
+    // Advances the running transitions and animation, returning the new styles to display if any were running
+    pub fn update(&mut self, dt: f32) -> Option<Styles> {
+        if !self.is_animating() {
+            return None;
+        }
+
+        for running_transition in self.transitions.iter_mut() {
+            running_transition.elapsed += dt;
+        }
+        if let Some(running_animation) = self.animation.as_mut() {
+            running_animation.elapsed += dt;
+        }
+
+        let styles = self.compute_styles();
+        self.transitions.retain(|running_transition| !running_transition.is_finished());
+
+        Some(styles)
+    }
+
+    pub fn is_target(&self, styles: &Styles) -> bool {
+        self.target_styles.as_ref() == Some(styles)
+    }
+
+    pub fn is_animating(&self) -> bool {
+        !self.transitions.is_empty() 
+            || self.animation.as_ref().map_or(false, |running_animation| !running_animation.is_finished())
+    }
+
+    fn start_transitions(&mut self, displayed_styles: &Styles, previous_target_styles: &Styles, target_styles: &Styles) {
+        for property in AnimatableProperty::ALL {
+            if is_property_equal(property, previous_target_styles, target_styles) {
+                continue; // Running transitions towards an unchanged value go on
+            }
+
+            self.transitions.retain(|running_transition| running_transition.property != property);
+
+            let transition = match target_styles.transitions.as_ref().and_then(|transitions| transitions.get(property)) {
+                Some(transition) => *transition,
+                None => continue,
+            };
+            if transition.duration <= 0.0 || is_property_equal(property, displayed_styles, target_styles) {
+                continue;
+            }
+
+            self.transitions.push(RunningTransition { property, from: *displayed_styles, transition, elapsed: 0.0 });
+        }
+    }
+
+    fn compute_styles(&self) -> Styles {
+        let target_styles = self.target_styles.unwrap_or_default();
+        let mut styles = target_styles;
+
+        for running_transition in &self.transitions {
+            running_transition.apply(&target_styles, &mut styles);
+        }
+        if let Some(running_animation) = &self.animation {
+            running_animation.apply(&target_styles, &mut styles);
+        }
+
+        styles
+    }
+}
+
+impl RunningTransition {
+    fn apply(&self, target_styles: &Styles, styles: &mut Styles) {
+        let linear_progress = if self.transition.duration > 0.0 {
+            (self.elapsed - self.transition.delay) / self.transition.duration
+        } else {
+            1.0
+        };
+        let progress = self.transition.timing_function.evaluate(linear_progress);
+
+        interpolate_property(self.property, &self.from, target_styles, progress, styles);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.elapsed >= self.transition.delay + self.transition.duration
+    }
+}
+
+impl RunningAnimation {
+    fn new(animation: Animation) -> Self {
+        Self {
+            animation,
+            keyframes: get_keyframes(animation.keyframes_id),
+            elapsed: 0.0,
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        match self.animation.iteration_count {
+            IterationCount::Infinite => self.keyframes.is_none(),
+            IterationCount::Finite(count) => self.elapsed >= self.animation.delay + self.animation.duration * count,
+        }
+    }
+
+    fn apply(&self, target_styles: &Styles, styles: &mut Styles) {
+        let keyframes = match &self.keyframes {
+            Some(keyframes) => keyframes,
+            None => return,
+        };
+        let progress = match self.get_progress() {
+            Some(progress) => progress,
+            None => return,
+        };
+
+        for property in keyframes.get_animated_properties() {
+            apply_keyframes_property(keyframes, property, target_styles, progress, self.animation.timing_function, styles);
+        }
+    }
+
+    /*
+     * Returns the progress within the current iteration, accounting for the direction,
+     * or None when the animation doesn't apply (before the delay or after the end, depending on the fill mode).
+     */
+    fn get_progress(&self) -> Option<f32> {
+        let animation = &self.animation;
+        let active_time = self.elapsed - animation.delay;
+        let fills_backwards = matches!(animation.fill_mode, AnimationFillMode::Backwards | AnimationFillMode::Both);
+        let fills_forwards = matches!(animation.fill_mode, AnimationFillMode::Forwards | AnimationFillMode::Both);
+
+        if active_time < 0.0 && !fills_backwards {
+            return None;
+        }
+
+        let (iteration, iteration_progress) = match animation.iteration_count {
+            IterationCount::Finite(count) if active_time >= animation.duration * count => {
+                if !fills_forwards {
+                    return None;
+                }
+                // Ends at the end of the last (possibly partial) iteration
+                let last_iteration = (count.ceil() - 1.0).max(0.0);
+                let progress = if count.fract() > 0.0 { count.fract() } else { 1.0 };
+                (last_iteration, progress)
+            },
+            _ if active_time <= 0.0 || animation.duration <= 0.0 => (0.0, 0.0),
+            _ => {
+                let iteration_time = active_time / animation.duration;
+                (iteration_time.floor(), iteration_time.fract())
+            },
+        };
+
+        let is_reversed = match animation.direction {
+            AnimationDirection::Normal => false,
+            AnimationDirection::Reverse => true,
+            AnimationDirection::Alternate => iteration % 2.0 == 1.0,
+            AnimationDirection::AlternateReverse => iteration % 2.0 == 0.0,
+        };
+
+        Some(if is_reversed { 1.0 - iteration_progress } else { iteration_progress })
+    }
+}
+
+/*
+ * Interpolates one property between the two keyframes surrounding the progress.
+ * Keyframes missing at 0% or 100% take the element's own (target) value, as in CSS.
+ * The timing function applies to each segment between keyframes.
+ */
+fn apply_keyframes_property(
+    keyframes: &Keyframes, 
+    property: AnimatableProperty, 
+    target_styles: &Styles, 
+    progress: f32, 
+    timing_function: TimingFunction,
+    styles: &mut Styles
+) {
+    let mut frames: Vec<(f32, &Styles)> = keyframes.frames.iter()
+        .filter(|frame| frame.properties.contains(&property))
+        .map(|frame| (frame.offset, &frame.styles))
+        .collect();
+
+    if frames.first().map_or(true, |(offset, _)| *offset > 0.0) {
+        frames.insert(0, (0.0, target_styles));
+    }
+    if frames.last().map_or(true, |(offset, _)| *offset < 1.0) {
+        frames.push((1.0, target_styles));
+    }
+
+    let segment_end = frames.iter().position(|(offset, _)| *offset >= progress).unwrap_or(frames.len() - 1).max(1);
+    let (start_offset, start_styles) = frames[segment_end - 1];
+    let (end_offset, end_styles) = frames[segment_end];
+
+    let segment_length = end_offset - start_offset;
+    let segment_progress = if segment_length > 0.0 { (progress - start_offset) / segment_length } else { 1.0 };
+
+    interpolate_property(property, start_styles, end_styles, timing_function.evaluate(segment_progress), styles);
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use skia_safe::Color;
+
+    use crate::rendering::elements::styles::{TransitionProperty, Transitions};
+
+    use super::*;
+
+    #[test]
+    fn test_transition_interpolates_changed_property() {
+        // Arrange
+        let mut transitions = Transitions::default();
+        transitions.push(Transition {
+            property: TransitionProperty::Property(AnimatableProperty::BackgroundColor),
+            duration: 0.2,
+            timing_function: TimingFunction::Linear,
+            delay: 0.0,
+        }).unwrap();
+        let initial_styles = Styles { background_color: Some(Color::BLACK), transitions: Some(transitions), ..Default::default() };
+        let target_styles = Styles { background_color: Some(Color::WHITE), ..initial_styles };
+
+        let mut style_animator = StyleAnimator::default();
+        let displayed_styles = style_animator.set_styles(&Styles::default(), initial_styles);
+
+        // Act
+        let transition_start_styles = style_animator.set_styles(&displayed_styles, target_styles);
+        let halfway_styles = style_animator.update(0.1);
+        let end_styles = style_animator.update(0.15);
+
+        // Assert
+        assert_eq!(displayed_styles.background_color, Some(Color::BLACK));
+        assert_eq!(transition_start_styles.background_color, Some(Color::BLACK));
+        assert_eq!(halfway_styles.unwrap().background_color, Some(Color::from_rgb(128, 128, 128)));
+        assert_eq!(end_styles.unwrap().background_color, Some(Color::WHITE));
+        assert!(!style_animator.is_animating());
+    }
+}
@@ -0,0 +1,93 @@
+use crate::rendering::elements::styles::TimingFunction;
+
+
+impl TimingFunction {
+    /*
+     * Maps the linear progress of a transition or animation (0 to 1) to the eased progress.
+     */
+    pub fn evaluate(&self, progress: f32) -> f32 {
+        let progress = progress.clamp(0.0, 1.0);
+
+        match *self {
+            Self::Linear => progress,
+            Self::Ease => evaluate_cubic_bezier(0.25, 0.1, 0.25, 1.0, progress),
+            Self::EaseIn => evaluate_cubic_bezier(0.42, 0.0, 1.0, 1.0, progress),
+            Self::EaseOut => evaluate_cubic_bezier(0.0, 0.0, 0.58, 1.0, progress),
+            Self::EaseInOut => evaluate_cubic_bezier(0.42, 0.0, 0.58, 1.0, progress),
+            Self::CubicBezier(x1, y1, x2, y2) => evaluate_cubic_bezier(x1, y1, x2, y2, progress),
+            Self::Steps(steps) => {
+                let steps = steps.max(1) as f32;
+                (progress * steps).floor().min(steps) / steps
+            },
+        }
+    }
+}
+
+/*
+ * Solves x(t) = progress for the curve parameter t (Newton's method, falling back to bisection), then returns y(t).
+ */
+fn evaluate_cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, progress: f32) -> f32 {
+    if progress <= 0.0 || progress >= 1.0 {
+        return progress;
+    }
+
+    let sample = |a: f32, b: f32, t: f32| 3.0 * a * (1.0 - t).powi(2) * t + 3.0 * b * (1.0 - t) * t.powi(2) + t.powi(3);
+    let sample_derivative = |a: f32, b: f32, t: f32| 3.0 * a * (1.0 - t).powi(2) + 6.0 * (b - a) * (1.0 - t) * t + 3.0 * (1.0 - b) * t.powi(2);
+
+    let mut t = progress;
+    for _ in 0..8 {
+        let error = sample(x1, x2, t) - progress;
+        if error.abs() < 1e-5 {
+            return sample(y1, y2, t);
+        }
+        let derivative = sample_derivative(x1, x2, t);
+        if derivative.abs() < 1e-6 {
+            break;
+        }
+        t -= error / derivative;
+    }
+
+    let (mut lower, mut upper) = (0.0, 1.0);
+    t = progress;
+    for _ in 0..32 {
+        let x = sample(x1, x2, t);
+        if (x - progress).abs() < 1e-5 {
+            break;
+        }
+        if x < progress {
+            lower = t;
+        } else {
+            upper = t;
+        }
+        t = (lower + upper) / 2.0;
+    }
+
+    sample(y1, y2, t)
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate_timing_functions() {
+        // Arrange
+        let cases = vec![
+            (TimingFunction::Linear, 0.25, 0.25),
+            (TimingFunction::EaseInOut, 0.5, 0.5),
+            (TimingFunction::EaseIn, 0.0, 0.0),
+            (TimingFunction::EaseOut, 1.0, 1.0),
+            (TimingFunction::Steps(4), 0.3, 0.25),
+        ];
+
+        for (timing_function, progress, expected) in cases {
+            // Act
+            let eased_progress = timing_function.evaluate(progress);
+
+            // Assert
+            assert!((eased_progress - expected).abs() < 1e-3, "{:?} at {}: {}", timing_function, progress, eased_progress);
+        }
+    }
+}
@@ -1,29 +1,47 @@
 use std::{any::Any, collections::HashMap};
 
-use skia_safe::{Canvas, Color, Point};
-
-use crate::{parsing::directive::for_parser::ForLoopContext, rendering::{layout::size_estimation_system::effective_size_estimator, rendering_interface::element_renderer::ElementRenderer}};
+use skia_safe::{Canvas, Color, Point, Rect};
+
+use crate::{
+    parsing::directive::for_parser::ForLoopContext, 
+    rendering::{
+        animation::{interpolation, style_animator::StyleAnimator},
+        layout::{layout_cache::{AllocationAction, LayoutCache}, size_estimation_system::effective_size_estimator}, 
+        rendering_interface::{element_renderer::ElementRenderer, render_cache::RenderCache}
+    }
+};
 
 use super::{
     common_types::{OptionalSize, Position, Size}, 
     component::component::ComponentInterface, 
     container::Container, element::{Element, ElementType, EventType}, 
     element_id_generator::ElementIDGenerator, 
+    event_handlers::EventHandlers, 
+    event_propagator, 
+    mouse_event::{EventPhase, MouseEvent}, 
+    pseudo_class_styles::{InteractionState, PseudoClassStyles}, 
     styles::Styles
 };
 
 pub struct Button {
     _id: String,
+    pub template_id: Option<String>, // The id attribute, not necessarily unique
 
     container: Option<Vec<Box<dyn Element>>>, // Only one container is allowed
-    pub on_click_handler_name: Option<String>,
-    pub loop_contexts: Vec<ForLoopContext>,
+    pub event_handlers: EventHandlers,
+    pub tab_index: Option<i32>,
+    pub pseudo_class_styles: PseudoClassStyles,
 
     position: Position,
     size: Size,
     styles: Styles,
     natural_size: Size,
     requested_size: OptionalSize,
+    base_styles: Styles, // Before pseudo-class rules
+    interaction_state: InteractionState,
+    layout_cache: LayoutCache,
+    render_cache: RenderCache,
+    style_animator: StyleAnimator,
 }
 
 impl Button {
@@ -40,17 +58,32 @@ impl Button {
         } else {
             None
         };
+        let mut style_animator = StyleAnimator::default();
+        let base_styles = styles.unwrap_or_default();
+        let styles = style_animator.set_styles(&Styles::default(), base_styles);
+
+        let mut event_handlers = EventHandlers::new(loop_contexts.unwrap_or(vec![]));
+        if let Some(on_click_handler_name) = on_click_handler_name {
+            event_handlers.add_handler("click".to_string(), on_click_handler_name);
+        }
 
         Self {
             _id: id,
+            template_id: None,
             container: container_vec,
-            on_click_handler_name,
-            loop_contexts: loop_contexts.unwrap_or(vec![]),
+            event_handlers,
+            tab_index: Some(0), // Buttons are focusable by default
+            pseudo_class_styles: PseudoClassStyles::default(),
             position: Position::default(),
             size: Size::default(),
-            styles: styles.unwrap_or_default(),
+            styles,
             natural_size: Size::default(),
             requested_size: OptionalSize::default(),
+            base_styles,
+            interaction_state: InteractionState::default(),
+            layout_cache: LayoutCache::default(),
+            render_cache: RenderCache::default(),
+            style_animator,
         }
     }
     
@@ -58,10 +91,34 @@ impl Button {
         self.container.as_ref()
     }
 
-    // Utils    
-    fn is_position_within_bounds(&self, point: skia_safe::Point) -> bool {
-        point.x >= self.position.x && point.x <= self.position.x + self.size.width &&
-        point.y >= self.position.y && point.y <= self.position.y + self.size.height
+    // Displays the styles matching the current interaction state (eg. :focus rules)
+    fn apply_interaction_state(&mut self) {
+        if self.pseudo_class_styles.is_empty() {
+            return;
+        }
+        let styles = self.pseudo_class_styles.resolve(self.base_styles, &self.interaction_state);
+        if self.style_animator.is_target(&styles) {
+            return; // The matching rules leave the styles unchanged
+        }
+        let displayed_styles = self.style_animator.set_styles(&self.styles, styles);
+        self.apply_animated_styles(displayed_styles);
+    }
+
+    pub fn set_disabled(&mut self, is_disabled: bool) {
+        if self.interaction_state.is_disabled == is_disabled {
+            return;
+        }
+        self.interaction_state.is_disabled = is_disabled;
+        self.apply_interaction_state();
+    }
+
+    // Displays the styles computed by the style animator on this frame
+    fn apply_animated_styles(&mut self, styles: Styles) {
+        if interpolation::affects_layout(&self.styles, &styles) {
+            self.layout_cache.mark_dirty();
+        }
+        self.styles = styles;
+        self.render_cache.invalidate();
     }
 }
 
@@ -75,55 +132,145 @@ impl Element for Button {
             }
         } else { false };
         if !has_children {
+            self.render_cache.mark_painted(self.get_visual_bounds());
             return;
         }
         
+        let save_count = ElementRenderer::begin_effects(canvas, self.position, self.size, &self.styles);
+
         ElementRenderer::render_element(
             canvas,
             self.position,
             self.size,
             self.styles.background_color.unwrap_or(Color::TRANSPARENT),
-            self.styles.border.unwrap_or_default().width,
-            self.styles.border.unwrap_or_default().color,
+            &self.styles.border.unwrap_or_default(),
+            self.styles.text_color.unwrap_or(Color::BLACK),
         );
+        ElementRenderer::render_inset_shadows(canvas, self.position, self.size, &self.styles);
 
         if let Some(child_container) = self.get_children() {
             if let Some(child_element) = child_container.get(0) {
                 child_element.render(canvas);
             }
         }
+
+        ElementRenderer::end_effects(canvas, save_count);
+        self.render_cache.mark_painted(self.get_visual_bounds());
     }
 
-    fn update(&mut self) {}
+    fn update(&mut self, dt: f32) {
+        if let Some(styles) = self.style_animator.update(dt) {
+            self.apply_animated_styles(styles);
+        }
+
+        let mut has_dirty_child = false;
+        if let Some(child_element) = self.get_children_mut().and_then(|children| children.get_mut(0)) {
+            child_element.update(dt);
+            has_dirty_child = child_element.needs_layout();
+        }
+        if has_dirty_child {
+            self.layout_cache.mark_descendants_dirty();
+        }
+    }
 
     fn handle_event(&mut self, _: Point, _: &EventType) {
         
     }
 
     fn propagate_event(&mut self, cursor_position: skia_safe::Point, event_type: &EventType) -> Vec<EventPropagationData> {
-        let mut event_targets = Vec::new();
+        event_propagator::propagate_event(self, cursor_position, event_type)
+    }
 
-        // Check if the cursor_position is within the bounds of the button
-        let is_position_within_bounds = self.is_position_within_bounds(cursor_position);
-        if !is_position_within_bounds {
-            return event_targets;
+    fn propagate_targeted_event(&mut self, target_id: Option<&String>, event_type: &EventType) -> Option<Vec<EventPropagationData>> {
+        let mut event_targets = event_propagator::propagate_targeted_event(self, target_id, event_type)?;
+        if event_propagator::should_handle_targeted_event(self, target_id, event_type) {
+            event_targets.extend(self.event_handlers.get_propagation_data(event_type));
         }
 
-        if self.on_click_handler_name.is_none() {
-            return event_targets;
+        Some(event_targets)
+    }
+
+    fn dispatch_mouse_event(&mut self, path: &[String], mouse_event: &MouseEvent, phase: EventPhase) -> Vec<EventPropagationData> {
+        let event_targets = self.event_handlers.get_dispatch_data(&self._id, path, mouse_event, phase);
+        event_propagator::dispatch_mouse_event(self, path, mouse_event, phase, event_targets)
+    }
+
+    // Focus
+    fn get_tab_index(&self) -> Option<i32> {
+        self.tab_index.filter(|_| !self.interaction_state.is_disabled)
+    }
+
+    fn set_focused(&mut self, is_focused: bool) {
+        if self.interaction_state.is_focused == is_focused {
+            return;
         }
-        let handler_name = self.on_click_handler_name.as_ref().unwrap();
-        
-        match event_type {
-            EventType::MouseClick => {
-                event_targets.push(EventPropagationData { handler_name: handler_name.clone(), for_loop_contexts: self.loop_contexts.clone() });
-            },
-            _ => {}
+        self.interaction_state.is_focused = is_focused;
+        self.apply_interaction_state();
+    }
+
+    // Pointer interaction
+    fn set_hovered(&mut self, is_hovered: bool) {
+        if self.interaction_state.is_hovered == is_hovered {
+            return;
         }
+        self.interaction_state.is_hovered = is_hovered;
+        self.apply_interaction_state();
+    }
 
-        // TODO: Propagate to children as well
+    fn set_active(&mut self, is_active: bool) {
+        if self.interaction_state.is_active == is_active {
+            return;
+        }
+        self.interaction_state.is_active = is_active;
+        self.apply_interaction_state();
+    }
 
-        event_targets
+    fn is_disabled(&self) -> bool {
+        self.interaction_state.is_disabled
+    }
+
+    // Animation
+    fn needs_animation_frame(&self) -> bool {
+        self.style_animator.is_animating() || self.get_children()
+            .and_then(|children| children.get(0))
+            .map_or(false, |child_element| child_element.needs_animation_frame())
+    }
+
+    fn get_style_animator(&self) -> Option<&StyleAnimator> {
+        Some(&self.style_animator)
+    }
+
+    fn inherit_style_animator(&mut self, previous_animator: &StyleAnimator, previous_styles: &Styles) {
+        if let Some(styles) = self.style_animator.inherit(previous_animator, previous_styles) {
+            self.apply_animated_styles(styles);
+        }
+    }
+
+    // Rendering cache
+    fn needs_repaint(&self) -> bool {
+        self.render_cache.needs_repaint() || self.get_children()
+            .and_then(|children| children.get(0))
+            .map_or(false, |child_element| child_element.needs_repaint())
+    }
+
+    fn get_visual_bounds(&self) -> Rect {
+        let mut visual_bounds = Rect::from_xywh(self.position.x, self.position.y, self.size.width, self.size.height);
+        if let Some(child_element) = self.get_children().and_then(|children| children.get(0)) {
+            visual_bounds.join(child_element.get_visual_bounds());
+        }
+
+        ElementRenderer::transform_bounds(visual_bounds, self.position, self.size, &self.styles)
+    }
+
+    fn collect_damage(&self, damaged_rects: &mut Vec<Rect>) {
+        if self.render_cache.is_stale() {
+            self.render_cache.collect_own_damage(self.get_visual_bounds(), damaged_rects);
+            return;
+        }
+
+        if let Some(child_element) = self.get_children().and_then(|children| children.get(0)) {
+            child_element.collect_damage(damaged_rects);
+        }
     }
 
 
@@ -132,15 +279,25 @@ impl Element for Button {
     }
 
     fn set_position(&mut self, position: Position) {
+        if self.position != position {
+            self.render_cache.invalidate();
+        }
         self.position = position;
     }
 
     fn set_size(&mut self, size: Size) {
+        if self.size != size {
+            self.render_cache.invalidate();
+        }
         self.size = size;
     }
 
     fn set_styles(&mut self, styles: Styles) {
-        self.styles = styles;
+        self.base_styles = styles;
+        let styles = self.pseudo_class_styles.resolve(styles, &self.interaction_state);
+        self.styles = self.style_animator.set_styles(&self.styles, styles);
+        self.layout_cache.mark_dirty();
+        self.render_cache.invalidate();
     }
 
     fn add_child(&mut self, container: Box<dyn Element>) {
@@ -150,12 +307,18 @@ impl Element for Button {
         }
 
         self.container = Some(vec![container]);
+        self.layout_cache.mark_dirty();
+        self.render_cache.invalidate();
     }
 
     fn get_id(&self) -> String {
         self._id.clone()
     }
 
+    fn get_template_id(&self) -> Option<&String> {
+        self.template_id.as_ref()
+    }
+
     fn get_element_type(&self) -> ElementType {
         ElementType::Button
     }
@@ -190,14 +353,20 @@ impl Element for Button {
     }
 
     fn handle_route_change(&mut self, route: &String, component_name: &String) {
+        let mut has_dirty_child = false;
         if let Some(child_container) = self.get_children_mut() {
             if child_container.len() != 1 {
                 return;
             }
             if let Some(child_element) = child_container.get_mut(0) {
                 child_element.handle_route_change(route, component_name);
+                has_dirty_child = child_element.needs_layout();
             }
         }
+
+        if has_dirty_child {
+            self.layout_cache.mark_descendants_dirty();
+        }
     }
 
     // Layout system
@@ -225,7 +394,16 @@ impl Element for Button {
 
     
     fn estimate_sizes(&mut self) {
+        if !self.layout_cache.needs_estimation() {
+            if let Some((natural_size, requested_size)) = self.layout_cache.get_estimated_sizes() {
+                self.natural_size = natural_size;
+                self.requested_size = requested_size;
+            }
+            return;
+        }
+
         let (mut natural_size, mut requested_size) = (None, None);
+        let mut has_child_estimate_changed = false;
 
         if let Some(child_container) = self.get_children_mut() {
             if child_container.len() != 1 {
@@ -236,6 +414,7 @@ impl Element for Button {
 
                 natural_size = Some(child_element.get_natural_size());
                 requested_size = Some(child_element.get_requested_size());
+                has_child_estimate_changed = child_element.has_estimate_changed();
             }
         } 
 
@@ -245,21 +424,39 @@ impl Element for Button {
         if let Some(size) = requested_size {
             self.set_requested_size(size);
         }
+        if has_child_estimate_changed {
+            self.layout_cache.mark_allocation_needed();
+        }
+
+        self.layout_cache.store_estimated_sizes(self.natural_size, self.requested_size);
     }
 
     // Second pass: Traverse the DOM from root to leaves and allocate space to each container.
     fn allocate_space(&mut self, allocated_position: Position, allocated_size: Size) {
+        let allocation_action = self.layout_cache.get_allocation_action(allocated_position, allocated_size);
+        if allocation_action == AllocationAction::Skip {
+            return;
+        }
+
+        if allocation_action == AllocationAction::Full {
+            self.render_cache.invalidate();
+        }
         self.position = allocated_position;
         self.size = allocated_size;
 
-        if let Some(child_container) = self.get_children_mut() {
-            if child_container.len() != 1 {
-                return;
-            }
+        // Only a single child is laid out; the allocation is stored regardless, so that the button doesn't stay dirty
+        if let Some(child_container) = self.get_children_mut().filter(|child_container| child_container.len() == 1) {
             if let Some(child_element) = child_container.get_mut(0) {
-                child_element.allocate_space(allocated_position, allocated_size);
+                if allocation_action == AllocationAction::Full {
+                    child_element.estimate_sizes();
+                    child_element.allocate_space(allocated_position, allocated_size);
+                } else if child_element.needs_layout() {
+                    child_element.allocate_pending_space();
+                }
             }
         } 
+
+        self.layout_cache.store_allocation(allocated_position, allocated_size);
     }
     
     fn layout(&mut self, allocated_position: Position, allocated_size: Size) {
@@ -267,16 +464,41 @@ impl Element for Button {
         self.allocate_space(allocated_position, allocated_size);
     }
 
+    // Incremental layout
+    fn needs_layout(&self) -> bool {
+        self.layout_cache.needs_layout()
+    }
+
+    fn mark_layout_dirty(&mut self) {
+        self.layout_cache.mark_dirty();
+    }
+
+    fn has_estimate_changed(&self) -> bool {
+        self.layout_cache.has_estimate_changed()
+    }
+
+    fn allocate_pending_space(&mut self) {
+        if let Some((allocated_position, allocated_size)) = self.layout_cache.get_allocation() {
+            self.allocate_space(allocated_position, allocated_size);
+        }
+    }
+
     // Reactivity
     fn react_to_state_change(&mut self, component_id: String) {
+        let mut has_dirty_child = false;
         if let Some(child_container) = self.get_children_mut() {
             if child_container.len() != 1 {
                 return;
             }
             if let Some(child_element) = child_container.get_mut(0) {
                 child_element.react_to_state_change(component_id);
+                has_dirty_child = child_element.needs_layout();
             }
         }
+
+        if has_dirty_child {
+            self.layout_cache.mark_descendants_dirty();
+        }
     }
 }
 
@@ -284,4 +506,6 @@ impl Element for Button {
 pub struct EventPropagationData {
     pub handler_name: String,
     pub for_loop_contexts: Vec<ForLoopContext>,
+    pub event: Option<EventType>, // If other than the event being propagated (eg. an input event emitted on key down)
+    pub mouse_event: Option<MouseEvent>, // Payload of dispatched mouse events, with the element the handler is bound to
 }
\ No newline at end of file
@@ -0,0 +1,523 @@
+use std::{any::Any, collections::HashMap};
+
+use skia_safe::{Canvas, Color, Point, Rect};
+
+use crate::rendering::{
+    animation::{interpolation, style_animator::StyleAnimator},
+    layout::{layout_cache::{AllocationAction, LayoutCache}, size_estimation_system::effective_size_estimator},
+    rendering_interface::{element_renderer::ElementRenderer, render_cache::RenderCache}
+};
+
+use super::{
+    button::EventPropagationData,
+    common_types::{OptionalSize, Position, Size},
+    component::component::ComponentInterface,
+    element::{Element, ElementType, EventType},
+    element_id_generator::ElementIDGenerator,
+    event_handlers::EventHandlers,
+    event_propagator,
+    form_control::FormControlState,
+    input::InputType,
+    mouse_event::{EventPhase, MouseEvent},
+    pseudo_class_styles::{InteractionState, PseudoClassStyles},
+    styles::{Border, BorderSide, Dimension, Styles, Unit}
+};
+
+
+const DEFAULT_SIZE: f32 = 11.0; // Of the content box, 13px with the default border as in browsers
+pub const DEFAULT_ACCENT_COLOR: Color = Color::from_rgb(0, 117, 255);
+
+/*
+ * Checkbox or radio button (input type="checkbox" or "radio"), toggled by clicking it or pressing Space once focused.
+ * Radio buttons with the same name form a group in which only one is checked (see radio_groups), and cannot be unchecked by the user.
+ * Emits input and change events with "true" or "false" for checkboxes, and with their value for radio buttons once checked.
+ * When checked, the box is filled with the text color.
+ */
+pub struct Checkbox {
+    _id: String,
+    pub template_id: Option<String>, // The id attribute, not necessarily unique
+    input_type: InputType, // Checkbox or Radio
+    pub name: Option<String>, // Group of radio buttons
+    pub value: String, // Of radio buttons, emitted once checked
+    pub event_handlers: EventHandlers,
+    pub tab_index: Option<i32>,
+    pub pseudo_class_styles: PseudoClassStyles,
+
+    control_state: FormControlState,
+    is_newly_checked: bool, // Until the other radio buttons of the group are unchecked
+
+    position: Position,
+    size: Size,
+    styles: Styles,
+    natural_size: Size,
+    requested_size: OptionalSize,
+    base_styles: Styles, // Before pseudo-class rules
+    interaction_state: InteractionState,
+    layout_cache: LayoutCache,
+    render_cache: RenderCache,
+    style_animator: StyleAnimator,
+}
+
+impl Checkbox {
+    pub fn new(input_type: InputType, is_checked: bool, styles: Option<Styles>) -> Self {
+        let id = ElementIDGenerator::get();
+        let mut style_animator = StyleAnimator::default();
+        let base_styles = styles.unwrap_or_default();
+        let styles = style_animator.set_styles(&Styles::default(), base_styles);
+
+        Self {
+            _id: id,
+            template_id: None,
+            input_type,
+            name: None,
+            value: "on".to_string(), // As in the DOM
+            event_handlers: EventHandlers::default(),
+            tab_index: Some(0), // Checkboxes are focusable by default
+            pseudo_class_styles: PseudoClassStyles::default(),
+            control_state: FormControlState::new(is_checked.to_string()),
+            is_newly_checked: false,
+            position: Position::default(),
+            size: Size::default(),
+            styles,
+            natural_size: Size::default(),
+            requested_size: OptionalSize::default(),
+            base_styles,
+            interaction_state: InteractionState::default(),
+            layout_cache: LayoutCache::default(),
+            render_cache: RenderCache::default(),
+            style_animator,
+        }
+    }
+
+    pub fn get_default_styles() -> Styles {
+        Styles {
+            border: Some(Border::uniform(BorderSide {
+                width: Dimension { value: 1.0, unit: Unit::Px },
+                color: Some(Color::from_rgb(118, 118, 118)),
+                ..Default::default()
+            })),
+            background_color: Some(Color::WHITE),
+            ..Default::default()
+        }
+    }
+
+    pub fn is_checked(&self) -> bool {
+        self.control_state.is_checked()
+    }
+
+    pub fn get_input_type(&self) -> InputType {
+        self.input_type
+    }
+
+    // Default actions
+    /*
+     * Toggles the checkbox, or checks the radio button, returning the handlers of the input and change events.
+     * Checked radio buttons stay checked until another one of their group is.
+     */
+    fn toggle(&mut self) -> Vec<EventPropagationData> {
+        let is_radio = self.input_type == InputType::Radio;
+        if is_radio && self.is_checked() {
+            return Vec::new();
+        }
+
+        self.control_state.set_checked(!self.is_checked());
+        self.is_newly_checked = is_radio && self.name.is_some();
+        self.render_cache.invalidate();
+
+        let value = if is_radio { self.value.clone() } else { self.is_checked().to_string() };
+        let mut event_targets = self.event_handlers.get_propagation_data(&EventType::Input(value.clone()));
+        event_targets.extend(self.event_handlers.get_propagation_data(&EventType::Change(value)));
+        event_targets
+    }
+
+    fn apply_default_action(&mut self, event_type: &EventType) -> Vec<EventPropagationData> {
+        match event_type {
+            EventType::KeyDown(keyboard_event) if keyboard_event.key == " " => self.toggle(),
+            _ => Vec::new(),
+        }
+    }
+
+    // Utils
+    fn is_position_within_bounds(&self, point: Point) -> bool {
+        point.x >= self.position.x && point.x <= self.position.x + self.size.width &&
+        point.y >= self.position.y && point.y <= self.position.y + self.size.height
+    }
+
+    // Displays the styles matching the current interaction state (eg. :focus rules)
+    fn apply_interaction_state(&mut self) {
+        if self.pseudo_class_styles.is_empty() {
+            return;
+        }
+        let styles = self.pseudo_class_styles.resolve(self.base_styles, &self.interaction_state);
+        if self.style_animator.is_target(&styles) {
+            return; // The matching rules leave the styles unchanged
+        }
+        let displayed_styles = self.style_animator.set_styles(&self.styles, styles);
+        self.apply_animated_styles(displayed_styles);
+    }
+
+    pub fn set_disabled(&mut self, is_disabled: bool) {
+        if self.interaction_state.is_disabled == is_disabled {
+            return;
+        }
+        self.interaction_state.is_disabled = is_disabled;
+        self.apply_interaction_state();
+    }
+
+    // Displays the styles computed by the style animator on this frame
+    fn apply_animated_styles(&mut self, styles: Styles) {
+        if interpolation::affects_layout(&self.styles, &styles) {
+            self.layout_cache.mark_dirty();
+        }
+        self.styles = styles;
+        self.render_cache.invalidate();
+    }
+}
+
+impl Element for Checkbox {
+    fn render(&self, canvas: &Canvas) {
+        let save_count = ElementRenderer::begin_effects(canvas, self.position, self.size, &self.styles);
+
+        let is_checked = self.is_checked();
+        let accent_color = self.styles.text_color.unwrap_or(DEFAULT_ACCENT_COLOR);
+        let background_color = self.styles.background_color.unwrap_or(Color::TRANSPARENT);
+        let border = self.styles.border.unwrap_or_default();
+
+        match self.input_type {
+            InputType::Radio => {
+                let center = Point::new(self.position.x + self.size.width / 2.0, self.position.y + self.size.height / 2.0);
+                let radius = self.size.width.min(self.size.height) / 2.0;
+                let border_color = if is_checked { accent_color } else { border.top.color.unwrap_or(accent_color) };
+                ElementRenderer::render_circle(canvas, center, radius, background_color, border.top.get_width(), border_color);
+                if is_checked {
+                    ElementRenderer::render_circle(canvas, center, radius * 0.5, accent_color, 0.0, Color::TRANSPARENT);
+                }
+            },
+            _ => {
+                if is_checked {
+                    let mut checked_border = border;
+                    checked_border.get_sides_mut().into_iter().for_each(|side| side.color = Some(accent_color));
+                    ElementRenderer::render_element(canvas, self.position, self.size, accent_color, &checked_border, accent_color);
+                    ElementRenderer::render_check_mark(canvas, self.position, self.size, Color::WHITE);
+                } else {
+                    ElementRenderer::render_element(canvas, self.position, self.size, background_color, &border, accent_color);
+                }
+            }
+        }
+
+        ElementRenderer::end_effects(canvas, save_count);
+        self.render_cache.mark_painted(self.get_visual_bounds());
+    }
+
+    fn update(&mut self, dt: f32) {
+        if let Some(styles) = self.style_animator.update(dt) {
+            self.apply_animated_styles(styles);
+        }
+    }
+
+    fn handle_event(&mut self, _: Point, _: &EventType) {}
+
+    fn propagate_event(&mut self, cursor_position: skia_safe::Point, event_type: &EventType) -> Vec<EventPropagationData> {
+        match event_type {
+            EventType::MouseClick if self.is_position_within_bounds(cursor_position) && !self.interaction_state.is_disabled => self.toggle(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn propagate_targeted_event(&mut self, target_id: Option<&String>, event_type: &EventType) -> Option<Vec<EventPropagationData>> {
+        let mut event_targets = event_propagator::propagate_targeted_event(self, target_id, event_type)?;
+        if event_propagator::should_handle_targeted_event(self, target_id, event_type) {
+            event_targets.extend(self.event_handlers.get_propagation_data(event_type));
+        }
+        event_targets.extend(self.apply_default_action(event_type));
+
+        Some(event_targets)
+    }
+
+    fn dispatch_mouse_event(&mut self, path: &[String], mouse_event: &MouseEvent, phase: EventPhase) -> Vec<EventPropagationData> {
+        let event_targets = self.event_handlers.get_dispatch_data(&self._id, path, mouse_event, phase);
+        event_propagator::dispatch_mouse_event(self, path, mouse_event, phase, event_targets)
+    }
+
+    // Focus
+    fn get_tab_index(&self) -> Option<i32> {
+        self.tab_index.filter(|_| !self.interaction_state.is_disabled)
+    }
+
+    fn set_focused(&mut self, is_focused: bool) {
+        if self.interaction_state.is_focused == is_focused {
+            return;
+        }
+        self.interaction_state.is_focused = is_focused;
+        self.apply_interaction_state();
+    }
+
+    // Pointer interaction
+    fn set_hovered(&mut self, is_hovered: bool) {
+        if self.interaction_state.is_hovered == is_hovered {
+            return;
+        }
+        self.interaction_state.is_hovered = is_hovered;
+        self.apply_interaction_state();
+    }
+
+    fn set_active(&mut self, is_active: bool) {
+        if self.interaction_state.is_active == is_active {
+            return;
+        }
+        self.interaction_state.is_active = is_active;
+        self.apply_interaction_state();
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.interaction_state.is_disabled
+    }
+
+    // Form controls
+    fn get_form_control_state(&self) -> Option<&FormControlState> {
+        Some(&self.control_state)
+    }
+
+    fn inherit_form_control_state(&mut self, previous_state: &FormControlState) {
+        self.control_state.inherit(previous_state);
+        self.render_cache.invalidate();
+    }
+
+    fn take_checked_radio_group(&mut self) -> Option<String> {
+        if !self.is_newly_checked {
+            return None;
+        }
+
+        self.is_newly_checked = false;
+        self.name.clone()
+    }
+
+    fn uncheck_radio(&mut self, group_name: &String) {
+        if self.input_type != InputType::Radio || self.name.as_ref() != Some(group_name) || !self.is_checked() {
+            return;
+        }
+
+        self.control_state.set_checked(false);
+        self.render_cache.invalidate();
+    }
+
+    // Animation
+    fn needs_animation_frame(&self) -> bool {
+        self.style_animator.is_animating()
+    }
+
+    fn get_style_animator(&self) -> Option<&StyleAnimator> {
+        Some(&self.style_animator)
+    }
+
+    fn inherit_style_animator(&mut self, previous_animator: &StyleAnimator, previous_styles: &Styles) {
+        if let Some(styles) = self.style_animator.inherit(previous_animator, previous_styles) {
+            self.apply_animated_styles(styles);
+        }
+    }
+
+    // Rendering cache
+    fn needs_repaint(&self) -> bool {
+        self.render_cache.needs_repaint()
+    }
+
+    fn get_visual_bounds(&self) -> Rect {
+        let visual_bounds = Rect::from_xywh(self.position.x, self.position.y, self.size.width, self.size.height);
+
+        ElementRenderer::transform_bounds(visual_bounds, self.position, self.size, &self.styles)
+    }
+
+    fn collect_damage(&self, damaged_rects: &mut Vec<Rect>) {
+        if self.render_cache.needs_repaint() {
+            self.render_cache.collect_own_damage(self.get_visual_bounds(), damaged_rects);
+        }
+    }
+
+    fn set_id(&mut self, id: String) {
+        self._id = id;
+    }
+
+    fn set_position(&mut self, position: Position) {
+        if self.position != position {
+            self.render_cache.invalidate();
+        }
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Size) {
+        if self.size != size {
+            self.render_cache.invalidate();
+        }
+        self.size = size;
+    }
+
+    fn set_styles(&mut self, styles: Styles) {
+        self.base_styles = styles;
+        let styles = self.pseudo_class_styles.resolve(styles, &self.interaction_state);
+        self.styles = self.style_animator.set_styles(&self.styles, styles);
+        self.layout_cache.mark_dirty();
+        self.render_cache.invalidate();
+    }
+
+    fn add_child(&mut self, _: Box<dyn Element>) {}
+
+    fn get_id(&self) -> String {
+        self._id.clone()
+    }
+
+    fn get_template_id(&self) -> Option<&String> {
+        self.template_id.as_ref()
+    }
+
+    fn get_element_type(&self) -> ElementType {
+        ElementType::Checkbox
+    }
+
+    fn get_name(&self) -> String {
+        "input".to_string()
+    }
+
+    fn get_position(&self) -> Position {
+        self.position
+    }
+
+    fn get_size(&self) -> Size {
+        self.size
+    }
+
+    fn get_styles(&self) -> Styles {
+        self.styles
+    }
+
+    fn get_children_mut(&mut self) -> Option<&mut Vec<Box<dyn Element>>> { None }
+
+    // Component system
+    fn get_component_interface(&mut self) -> Option<&mut dyn ComponentInterface> {
+        None
+    }
+
+    fn initialize(&mut self, _: HashMap<String, Box<dyn Any>>) {
+        // Nothing for now (implemented for components only)
+    }
+
+    fn handle_route_change(&mut self, _: &String, _: &String) {
+        // Nothing for now (implemented for components only)
+    }
+
+    // Layout system
+    fn set_natural_size(&mut self, size: Size) {
+        self.natural_size = size;
+    }
+
+    fn set_requested_size(&mut self, requested_size: OptionalSize) {
+        self.requested_size = requested_size;
+    }
+
+    fn get_natural_size(&self) -> Size {
+        self.natural_size
+    }
+
+    fn get_requested_size(&self) -> OptionalSize {
+        self.requested_size
+    }
+
+    fn get_effective_size(&self) -> Size {
+        effective_size_estimator::estimate_effective_size(&self.get_requested_size(), &self.get_natural_size())
+    }
+
+    fn is_text_wrapper(&self) -> bool { false }
+
+    fn estimate_sizes(&mut self) {
+        if !self.layout_cache.needs_estimation() {
+            if let Some((natural_size, requested_size)) = self.layout_cache.get_estimated_sizes() {
+                self.natural_size = natural_size;
+                self.requested_size = requested_size;
+            }
+            return;
+        }
+
+        let border = self.styles.border.unwrap_or_default();
+        let padding = self.styles.padding.unwrap_or_default();
+        self.set_natural_size(Size {
+            width: DEFAULT_SIZE + border.horizontal() + padding.horizontal(),
+            height: DEFAULT_SIZE + border.vertical() + padding.vertical(),
+        });
+
+        let sizing_policy = self.get_styles().sizing_policy.unwrap_or_default();
+        self.set_requested_size(OptionalSize { width: sizing_policy.width, height: sizing_policy.height });
+
+        self.layout_cache.store_estimated_sizes(self.natural_size, self.requested_size);
+    }
+
+    fn allocate_space(&mut self, allocated_position: Position, allocated_size: Size) {
+        if self.layout_cache.get_allocation_action(allocated_position, allocated_size) != AllocationAction::Full {
+            return;
+        }
+
+        self.position = allocated_position;
+        self.size = allocated_size;
+
+        self.layout_cache.store_allocation(allocated_position, allocated_size);
+    }
+
+    fn layout(&mut self, allocated_position: Position, allocated_size: Size) {
+        self.estimate_sizes();
+        self.allocate_space(allocated_position, allocated_size);
+    }
+
+    // Incremental layout
+    fn needs_layout(&self) -> bool {
+        self.layout_cache.needs_layout()
+    }
+
+    fn mark_layout_dirty(&mut self) {
+        self.layout_cache.mark_dirty();
+    }
+
+    fn has_estimate_changed(&self) -> bool {
+        self.layout_cache.has_estimate_changed()
+    }
+
+    fn allocate_pending_space(&mut self) {
+        if let Some((allocated_position, allocated_size)) = self.layout_cache.get_allocation() {
+            self.allocate_space(allocated_position, allocated_size);
+        }
+    }
+
+    fn react_to_state_change(&mut self, _component_id: String) {}
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use crate::rendering::elements::{container::Container, element::KeyboardEvent, radio_groups};
+
+    use super::*;
+
+    fn create_radio(name: &str, is_checked: bool) -> Checkbox {
+        let mut radio = Checkbox::new(InputType::Radio, is_checked, None);
+        radio.name = Some(name.to_string());
+        radio
+    }
+
+    #[test]
+    fn test_checking_radio_unchecks_its_group() {
+        // Arrange
+        let mut root_element = Container::new();
+        root_element.add_child(Box::new(create_radio("size", true)));
+        root_element.add_child(Box::new(create_radio("size", false)));
+        root_element.add_child(Box::new(create_radio("color", true)));
+        let radio_id = root_element.children[1].get_id();
+
+        // Act
+        root_element.propagate_targeted_event(Some(&radio_id), &EventType::KeyDown(KeyboardEvent::new(" ", "Space")));
+        radio_groups::update_radio_groups(&mut root_element);
+
+        // Assert
+        let checked_states: Vec<bool> = root_element.children.iter()
+            .map(|radio| radio.get_form_control_state().unwrap().is_checked())
+            .collect();
+        assert_eq!(checked_states, vec![false, true, true]);
+    }
+}
@@ -3,7 +3,7 @@ use std::ops::Add;
 use super::styles::{Dimension, Directions};
 
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Position {
     pub x: f32,
     pub y: f32,
@@ -15,7 +15,7 @@ impl Default for Position {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Size {
     pub width: f32,
     pub height: f32,
@@ -55,7 +55,7 @@ impl Default for Space {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct OptionalSize {
     pub width: Option<Dimension>,
     pub height: Option<Dimension>,
@@ -75,8 +75,9 @@ pub struct ScrollbarState {
     pub thumb_scrollbar_width_ratio: f32,
     pub thumb_scrollbar_height_ratio: f32,
     pub is_overflowing: Directions,
-    pub current_scroll_position: Position,
-    pub is_dragging: bool,
+    pub current_scroll_position: Position, // Between 0.0 and 1.0 on each axis
+    pub max_scroll_offset: Position, // Overflow of the content, in logical pixels
+    pub dragged_axis: Option<ScrollbarAxis>, // Scrollbar whose thumb is being dragged
     pub drag_start_position: Position,
     pub drag_start_scroll_position: Position,
     pub scrollbar_thickness: f32,
@@ -92,10 +93,78 @@ impl Default for ScrollbarState {
                 vertical: false,
             },
             current_scroll_position: Position::default(),
-            is_dragging: false,
+            max_scroll_offset: Position::default(),
+            dragged_axis: None,
             drag_start_position: Position::default(),
             drag_start_scroll_position: Position::default(),
             scrollbar_thickness: 10.0,
         }
     }
+}
+
+impl ScrollbarState {
+    // Scrolled distance from the top-left of the content, in logical pixels
+    pub fn get_scroll_offset(&self) -> Position {
+        Position {
+            x: self.current_scroll_position.x * self.max_scroll_offset.x,
+            y: self.current_scroll_position.y * self.max_scroll_offset.y,
+        }
+    }
+
+    pub fn to_scroll_position(&self, offset: Position) -> Position {
+        Position {
+            x: to_relative_position(offset.x, self.max_scroll_offset.x),
+            y: to_relative_position(offset.y, self.max_scroll_offset.y),
+        }
+    }
+
+    /*
+     * Called on layout with the content's overflow along an axis (0.0 if it fits).
+     * Keeps the scrolled distance rather than the relative position, so that the view doesn't jump when items are appended to a list.
+     */
+    pub fn set_max_scroll_offset(&mut self, axis: ScrollbarAxis, max_scroll_offset: f32) {
+        let max_scroll_offset = max_scroll_offset.max(0.0);
+        let scroll_offset = self.get_scroll_offset();
+
+        match axis {
+            ScrollbarAxis::Horizontal => {
+                if self.max_scroll_offset.x > 0.0 {
+                    self.current_scroll_position.x = to_relative_position(scroll_offset.x, max_scroll_offset);
+                }
+                self.max_scroll_offset.x = max_scroll_offset;
+            },
+            ScrollbarAxis::Vertical => {
+                if self.max_scroll_offset.y > 0.0 {
+                    self.current_scroll_position.y = to_relative_position(scroll_offset.y, max_scroll_offset);
+                }
+                self.max_scroll_offset.y = max_scroll_offset;
+            },
+        }
+    }
+}
+
+fn to_relative_position(offset: f32, max_offset: f32) -> f32 {
+    if max_offset <= 0.0 {
+        return 0.0;
+    }
+
+    (offset / max_offset).clamp(0.0, 1.0)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScrollbarAxis {
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScrollBehavior {
+    Instant,
+    Smooth, // Animated over a short duration
+}
+
+impl Default for ScrollBehavior {
+    fn default() -> Self {
+        Self::Instant
+    }
 }
\ No newline at end of file
@@ -1,6 +1,7 @@
 use std::{any::Any, cell::RefCell, collections::HashMap, rc::Rc};
 
 use regex::Regex;
+use skia_safe::Rect;
 
 use crate::{
     application::event_loop_proxy::{get_event_loop_proxy, ApplicationEvent}, 
@@ -13,18 +14,28 @@ use crate::{
             button::EventPropagationData, 
             common_types::{OptionalSize, Position, Size}, 
             container::Container, 
-            element::{Element, ElementType, EventType}, 
+            element::{Element, ElementType, EventPayload, EventType}, 
             element_id_generator::ElementIDGenerator, 
+            event_handlers::EventHandlers, 
             event_propagator, 
+            mouse_event::{EventPhase, MouseEvent}, 
+            state_transfer::transfer_element_state, 
             styles::Styles
         }, 
-        layout::size_estimation_system::effective_size_estimator,  
+        layout::{
+            layout_cache::{AllocationAction, LayoutCache}, 
+            size_estimation_system::effective_size_estimator
+        },  
+        rendering_interface::render_cache::RenderCache,
     }
 };
 
 use super::{
     functions::component_functions::ComponentFunctions, 
-    state::reactivity::{EventQueue, ReactiveState}, 
+    state::{
+        nested_reflectivity::{get_nested_field, is_same_value, set_nested_field}, 
+        reactivity::{EventQueue, ReactiveState}
+    }, 
     template_loader
 };
 
@@ -41,6 +52,8 @@ pub struct Component<State: ReactiveState> {
     natural_size: Size,
     requested_size: OptionalSize,
     styles: Styles,
+    layout_cache: LayoutCache,
+    render_cache: RenderCache,
 
     // User-defined properties
     pub state: State,
@@ -53,6 +66,12 @@ pub struct Component<State: ReactiveState> {
     pub template_expressions_asts: Vec<ASTNode>,
     pub template_event_handler_asts: HashMap<String, ASTNode>,
     pub input_expressions_asts: HashMap<String, ASTNode>,
+
+    // Two-way bindings [(property)]="field_path": property -> field path in the parent's state
+    pub model_bindings: HashMap<String, String>,
+
+    // Host element: its @on<event> handlers belong to the parent component, which runs them
+    pub host_event_handlers: EventHandlers,
 }
 
 impl<State: ReactiveState> Component<State> {
@@ -68,12 +87,16 @@ impl<State: ReactiveState> Component<State> {
             natural_size: Size::default(),
             requested_size: OptionalSize::default(),
             styles: Styles::default(),
+            layout_cache: LayoutCache::default(),
+            render_cache: RenderCache::default(),
             state,
             component_functions: ComponentFunctions::default(),
             event_queue: Rc::new(RefCell::new(EventQueue::new())), 
             template_expressions_asts: vec![],
             template_event_handler_asts: HashMap::new(),
             input_expressions_asts: HashMap::new(),
+            model_bindings: HashMap::new(),
+            host_event_handlers: EventHandlers::default(),
         }
     }
 
@@ -100,7 +123,11 @@ impl<State: ReactiveState> Component<State> {
             let property_name_clone = property_name.clone(); 
             let component_id_clone = component_id.clone(); 
             let event_proxy_clone = event_proxy.clone();
+            let are_frame_callbacks_running = self.component_functions.get_frame_callbacks_running_flag();
             self.state.subscribe_to_property(&property_name_clone, move |event: &ApplicationEvent| {
+                if are_frame_callbacks_running.get() {
+                    return; // Picked up by update, without re-arming the callbacks
+                }
                 match event {
                     ApplicationEvent::StateChange(_) => {
                         event_proxy_clone.send_event(ApplicationEvent::StateChange(component_id_clone.clone()))
@@ -116,6 +143,17 @@ impl<State: ReactiveState> Component<State> {
         template_loader::load_component_template(self, inputs);
     }
 
+    // Naive approach; to be replaced later
+    fn reload_template(&mut self) {
+        let mut previous_content = std::mem::replace(&mut self.content, Box::new(Container::new()));
+        self.load_component_template(HashMap::new());
+        self.content.set_styles(self.styles);
+        transfer_element_state(previous_content.as_mut(), self.content.as_mut()); // Transitions and focus carry on across reloads
+        self.update_children_inputs();
+        self.layout_cache.mark_dirty();
+        self.render_cache.invalidate();
+    }
+
     fn trigger_user_defined_init(&mut self) {
         let user_defined_init_optional = self.component_functions.initialization_function.as_ref();
         if user_defined_init_optional.is_none() {
@@ -145,15 +183,104 @@ impl<State: ReactiveState> Component<State> {
         }
     }
 
+    /*
+     * Registers a callback run on every animation frame with the elapsed time in seconds.
+     * The callback keeps frames coming for as long as it returns true, 
+     * and is re-armed whenever the component's state changes.
+     */
+    pub fn add_frame_callback<F>(&mut self, callback: F)
+    where
+        F: 'static + FnMut(&mut State, f32) -> bool,
+    {
+        self.component_functions.add_frame_callback(callback);
+    }
+
     // Internal
     // - Event handling
-    fn trigger_dynamic_params_event_handler(&mut self, event_name: &String, event_ast: &ASTNode, for_loop_contexts: Vec<ForLoopContext>) {
+    fn trigger_event_handlers(&mut self, event_propagation_datas: Vec<EventPropagationData>, event_type: &EventType) {
+        for data in event_propagation_datas.iter() {
+            let handler_name = &data.handler_name;
+            let for_loop_contexts = &data.for_loop_contexts;
+            let event_type = data.event.as_ref().unwrap_or(event_type);
+            let mouse_event = data.mouse_event.as_ref();
+            if mouse_event.map_or(false, |mouse_event| !mouse_event.should_run_handlers()) {
+                continue; // The propagation was stopped before reaching this handler's element
+            }
+
+            if let Some(handler) = self.component_functions.event_handlers.get_mut(handler_name) {
+                handler(&mut self.state);
+            }
+            
+            if let Some(event_ast) = self.template_event_handler_asts.get(handler_name) {
+                match event_ast.clone() {
+                    ASTNode::Identifier(field_path) => self.write_model_binding(&field_path, event_type),
+                    event_ast => self.trigger_dynamic_params_event_handler(&handler_name.clone(), &event_ast, for_loop_contexts.clone(), event_type, mouse_event), // TODO: Fix this clone
+                }
+            }
+        }
+    }
+
+    // - Two-way binding
+    // [(model)] bindings of form elements write the edited value to the bound field
+    fn write_model_binding(&mut self, field_path: &str, event_type: &EventType) {
+        let value: Box<dyn Any> = match event_type {
+            EventType::Input(value) | EventType::Change(value) => Box::new(value.clone()),
+            _ => return,
+        };
+
+        self.write_model_value(field_path, value);
+    }
+
+    // Custom components bound with [(property)] write their changed properties back to this component's state
+    fn write_back_component_models(&mut self, component_id: &String) {
+        let model_values = match find_template_component(self.content.as_mut(), component_id).and_then(|component| component.get_component_interface()) {
+            Some(component_interface) => component_interface.get_model_values(),
+            None => return,
+        };
+
+        for (field_path, value) in model_values {
+            self.write_model_value(&field_path, value);
+        }
+    }
+
+    // Goes through the normal reactivity path if the value changed
+    fn write_model_value(&mut self, field_path: &str, value: Box<dyn Any>) {
+        let path: Vec<&str> = field_path.split('.').collect();
+        if let Some(current_value) = get_nested_field(&self.state, &path) {
+            if is_same_value(current_value.as_any().as_ref(), value.as_ref()) {
+                return;
+            }
+        }
+
+        match set_nested_field(&mut self.state, &path, value) {
+            Ok(true) => (),
+            Ok(false) => return, // Not convertible yet, eg. "-" being typed in a number field
+            Err(e) => {
+                println!("Error writing to bound field {}: {}", field_path, e);
+                return;
+            },
+        }
+
+        if let Some(event_proxy) = get_event_loop_proxy() {
+            event_proxy.send_event(ApplicationEvent::StateChange(self._id.clone()))
+                .expect("Failed to send event");
+        }
+    }
+
+    fn trigger_dynamic_params_event_handler(
+        &mut self, 
+        event_name: &String, 
+        event_ast: &ASTNode, 
+        for_loop_contexts: Vec<ForLoopContext>, 
+        event_type: &EventType, 
+        mouse_event: Option<&MouseEvent>,
+    ) {
         let params_asts = match event_ast {
             ASTNode::FunctionCall(_, params) => params,
             _ => return,
         };
 
-        let param_values = self.determine_handler_params(params_asts, for_loop_contexts);
+        let param_values = self.determine_handler_params(params_asts, for_loop_contexts, event_type, mouse_event);
 
         // Identify the function name (to be refactored later)
         let regex = Regex::new(r"^(.+?)_id_\d+$").unwrap();
@@ -172,11 +299,18 @@ impl<State: ReactiveState> Component<State> {
         }
     }
 
-    fn determine_handler_params(&mut self, params_asts: &Vec<ASTNode>, for_loop_contexts: Vec<ForLoopContext>) -> Vec<Box<dyn Any>> {
+    fn determine_handler_params(
+        &mut self, 
+        params_asts: &Vec<ASTNode>, 
+        for_loop_contexts: Vec<ForLoopContext>, 
+        event_type: &EventType, 
+        mouse_event: Option<&MouseEvent>,
+    ) -> Vec<Box<dyn Any>> {
         let mut param_values: Vec<Box<dyn Any>> = vec![];
+        let event_payload = EventPayload::from_event(event_type, mouse_event);
 
         for params_ast in params_asts {
-            let param_value = match ast_evaluator::evaluate_ast(&params_ast, &self.state, &self.component_functions, &for_loop_contexts) {
+            let param_value = match ast_evaluator::evaluate_handler_param(&params_ast, &self.state, &self.component_functions, &for_loop_contexts, event_payload.as_ref()) {
                 Ok(value) => value,
                 Err(e) => {
                     println!("Error evaluating dynamic params: {}", e);
@@ -235,10 +369,22 @@ impl<State: ReactiveState> Element for Component<State> {
     
     fn render(&self, canvas: &skia_safe::Canvas) {
         self.content.render(canvas);
+        self.render_cache.mark_painted(self.content.get_visual_bounds());
     }
 
-    fn update(&mut self) {
-        self.content.update();
+    /*
+     * Runs the component's active frame callbacks, which may mutate the state,
+     * then reloads the template to show their changes.
+     */
+    fn update(&mut self, dt: f32) {
+        if self.component_functions.run_frame_callbacks(&mut self.state, dt) {
+            self.reload_template();
+        }
+
+        self.content.update(dt);
+        if self.content.needs_layout() {
+            self.layout_cache.mark_descendants_dirty();
+        }
     }
     
     fn handle_event(&mut self, cursor_position: skia_safe::Point, event_type: &EventType) {
@@ -247,25 +393,77 @@ impl<State: ReactiveState> Element for Component<State> {
     
     fn propagate_event(&mut self, cursor_position: skia_safe::Point, event_type: &EventType) -> Vec<EventPropagationData> {
         let event_propagation_datas = event_propagator::propagate_event(self, cursor_position, event_type);
-        
-        for data in event_propagation_datas.iter() {
-            let handler_name = &data.handler_name;
-            let for_loop_contexts = &data.for_loop_contexts;
+        self.trigger_event_handlers(event_propagation_datas, event_type);
 
-            if let Some(handler) = self.component_functions.event_handlers.get_mut(handler_name) {
-                handler(&mut self.state);
-            }
-            
-            if let Some(event_ast) = self.template_event_handler_asts.get(handler_name) {
-                self.trigger_dynamic_params_event_handler(&handler_name.clone(), &event_ast.clone(), for_loop_contexts.clone()); // TODO: Fix this clone
-            }
+        vec![]
+    }
+
+    fn propagate_targeted_event(&mut self, target_id: Option<&String>, event_type: &EventType) -> Option<Vec<EventPropagationData>> {
+        let event_propagation_datas = event_propagator::propagate_targeted_event(self, target_id, event_type)?;
+        self.trigger_event_handlers(event_propagation_datas, event_type);
+
+        Some(vec![])
+    }
+
+    /*
+     * The handlers of the host element belong to the parent component, to which they are returned.
+     * In the bubble phase, the event is first dispatched through the template, whose handlers run here.
+     */
+    fn dispatch_mouse_event(&mut self, path: &[String], mouse_event: &MouseEvent, phase: EventPhase) -> Vec<EventPropagationData> {
+        if phase == EventPhase::Capture {
+            return self.host_event_handlers.get_dispatch_data(&self._id, path, mouse_event, phase);
         }
 
-        vec![]
+        let capture_event_targets = self.content.dispatch_mouse_event(path, mouse_event, EventPhase::Capture);
+        self.trigger_event_handlers(capture_event_targets, &mouse_event.event_type);
+        if !mouse_event.is_propagation_stopped() {
+            let bubble_event_targets = self.content.dispatch_mouse_event(path, mouse_event, EventPhase::Bubble);
+            self.trigger_event_handlers(bubble_event_targets, &mouse_event.event_type);
+        }
+
+        self.host_event_handlers.get_dispatch_data(&self._id, path, mouse_event, phase)
+    }
+
+    // Overlays
+    fn render_overlay(&self, canvas: &skia_safe::Canvas) {
+        self.content.render_overlay(canvas);
+    }
+
+    fn propagate_overlay_event(&mut self, cursor_position: skia_safe::Point, event_type: &EventType) -> Option<Vec<EventPropagationData>> {
+        let event_propagation_datas = self.content.propagate_overlay_event(cursor_position, event_type)?;
+        self.trigger_event_handlers(event_propagation_datas, event_type);
+
+        Some(vec![])
+    }
+
+    // Animation
+    fn needs_animation_frame(&self) -> bool {
+        self.component_functions.frame_callbacks.iter().any(|frame_callback| frame_callback.is_active())
+            || self.content.needs_animation_frame()
+    }
+
+    // Rendering cache
+    fn needs_repaint(&self) -> bool {
+        self.render_cache.is_stale() || self.content.needs_repaint()
+    }
+
+    fn get_visual_bounds(&self) -> Rect {
+        self.content.get_visual_bounds()
+    }
+
+    // Replaced content (template reload, route change) damages both the old and the new bounds
+    fn collect_damage(&self, damaged_rects: &mut Vec<Rect>) {
+        if self.render_cache.is_stale() {
+            self.render_cache.collect_own_damage(self.get_visual_bounds(), damaged_rects);
+            return;
+        }
+
+        self.content.collect_damage(damaged_rects);
     }
 
     fn add_child(&mut self, child: Box<dyn Element>) {
         self.content.add_child(child);
+        self.layout_cache.mark_dirty();
     }
 
     fn set_id(&mut self, id: String) {
@@ -291,6 +489,7 @@ impl<State: ReactiveState> Element for Component<State> {
     fn set_styles(&mut self, styles: Styles) {
         self.styles = styles;
         self.content.set_styles(styles);
+        self.layout_cache.mark_dirty();
     }
 
     fn is_text_wrapper(&self) -> bool {
@@ -348,22 +547,51 @@ impl<State: ReactiveState> Element for Component<State> {
     
     fn handle_route_change(&mut self, route: &String, component_name: &String) {
         self.content.handle_route_change(route, component_name);
+        if self.content.needs_layout() {
+            self.layout_cache.mark_descendants_dirty();
+        }
     }
 
     // Layout system
     fn estimate_sizes(&mut self) {
+        if !self.layout_cache.needs_estimation() {
+            if let Some((natural_size, requested_size)) = self.layout_cache.get_estimated_sizes() {
+                self.natural_size = natural_size;
+                self.requested_size = requested_size;
+            }
+            return;
+        }
+
         self.content.estimate_sizes();
+        if self.content.has_estimate_changed() {
+            self.layout_cache.mark_allocation_needed();
+        }
         self.set_natural_size(self.content.get_natural_size());
 
         let sizing_policy = self.get_styles().sizing_policy.unwrap_or_default();
         self.set_requested_size(OptionalSize { width: sizing_policy.width, height: sizing_policy.height }); 
+
+        self.layout_cache.store_estimated_sizes(self.natural_size, self.requested_size);
     }
 
     fn allocate_space(&mut self, allocated_position: Position, allocated_size: Size) {
-        self.content.set_position(allocated_position);
-        self.content.set_size(allocated_size);
+        match self.layout_cache.get_allocation_action(allocated_position, allocated_size) {
+            AllocationAction::Skip => return,
+            AllocationAction::DescendantsOnly => {
+                if self.content.needs_layout() {
+                    self.content.allocate_pending_space();
+                }
+            },
+            AllocationAction::Full => {
+                self.content.estimate_sizes();
+                self.content.set_position(allocated_position);
+                self.content.set_size(allocated_size);
+
+                self.content.allocate_space(allocated_position, allocated_size);
+            }
+        }
 
-        self.content.allocate_space(allocated_position, allocated_size);
+        self.layout_cache.store_allocation(allocated_position, allocated_size);
     }
 
     fn layout(&mut self, allocated_position: Position, allocated_size: Size) {
@@ -371,18 +599,65 @@ impl<State: ReactiveState> Element for Component<State> {
         self.allocate_space(allocated_position, allocated_size);
     }
 
+    // Incremental layout
+    fn needs_layout(&self) -> bool {
+        self.layout_cache.needs_layout()
+    }
+
+    fn mark_layout_dirty(&mut self) {
+        self.layout_cache.mark_dirty();
+    }
+
+    fn has_estimate_changed(&self) -> bool {
+        self.layout_cache.has_estimate_changed()
+    }
+
+    fn allocate_pending_space(&mut self) {
+        if let Some((allocated_position, allocated_size)) = self.layout_cache.get_allocation() {
+            self.allocate_space(allocated_position, allocated_size);
+        }
+    }
+
     // Reactivity
     fn react_to_state_change(&mut self, component_id: String) {
         if component_id == self.get_id() {
-            self.load_component_template(HashMap::new()); // Naive approach; to be replaced later
-            self.update_children_inputs();
+            self.reload_template();
+            self.component_functions.request_animation_frame(); // The changes of frame callbacks don't come through here
+            return;
+        }
+
+        // The changed component may be nested inside this one's template
+        self.content.react_to_state_change(component_id.clone());
+        if self.content.needs_layout() {
+            self.layout_cache.mark_descendants_dirty();
+        }
+        self.write_back_component_models(&component_id);
+    }
+}
+
+// Finds a component of this component's template, without descending into the templates of nested components
+fn find_template_component<'a>(element: &'a mut dyn Element, component_id: &String) -> Option<&'a mut dyn Element> {
+    if element.get_id() == *component_id {
+        return Some(element);
+    }
+    if element.get_element_type() == ElementType::CustomComponent {
+        return None;
+    }
+
+    for child in element.get_children_mut()?.iter_mut() {
+        if let Some(component) = find_template_component(child.as_mut(), component_id) {
+            return Some(component);
         }
     }
+    None
 }
 
 pub trait ComponentInterface {
     fn update_input(&mut self, input_name: &str, value: Vec<Box<dyn Any>>);
     fn get_input_asts(&self) -> HashMap<String, ASTNode>;
+    fn set_model_bindings(&mut self, model_bindings: HashMap<String, String>);
+    fn get_model_values(&self) -> Vec<(String, Box<dyn Any>)>; // (Field path in the parent's state, property value)
+    fn set_host_event_handlers(&mut self, event_handlers: EventHandlers);
 }
 
 impl<State: ReactiveState> ComponentInterface for Component<State> {
@@ -396,4 +671,20 @@ impl<State: ReactiveState> ComponentInterface for Component<State> {
     fn get_input_asts(&self) -> HashMap<String, ASTNode> {
         self.input_expressions_asts.clone()
     }
+
+    fn set_model_bindings(&mut self, model_bindings: HashMap<String, String>) {
+        self.model_bindings = model_bindings;
+    }
+
+    fn get_model_values(&self) -> Vec<(String, Box<dyn Any>)> {
+        self.model_bindings.iter()
+            .filter_map(|(property_name, field_path)| {
+                self.state.get_field(property_name).map(|value| (field_path.clone(), value.as_any()))
+            })
+            .collect()
+    }
+
+    fn set_host_event_handlers(&mut self, event_handlers: EventHandlers) {
+        self.host_event_handlers = event_handlers;
+    }
 }
\ No newline at end of file
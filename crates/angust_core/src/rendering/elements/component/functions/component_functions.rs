@@ -1,4 +1,4 @@
-use std::{any::Any, collections::HashMap};
+use std::{any::Any, cell::Cell, collections::HashMap, rc::Rc};
 
 pub struct ComponentFunctions<State> {
     pub event_handlers: HashMap<String, Box<dyn FnMut(&mut State)>>,
@@ -8,6 +8,8 @@ pub struct ComponentFunctions<State> {
     pub dynamic_params_event_handlers: HashMap<String, Box<dyn Fn(&mut State, Vec<Box<dyn Any>>)>>,
     pub input_setters: HashMap<String, Box<dyn Fn(&mut State, Vec<Box<dyn Any>>)>>,
     pub initialization_function: Option<Box<dyn Fn(&mut State) -> ()>>,
+    pub frame_callbacks: Vec<FrameCallback<State>>,
+    are_frame_callbacks_running: Rc<Cell<bool>>,
 }
 
 impl<State> ComponentFunctions<State> {
@@ -45,6 +47,43 @@ impl<State> ComponentFunctions<State> {
 
         functions
     }
+
+    // Animation
+    pub fn add_frame_callback<F>(&mut self, callback: F)
+    where
+        F: 'static + FnMut(&mut State, f32) -> bool,
+    {
+        self.frame_callbacks.push(FrameCallback::new(Box::new(callback)));
+    }
+
+    pub fn request_animation_frame(&mut self) {
+        for frame_callback in self.frame_callbacks.iter_mut() {
+            frame_callback.activate();
+        }
+    }
+
+    /*
+     * Runs the active frame callbacks. Returns whether any ran, in which case the caller reloads the template:
+     * the state changes they make don't go through the reactivity path, which would re-arm them.
+     */
+    pub fn run_frame_callbacks(&mut self, state: &mut State, dt: f32) -> bool {
+        if !self.frame_callbacks.iter().any(|frame_callback| frame_callback.is_active()) {
+            return false;
+        }
+
+        self.are_frame_callbacks_running.set(true);
+        for frame_callback in self.frame_callbacks.iter_mut() {
+            frame_callback.run(state, dt);
+        }
+        self.are_frame_callbacks_running.set(false);
+
+        true
+    }
+
+    // Shared with the state listeners, which ignore the changes made while the frame callbacks run
+    pub fn get_frame_callbacks_running_flag(&self) -> Rc<Cell<bool>> {
+        self.are_frame_callbacks_running.clone()
+    }
 }
 
 impl<State> Default for ComponentFunctions<State> {
@@ -57,6 +96,78 @@ impl<State> Default for ComponentFunctions<State> {
             dynamic_params_event_handlers: HashMap::new(),
             input_setters: HashMap::new(),
             initialization_function: None,
+            frame_callbacks: Vec::new(),
+            are_frame_callbacks_running: Rc::new(Cell::new(false)),
+        }
+    }
+}
+
+/*
+ * Per-frame callback of a component, receiving the elapsed time in seconds.
+ * It stays active (requesting animation frames) until it returns false.
+ */
+pub struct FrameCallback<State> {
+    callback: Box<dyn FnMut(&mut State, f32) -> bool>,
+    is_active: bool,
+}
+
+impl<State> FrameCallback<State> {
+    pub fn new(callback: Box<dyn FnMut(&mut State, f32) -> bool>) -> Self {
+        Self { callback, is_active: true }
+    }
+
+    pub fn run(&mut self, state: &mut State, dt: f32) {
+        if !self.is_active {
+            return;
         }
+
+        self.is_active = (self.callback)(state, dt);
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    pub fn activate(&mut self) {
+        self.is_active = true;
+    }
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct ProgressState {
+        progress: f32,
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_run_frame_callbacks_flags_the_run_and_stops_finished_callbacks() {
+        // Arrange
+        let mut functions = ComponentFunctions::<ProgressState>::default();
+        let are_frame_callbacks_running = functions.get_frame_callbacks_running_flag();
+        let was_flag_set_during_run = Rc::new(Cell::new(true));
+        let was_flag_set_during_run_clone = was_flag_set_during_run.clone();
+        functions.add_frame_callback(move |state: &mut ProgressState, dt: f32| {
+            was_flag_set_during_run_clone.set(was_flag_set_during_run_clone.get() && are_frame_callbacks_running.get());
+            state.progress = (state.progress + dt).min(1.0);
+            state.progress < 1.0
+        });
+        let mut state = ProgressState { progress: 0.0 };
+
+        // Act
+        let first_run = functions.run_frame_callbacks(&mut state, 0.6);
+        let second_run = functions.run_frame_callbacks(&mut state, 0.6);
+        let third_run = functions.run_frame_callbacks(&mut state, 0.6);
+
+        // Assert
+        assert!(first_run && second_run);
+        assert!(!third_run); // The callback finished on the second run
+        assert_eq!(state.progress, 1.0);
+        assert!(was_flag_set_during_run.get());
+        assert!(!functions.get_frame_callbacks_running_flag().get());
+        assert!(!functions.frame_callbacks[0].is_active());
+    }
+}
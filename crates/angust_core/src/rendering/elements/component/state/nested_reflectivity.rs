@@ -1,3 +1,5 @@
+use std::any::Any;
+
 use crate::parsing::{
     directive::for_parser::{access_loop_field, ForLoopContext}, 
     html::error::ParsingError
@@ -42,3 +44,100 @@ pub fn get_nested_field(
     
     Some(current)
 }
+
+/*
+ * Writes a value to a (nested) field, eg. from a [(model)] binding. Nested fields are written on a copy of their parent,
+ * which is then written back. Text is converted to the type of numeric and boolean fields, as set_field expects the exact type.
+ * Returns false, leaving the field unchanged, for text that doesn't convert yet (eg. "-" being typed in a number field).
+ */
+pub fn set_nested_field(
+    obj: &mut dyn ReflectiveState,
+    path: &[&str],
+    value: Box<dyn Any>,
+) -> Result<bool, ParsingError> {
+    let field = match path.first() {
+        Some(field) => *field,
+        None => return Err(ParsingError::FieldAccessError("No property found".to_string())),
+    };
+    let mut current_value = obj.get_field(field).ok_or_else(|| {
+        ParsingError::FieldAccessError(format!("Property not found for '{}'", field))
+    })?;
+
+    if path.len() == 1 {
+        let type_mismatch_error = ParsingError::FieldAccessError(format!("Type mismatch for '{}'", field));
+        let converted_value = match convert_to_field_type(current_value.as_ref(), value).ok_or(type_mismatch_error)? {
+            Some(converted_value) => converted_value,
+            None => return Ok(false),
+        };
+        obj.set_field(field, converted_value);
+        return Ok(true);
+    }
+
+    if !set_nested_field(current_value.as_mut(), &path[1..], value)? {
+        return Ok(false);
+    }
+    obj.set_field(field, current_value.as_any());
+    Ok(true)
+}
+
+// None for a type mismatch, Some(None) for text that doesn't parse as the field's type
+fn convert_to_field_type(current_value: &dyn ReflectiveState, value: Box<dyn Any>) -> Option<Option<Box<dyn Any>>> {
+    let current_value = current_value.as_any();
+    if (*current_value).type_id() == (*value).type_id() {
+        return Some(Some(value));
+    }
+
+    let text = value.downcast::<String>().ok()?;
+    let text = text.trim();
+    if current_value.is::<f64>() {
+        Some(text.parse::<f64>().ok().map(|val| Box::new(val) as Box<dyn Any>))
+    } else if current_value.is::<u32>() {
+        Some(text.parse::<u32>().ok().map(|val| Box::new(val) as Box<dyn Any>))
+    } else if current_value.is::<usize>() {
+        Some(text.parse::<usize>().ok().map(|val| Box::new(val) as Box<dyn Any>))
+    } else if current_value.is::<bool>() {
+        Some(text.parse::<bool>().ok().map(|val| Box::new(val) as Box<dyn Any>))
+    } else {
+        None
+    }
+}
+
+// Compares values of the primitive field types; others are considered different
+pub fn is_same_value(first: &dyn Any, second: &dyn Any) -> bool {
+    if let (Some(first), Some(second)) = (first.downcast_ref::<String>(), second.downcast_ref::<String>()) {
+        first == second
+    } else if let (Some(first), Some(second)) = (first.downcast_ref::<f64>(), second.downcast_ref::<f64>()) {
+        first == second
+    } else if let (Some(first), Some(second)) = (first.downcast_ref::<u32>(), second.downcast_ref::<u32>()) {
+        first == second
+    } else if let (Some(first), Some(second)) = (first.downcast_ref::<usize>(), second.downcast_ref::<usize>()) {
+        first == second
+    } else if let (Some(first), Some(second)) = (first.downcast_ref::<bool>(), second.downcast_ref::<bool>()) {
+        first == second
+    } else {
+        false
+    }
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_partially_typed_number() {
+        // Arrange
+        let current_value = 1.0_f64;
+
+        // Act
+        let typed_value = convert_to_field_type(&current_value, Box::new(String::from(" 2.5 ")));
+        let partial_value = convert_to_field_type(&current_value, Box::new(String::from("-")));
+        let mismatched_value = convert_to_field_type(&current_value, Box::new(1_u32));
+
+        // Assert
+        assert_eq!(typed_value.unwrap().unwrap().downcast_ref::<f64>(), Some(&2.5));
+        assert!(partial_value.unwrap().is_none());
+        assert!(mismatched_value.is_none());
+    }
+}
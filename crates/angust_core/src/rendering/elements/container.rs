@@ -1,24 +1,27 @@
 use std::{any::Any, collections::HashMap};
 
-use skia_safe::{Canvas, Color, Point};
+use skia_safe::{Canvas, Color, Point, Rect};
 
 use crate::{
-    application::event_handling::scrollbar_movement_handler::handle_scrollbar_movement, 
+    application::event_handling::scrollbar_movement_handler::{get_scrollbar_rects, handle_scrollbar_movement, is_scrollbar_visible}, 
     rendering::{
+        animation::{interpolation, scroll_animator::ScrollAnimator, style_animator::StyleAnimator},
         layout::{
+            layout_cache::{AllocationAction, LayoutCache}, 
             size_estimation_system::{effective_size_estimator, size_estimator}, 
             space_allocation_system::container::container_space_allocator
         },
-        rendering_interface::element_renderer::ElementRenderer,
+        rendering_interface::{element_renderer::ElementRenderer, render_cache::RenderCache},
     }
 };
 
 use super::{
-    button::EventPropagationData, common_types::{OptionalSize, Position, ScrollbarState, Size}, component::component::ComponentInterface, element::{Element, ElementType, EventType}, element_id_generator::ElementIDGenerator, event_propagator, styles::{Directions, Styles}
+    button::EventPropagationData, common_types::{OptionalSize, Position, ScrollBehavior, ScrollbarAxis, ScrollbarState, Size}, component::component::ComponentInterface, element::{Element, ElementType, EventType, ScrollEvent}, element_id_generator::ElementIDGenerator, event_handlers::EventHandlers, event_propagator, mouse_event::{EventPhase, MouseEvent}, pseudo_class_styles::{InteractionState, PseudoClassStyles}, styles::{Directions, Styles}
 };
 
 pub struct Container {
     _id: String,
+    pub template_id: Option<String>, // The id attribute, not necessarily unique
     position: Position,
     size: Size,
     natural_size: Size,
@@ -26,6 +29,16 @@ pub struct Container {
     styles: Styles,
     pub children: Vec<Box<dyn Element>>,
     pub scrollbar_state: ScrollbarState,
+    pub event_handlers: EventHandlers,
+    pub tab_index: Option<i32>, // Not focusable by default
+    pub pseudo_class_styles: PseudoClassStyles,
+    base_styles: Styles, // Before pseudo-class rules
+    interaction_state: InteractionState,
+    layout_cache: LayoutCache,
+    render_cache: RenderCache,
+    style_animator: StyleAnimator,
+    scroll_animator: Option<ScrollAnimator>,
+    has_scrolled: bool, // Since the last scroll event
 }
 
 impl Container {
@@ -33,6 +46,7 @@ impl Container {
         let id = ElementIDGenerator::get();
         Self {
             _id: id,
+            template_id: None,
             position: Position::default(),
             size: Size::default(),
             natural_size: Size::default(),
@@ -40,54 +54,143 @@ impl Container {
             styles: Styles::default(),
             children: Vec::new(),
             scrollbar_state: ScrollbarState::default(),
+            event_handlers: EventHandlers::default(),
+            tab_index: None,
+            pseudo_class_styles: PseudoClassStyles::default(),
+            base_styles: Styles::default(),
+            interaction_state: InteractionState::default(),
+            layout_cache: LayoutCache::default(),
+            render_cache: RenderCache::default(),
+            style_animator: StyleAnimator::default(),
+            scroll_animator: None,
+            has_scrolled: false,
         }
     }
 
     pub fn add_children(&mut self, children: Vec<Box<dyn Element>>) -> &mut Self {
         self.children.extend(children);
+        self.layout_cache.mark_dirty();
+        self.render_cache.invalidate();
         self
     }
-}
 
-impl Element for Container {
-    fn render(&self, canvas: &Canvas) {
+    // Scrolls to a relative position (between 0.0 and 1.0 on each axis), laying out the children again
+    pub fn set_scroll_position(&mut self, scroll_position: Position) {
+        let scroll_position = Position {
+            x: scroll_position.x.clamp(0.0, 1.0),
+            y: scroll_position.y.clamp(0.0, 1.0),
+        };
+        if scroll_position == self.scrollbar_state.current_scroll_position {
+            return;
+        }
+
+        self.scrollbar_state.current_scroll_position = scroll_position;
+        self.has_scrolled = true;
+        self.render_cache.invalidate();
+        self.invalidate_allocation();
+        self.allocate_space(self.position, self.size);
+    }
+
+    // Forces the next allocation to run even if the allocated space is unchanged (e.g. after scrolling)
+    pub fn invalidate_allocation(&mut self) {
+        self.layout_cache.mark_allocation_needed();
+    }
+
+    fn render_content(&self, canvas: &Canvas) {
+        let save_count = ElementRenderer::begin_effects(canvas, self.position, self.size, &self.styles);
+
         ElementRenderer::render_element(
             canvas,
             self.position,
             self.size,
             self.styles.background_color.unwrap_or(Color::TRANSPARENT),
-            self.styles.border.unwrap_or_default().width,
-            self.styles.border.unwrap_or_default().color,
+            &self.styles.border.unwrap_or_default(),
+            self.styles.text_color.unwrap_or(Color::BLACK),
         );
+        ElementRenderer::render_inset_shadows(canvas, self.position, self.size, &self.styles);
 
+        // Children are clipped to the padding box unless the overflow is visible
+        let clip_save_count = canvas.save();
+        if let Some(clip) = ElementRenderer::get_overflow_clip(self.position, self.size, &self.styles) {
+            ElementRenderer::clip_overflow(canvas, &clip);
+        }
         for child in &self.children {
             child.render(canvas);
         }
+        canvas.restore_to_count(clip_save_count);
 
-        if self.scrollbar_state.is_overflowing.horizontal && self.scrollbar_state.thumb_scrollbar_width_ratio < 1.0 {
+        for axis in [ScrollbarAxis::Horizontal, ScrollbarAxis::Vertical] {
+            if !is_scrollbar_visible(&self.scrollbar_state, axis) {
+                continue;
+            }
+
+            let (track_rect, thumb_rect) = get_scrollbar_rects(self.position, self.size, &self.scrollbar_state, axis);
             ElementRenderer::render_scrollbar(
                 canvas,
-                Position {
-                    x: self.position.x,
-                    y: self.position.y + self.size.height - 10.0,
-                },
-                Size {
-                    width: self.size.width,
-                    height: 10.0,
-                },
+                track_rect,
+                thumb_rect,
                 Directions {
-                    horizontal: true,
-                    vertical: false,
+                    horizontal: axis == ScrollbarAxis::Horizontal,
+                    vertical: axis == ScrollbarAxis::Vertical,
                 },
-                self.scrollbar_state.current_scroll_position.x,
-                self.scrollbar_state.thumb_scrollbar_width_ratio
             );
         }
+
+        ElementRenderer::end_effects(canvas, save_count);
+    }
+
+    // Displays the styles matching the current interaction state (eg. :focus rules)
+    fn apply_interaction_state(&mut self) {
+        if self.pseudo_class_styles.is_empty() {
+            return;
+        }
+        let styles = self.pseudo_class_styles.resolve(self.base_styles, &self.interaction_state);
+        if self.style_animator.is_target(&styles) {
+            return; // The matching rules leave the styles unchanged
+        }
+        let displayed_styles = self.style_animator.set_styles(&self.styles, styles);
+        self.apply_animated_styles(displayed_styles);
+    }
+
+    // Displays the styles computed by the style animator on this frame
+    fn apply_animated_styles(&mut self, styles: Styles) {
+        if interpolation::affects_layout(&self.styles, &styles) {
+            self.layout_cache.mark_dirty();
+        }
+        self.styles = styles;
+        self.render_cache.invalidate();
+    }
+}
+
+impl Element for Container {
+    fn render(&self, canvas: &Canvas) {
+        if self.children.iter().any(|child| child.needs_repaint()) {
+            self.render_cache.mark_descendants_stale();
+        }
+
+        self.render_cache.render(canvas, self.get_visual_bounds(), |canvas| self.render_content(canvas));
     }
 
-    fn update(&mut self) {
+    fn update(&mut self, dt: f32) {
+        if let Some(styles) = self.style_animator.update(dt) {
+            self.apply_animated_styles(styles);
+        }
+        if let Some(scroll_animator) = self.scroll_animator.as_mut() {
+            let offset = scroll_animator.update(dt);
+            if scroll_animator.is_finished() {
+                self.scroll_animator = None;
+            }
+            self.set_scroll_position(self.scrollbar_state.to_scroll_position(offset));
+        }
+
         for child in &mut self.children {
-            child.update();
+            child.update(dt);
+            if child.needs_layout() {
+                self.layout_cache.mark_descendants_dirty();
+            }
+            if child.needs_repaint() {
+                self.render_cache.mark_descendants_stale();
+            }
         }
     }
 
@@ -96,6 +199,9 @@ impl Element for Container {
 
         for child in &mut self.children {
             child.handle_event(cursor_position, event_type);
+            if child.needs_repaint() {
+                self.render_cache.mark_descendants_stale();
+            }
         }
     }
 
@@ -103,30 +209,185 @@ impl Element for Container {
         event_propagator::propagate_event(self, cursor_position, event_type)
     }
 
+    fn propagate_targeted_event(&mut self, target_id: Option<&String>, event_type: &EventType) -> Option<Vec<EventPropagationData>> {
+        let mut event_targets = event_propagator::propagate_targeted_event(self, target_id, event_type)?;
+        if event_propagator::should_handle_targeted_event(self, target_id, event_type) {
+            event_targets.extend(self.event_handlers.get_propagation_data(event_type));
+        }
+
+        Some(event_targets)
+    }
+
+    fn dispatch_mouse_event(&mut self, path: &[String], mouse_event: &MouseEvent, phase: EventPhase) -> Vec<EventPropagationData> {
+        let event_targets = self.event_handlers.get_dispatch_data(&self._id, path, mouse_event, phase);
+        event_propagator::dispatch_mouse_event(self, path, mouse_event, phase, event_targets)
+    }
+
+    // Overlays
+    fn render_overlay(&self, canvas: &Canvas) {
+        for child in &self.children {
+            child.render_overlay(canvas);
+        }
+    }
+
+    fn propagate_overlay_event(&mut self, cursor_position: Point, event_type: &EventType) -> Option<Vec<EventPropagationData>> {
+        event_propagator::propagate_overlay_event(self, cursor_position, event_type)
+    }
+
+    // Focus
+    fn get_tab_index(&self) -> Option<i32> {
+        self.tab_index
+    }
+
+    fn set_focused(&mut self, is_focused: bool) {
+        if self.interaction_state.is_focused == is_focused {
+            return;
+        }
+        self.interaction_state.is_focused = is_focused;
+        self.apply_interaction_state();
+    }
+
+    // Pointer interaction
+    fn set_hovered(&mut self, is_hovered: bool) {
+        if self.interaction_state.is_hovered == is_hovered {
+            return;
+        }
+        self.interaction_state.is_hovered = is_hovered;
+        self.apply_interaction_state();
+    }
+
+    fn set_active(&mut self, is_active: bool) {
+        if self.interaction_state.is_active == is_active {
+            return;
+        }
+        self.interaction_state.is_active = is_active;
+        self.apply_interaction_state();
+    }
+
+    // Scrolling
+    fn get_scrollbar_state(&self) -> Option<&ScrollbarState> {
+        Some(&self.scrollbar_state)
+    }
+
+    fn inherit_scrollbar_state(&mut self, previous_state: &ScrollbarState) {
+        self.scrollbar_state.current_scroll_position = previous_state.current_scroll_position;
+        self.scrollbar_state.max_scroll_offset = previous_state.max_scroll_offset;
+    }
+
+    fn scroll_to(&mut self, offset: Position, behavior: ScrollBehavior) {
+        match behavior {
+            ScrollBehavior::Instant => {
+                self.scroll_animator = None;
+                self.set_scroll_position(self.scrollbar_state.to_scroll_position(offset));
+            },
+            ScrollBehavior::Smooth => {
+                self.scroll_animator = Some(ScrollAnimator::new(self.scrollbar_state.get_scroll_offset(), offset));
+            },
+        }
+    }
+
+    fn take_scroll_event(&mut self) -> Option<ScrollEvent> {
+        if !std::mem::take(&mut self.has_scrolled) {
+            return None;
+        }
+
+        Some(ScrollEvent {
+            offset: self.scrollbar_state.get_scroll_offset(),
+            max_offset: self.scrollbar_state.max_scroll_offset,
+        })
+    }
+
+    // Animation
+    fn needs_animation_frame(&self) -> bool {
+        self.style_animator.is_animating() || self.scroll_animator.is_some() || self.children.iter().any(|child| child.needs_animation_frame())
+    }
+
+    fn get_style_animator(&self) -> Option<&StyleAnimator> {
+        Some(&self.style_animator)
+    }
+
+    fn inherit_style_animator(&mut self, previous_animator: &StyleAnimator, previous_styles: &Styles) {
+        if let Some(styles) = self.style_animator.inherit(previous_animator, previous_styles) {
+            self.apply_animated_styles(styles);
+        }
+    }
+
+    // Rendering cache
+    // Also picks up descendants changed outside of the tree traversals (eg. by the focus manager or radio groups)
+    fn needs_repaint(&self) -> bool {
+        self.render_cache.needs_repaint() || self.children.iter().any(|child| child.needs_repaint())
+    }
+
+    fn get_visual_bounds(&self) -> Rect {
+        if !self.needs_repaint() {
+            if let Some(painted_bounds) = self.render_cache.get_painted_bounds() {
+                return painted_bounds;
+            }
+        }
+
+        let mut visual_bounds = Rect::from_xywh(self.position.x, self.position.y, self.size.width, self.size.height);
+        if ElementRenderer::get_overflow_clip(self.position, self.size, &self.styles).is_none() {
+            for child in &self.children {
+                visual_bounds.join(child.get_visual_bounds());
+            }
+        }
+
+        ElementRenderer::transform_bounds(visual_bounds, self.position, self.size, &self.styles)
+    }
+
+    fn collect_damage(&self, damaged_rects: &mut Vec<Rect>) {
+        if self.render_cache.is_stale() {
+            self.render_cache.collect_own_damage(self.get_visual_bounds(), damaged_rects);
+            return;
+        }
+
+        for child in &self.children {
+            if child.needs_repaint() {
+                child.collect_damage(damaged_rects);
+            }
+        }
+    }
+
     fn set_id(&mut self, id: String) {
         self._id = id;
     }
 
     fn set_position(&mut self, position: Position) {
+        if self.position != position {
+            self.render_cache.invalidate();
+        }
         self.position = position;
     }
 
     fn set_size(&mut self, size: Size) {
+        if self.size != size {
+            self.render_cache.invalidate();
+        }
         self.size = size;
     }
 
     fn set_styles(&mut self, styles: Styles) {
-        self.styles = styles;
+        self.base_styles = styles;
+        let styles = self.pseudo_class_styles.resolve(styles, &self.interaction_state);
+        self.styles = self.style_animator.set_styles(&self.styles, styles);
+        self.layout_cache.mark_dirty();
+        self.render_cache.invalidate();
     }
 
     fn add_child(&mut self, child: Box<dyn Element>) {
         self.children.push(child);
+        self.layout_cache.mark_dirty();
+        self.render_cache.invalidate();
     }
 
     fn get_id(&self) -> String {
         self._id.clone()
     }
 
+    fn get_template_id(&self) -> Option<&String> {
+        self.template_id.as_ref()
+    }
+
     fn get_element_type(&self) -> ElementType {
         ElementType::Container
     }
@@ -163,6 +424,12 @@ impl Element for Container {
     fn handle_route_change(&mut self, route: &String, component_name: &String) {
         for child in &mut self.children {
             child.handle_route_change(route, component_name);
+            if child.needs_layout() {
+                self.layout_cache.mark_descendants_dirty();
+            }
+            if child.needs_repaint() {
+                self.render_cache.mark_descendants_stale();
+            }
         }
     }
 
@@ -193,29 +460,61 @@ impl Element for Container {
 
     // First pass: Traverse the DOM from leaves to root and estimate the size of each container.
     fn estimate_sizes(&mut self) {
+        if !self.layout_cache.needs_estimation() {
+            if let Some((natural_size, requested_size)) = self.layout_cache.get_estimated_sizes() {
+                self.natural_size = natural_size;
+                self.requested_size = requested_size;
+            }
+            return;
+        }
 
         if !self.children.is_empty() {
             for child in &mut self.children {
                 child.estimate_sizes();
+                if child.has_estimate_changed() {
+                    self.layout_cache.mark_allocation_needed();
+                }
             }
 
             size_estimator::estimate_parent_container_sizes(self);
         } else {
             size_estimator::estimate_leaf_container_sizes(self);
         }
+
+        self.layout_cache.store_estimated_sizes(self.natural_size, self.requested_size);
     }
 
     // Second pass: Traverse the DOM from root to leaves and allocate space to each container.
     fn allocate_space(&mut self, allocated_position: Position, allocated_size: Size) {
-        self.position = allocated_position;
-        self.size = allocated_size;
-
-        if self.is_text_wrapper() {
-            self.children[0].allocate_space(allocated_position, allocated_size);
-            return;
+        match self.layout_cache.get_allocation_action(allocated_position, allocated_size) {
+            AllocationAction::Skip => return,
+            AllocationAction::DescendantsOnly => {
+                for child in &mut self.children {
+                    if child.needs_layout() {
+                        child.allocate_pending_space();
+                    }
+                }
+                self.render_cache.mark_descendants_stale();
+            },
+            AllocationAction::Full => {
+                self.position = allocated_position;
+                self.size = allocated_size;
+                self.render_cache.invalidate();
+
+                // Restore the children estimates, which previous allocations may have adjusted
+                for child in &mut self.children {
+                    child.estimate_sizes();
+                }
+
+                if self.is_text_wrapper() {
+                    self.children[0].allocate_space(allocated_position, allocated_size);
+                } else {
+                    container_space_allocator::allocate_space_to_children(self, allocated_position, allocated_size);
+                }
+            }
         }
 
-        container_space_allocator::allocate_space_to_children(self, allocated_position, allocated_size);
+        self.layout_cache.store_allocation(allocated_position, allocated_size);
     }
     
     fn layout(&mut self, allocated_position: Position, allocated_size: Size) {
@@ -223,10 +522,35 @@ impl Element for Container {
         self.allocate_space(allocated_position, allocated_size);
     }
 
+    // Incremental layout
+    fn needs_layout(&self) -> bool {
+        self.layout_cache.needs_layout()
+    }
+
+    fn mark_layout_dirty(&mut self) {
+        self.layout_cache.mark_dirty();
+    }
+
+    fn has_estimate_changed(&self) -> bool {
+        self.layout_cache.has_estimate_changed()
+    }
+
+    fn allocate_pending_space(&mut self) {
+        if let Some((allocated_position, allocated_size)) = self.layout_cache.get_allocation() {
+            self.allocate_space(allocated_position, allocated_size);
+        }
+    }
+
     // Reactivity
     fn react_to_state_change(&mut self, component_id: String) {
         for child in &mut self.children {
             child.react_to_state_change(component_id.clone());
+            if child.needs_layout() {
+                self.layout_cache.mark_descendants_dirty();
+            }
+            if child.needs_repaint() {
+                self.render_cache.mark_descendants_stale();
+            }
         }
     }
 }
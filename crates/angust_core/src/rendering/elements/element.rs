@@ -1,23 +1,81 @@
 use std::{any::Any, collections::HashMap};
 
-use skia_safe::{Canvas, Point};
+use skia_safe::{Canvas, Point, Rect};
+
+use crate::rendering::animation::style_animator::StyleAnimator;
 
 use super::{
     button::EventPropagationData, 
-    common_types::{OptionalSize, Position, Size}, 
-    component::component::ComponentInterface, styles::Styles
+    common_types::{OptionalSize, Position, ScrollBehavior, ScrollbarState, Size}, 
+    component::component::ComponentInterface, styles::Styles,
+    form_control::FormControlState,
+    mouse_event::{EventPhase, MouseEvent},
+    text_editing_model::TextEditingState
 };
 
 // This is the trait that all Angust elements must implement.
+// Optional capabilities (overlays, focus, pointer state, text editing, form controls, scrolling, style animations) have no-op defaults.
 pub trait Element {
     // Core
     fn render(&self, canvas: &Canvas);
-    fn update(&mut self);
+    fn update(&mut self, dt: f32); // dt: seconds elapsed since the previous animation frame
     fn handle_event(&mut self, cursor_position: Point, event_type: &EventType);
 
-    // Experimental: gather all names of event handlers that should be called by a parent Custom Component
+    // Default actions of mouse events (eg. toggling a checkbox), returning the handlers of the events they emit (eg. input and change),
+    // which are called by the parent Custom Component
     fn propagate_event(&mut self, cursor_position: skia_safe::Point, event_type: &EventType) -> Vec<EventPropagationData>;
 
+    // Mouse events go to the topmost element under the cursor, through the path of elements leading to it (see event_propagator::hit_test).
+    // In the capture phase, an element returns its capture handlers followed by those of the path below it; in the bubble phase, 
+    // those of the path below it followed by its own. Custom components run the handlers of their template
+    fn dispatch_mouse_event(&mut self, path: &[String], mouse_event: &MouseEvent, phase: EventPhase) -> Vec<EventPropagationData>;
+
+    // Keyboard and focus events go to a target (the focused element), keyboard events then bubble up to the root. 
+    // Returns None if the target is not within this element's subtree
+    fn propagate_targeted_event(&mut self, target_id: Option<&String>, event_type: &EventType) -> Option<Vec<EventPropagationData>>;
+
+    // Overlays (eg. the popup of a select), drawn above all other content and receiving the mouse events first.
+    // Returns None if no open overlay of this element's subtree took the event
+    fn render_overlay(&self, _canvas: &Canvas) {}
+    fn propagate_overlay_event(&mut self, _cursor_position: Point, _event_type: &EventType) -> Option<Vec<EventPropagationData>> { None }
+
+    // Focus
+    fn get_tab_index(&self) -> Option<i32> { None } // None if the element is not focusable
+    fn set_focused(&mut self, _is_focused: bool) {}
+
+    // Pointer interaction state, matched by :hover, :active and :disabled rules
+    fn set_hovered(&mut self, _is_hovered: bool) {}
+    fn set_active(&mut self, _is_active: bool) {}
+    fn is_disabled(&self) -> bool { false } // Disabled elements are not focusable and ignore mouse events
+
+    // Text editing (inputs), carried over on template reload
+    fn get_text_editing_state(&self) -> Option<&TextEditingState> { None }
+    fn inherit_text_editing_state(&mut self, _previous_state: &TextEditingState) {}
+
+    // Form controls (checkboxes, radio buttons, selects and ranges), carried over on template reload
+    fn get_form_control_state(&self) -> Option<&FormControlState> { None }
+    fn inherit_form_control_state(&mut self, _previous_state: &FormControlState) {}
+
+    // Radio buttons with the same name exclude each other (see radio_groups)
+    fn take_checked_radio_group(&mut self) -> Option<String> { None } // The group's name, if the user just checked this radio button
+    fn uncheck_radio(&mut self, _group_name: &String) {}
+
+    // Scrolling (overflowing containers), the scroll position being carried over on template reload
+    fn get_scrollbar_state(&self) -> Option<&ScrollbarState> { None } // None if the element doesn't scroll
+    fn inherit_scrollbar_state(&mut self, _previous_state: &ScrollbarState) {}
+    fn scroll_to(&mut self, _offset: Position, _behavior: ScrollBehavior) {} // Offset in logical pixels from the top-left of the content
+    fn take_scroll_event(&mut self) -> Option<ScrollEvent> { None } // The offset scrolled to since the previous call, if any
+
+    // Animation
+    fn needs_animation_frame(&self) -> bool;
+    fn get_style_animator(&self) -> Option<&StyleAnimator> { None }
+    fn inherit_style_animator(&mut self, _previous_animator: &StyleAnimator, _previous_styles: &Styles) {}
+
+    // Rendering cache
+    fn needs_repaint(&self) -> bool;
+    fn get_visual_bounds(&self) -> Rect;
+    fn collect_damage(&self, damaged_rects: &mut Vec<Rect>);
+
     // Getters and setters
     fn set_id(&mut self, id: String);
     fn set_position(&mut self, position: Position);
@@ -26,6 +84,7 @@ pub trait Element {
     fn add_child(&mut self, child: Box<dyn Element>);
 
     fn get_id(&self) -> String;
+    fn get_template_id(&self) -> Option<&String> { None } // The id attribute, by which components reference the element (see FocusProxy and ScrollProxy)
     fn get_element_type(&self) -> ElementType;
     fn get_name(&self) -> String;
     fn get_position(&self) -> Position;
@@ -50,6 +109,12 @@ pub trait Element {
     fn allocate_space(&mut self, allocated_position: Position, allocated_size: Size);
     fn layout(&mut self, allocated_position: Position, allocated_size: Size);
 
+    // Incremental layout
+    fn needs_layout(&self) -> bool;
+    fn mark_layout_dirty(&mut self);
+    fn has_estimate_changed(&self) -> bool;
+    fn allocate_pending_space(&mut self);
+
     // Reactivity
     fn react_to_state_change(&mut self, component_id: String);
 }
@@ -58,12 +123,148 @@ pub trait Element {
 #[derive(Clone, Debug, PartialEq)]
 pub enum EventType {
     MouseClick,
+    MouseDoubleClick,
+    MouseAuxClick, // Click of the right or middle button
+    ContextMenu, // Right click, eg. to open a custom context menu
     MouseMove,
     MouseDown,
     MouseDrag,
     MouseUp,
     MouseRoll(f32),
-    KeyPress(char),
+    MouseRollHorizontal(f32), // Eg. shift+wheel or a touchpad's sideways scroll
+    MouseEnter,
+    MouseLeave,
+    Scroll(ScrollEvent),
+    KeyDown(KeyboardEvent),
+    KeyUp(KeyboardEvent),
+    Focus,
+    Blur,
+    Input(String), // The value after an edit
+    Change(String), // The value once the edit is committed
+}
+
+impl EventType {
+    // Name of the event in templates, eg. "keydown" for @onkeydown
+    pub fn get_name(&self) -> Option<&'static str> {
+        match self {
+            EventType::MouseClick => Some("click"),
+            EventType::MouseDoubleClick => Some("dblclick"),
+            EventType::MouseAuxClick => Some("auxclick"),
+            EventType::ContextMenu => Some("contextmenu"),
+            EventType::MouseDown => Some("mousedown"),
+            EventType::MouseUp => Some("mouseup"),
+            EventType::MouseMove | EventType::MouseDrag => Some("mousemove"),
+            EventType::MouseRoll(_) | EventType::MouseRollHorizontal(_) => Some("wheel"),
+            EventType::MouseEnter => Some("mouseenter"),
+            EventType::MouseLeave => Some("mouseleave"),
+            EventType::Scroll(_) => Some("scroll"),
+            EventType::KeyDown(_) => Some("keydown"),
+            EventType::KeyUp(_) => Some("keyup"),
+            EventType::Focus => Some("focus"),
+            EventType::Blur => Some("blur"),
+            EventType::Input(_) => Some("input"),
+            EventType::Change(_) => Some("change"),
+            _ => None,
+        }
+    }
+
+    // As in the DOM, focus, blur, mouseenter, mouseleave and scroll only reach the target
+    pub fn bubbles(&self) -> bool {
+        !matches!(self, EventType::Focus | EventType::Blur | EventType::MouseEnter | EventType::MouseLeave | EventType::Scroll(_))
+    }
+}
+
+/*
+ * Payload of keyboard events, named after the DOM's KeyboardEvent:
+ * key is the logical key ("a", "A", "Enter", "ArrowLeft"), 
+ * code the physical key regardless of the layout ("KeyA", "Enter", "ArrowLeft"),
+ * and text the characters typed, if any.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyboardEvent {
+    pub key: String,
+    pub code: String,
+    pub text: Option<String>,
+    pub modifiers: KeyModifiers,
+    pub repeat: bool,
+}
+
+impl KeyboardEvent {
+    pub fn new(key: &str, code: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            code: code.to_string(),
+            text: None,
+            modifiers: KeyModifiers::default(),
+            repeat: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KeyModifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub meta: bool,
+}
+
+impl Default for KeyModifiers {
+    fn default() -> Self {
+        Self {
+            shift: false,
+            ctrl: false,
+            alt: false,
+            meta: false,
+        }
+    }
+}
+
+/*
+ * Payload of scroll events: offset is the scrolled distance from the top-left of the content, 
+ * and max_offset the largest one (the content's overflow), both in logical pixels.
+ * Eg. offset.y approaching max_offset.y means the end of a list is being reached.
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScrollEvent {
+    pub offset: Position,
+    pub max_offset: Position,
+}
+
+/*
+ * What $event resolves to in the handlers of an event: a MouseEvent for mouse events, a KeyboardEvent for keyboard events,
+ * the value (a String) for input and change events, and a ScrollEvent for scroll events
+ */
+#[derive(Clone, Debug)]
+pub enum EventPayload {
+    Mouse(MouseEvent),
+    Keyboard(KeyboardEvent),
+    Value(String),
+    Scroll(ScrollEvent),
+}
+
+impl EventPayload {
+    pub fn from_event(event_type: &EventType, mouse_event: Option<&MouseEvent>) -> Option<Self> {
+        if let Some(mouse_event) = mouse_event {
+            return Some(EventPayload::Mouse(mouse_event.clone()));
+        }
+
+        match event_type {
+            EventType::KeyDown(keyboard_event) | EventType::KeyUp(keyboard_event) => Some(EventPayload::Keyboard(keyboard_event.clone())),
+            EventType::Input(value) | EventType::Change(value) => Some(EventPayload::Value(value.clone())),
+            EventType::Scroll(scroll_event) => Some(EventPayload::Scroll(*scroll_event)),
+            _ => None,
+        }
+    }
+
+    pub fn to_any(&self) -> Box<dyn Any> {
+        match self {
+            EventPayload::Mouse(mouse_event) => Box::new(mouse_event.clone()),
+            EventPayload::Keyboard(keyboard_event) => Box::new(keyboard_event.clone()),
+            EventPayload::Value(value) => Box::new(value.clone()),
+            EventPayload::Scroll(scroll_event) => Box::new(*scroll_event),
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -72,5 +273,10 @@ pub enum ElementType {
     Button,
     Text,
     Image,
+    Input,
+    Textarea,
+    Checkbox,
+    Select,
+    Range,
     CustomComponent
 }
\ No newline at end of file
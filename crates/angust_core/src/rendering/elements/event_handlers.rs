@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use crate::parsing::directive::for_parser::ForLoopContext;
+
+use super::{button::EventPropagationData, element::EventType, mouse_event::{EventPhase, MouseEvent}};
+
+
+/*
+ * Template event handlers bound to an element through @on<event> attributes (and [(model)] bindings),
+ * stored by event name (eg. "keydown") along with the for loop contexts of the element.
+ * Mouse event handlers declared with @on<event>.capture run in the capture phase instead of the bubble phase.
+ */
+#[derive(Clone, Debug)]
+pub struct EventHandlers {
+    handler_names: HashMap<String, Vec<String>>,
+    capture_handler_names: HashMap<String, Vec<String>>,
+    for_loop_contexts: Vec<ForLoopContext>,
+}
+
+impl EventHandlers {
+    pub fn new(for_loop_contexts: Vec<ForLoopContext>) -> Self {
+        Self {
+            handler_names: HashMap::new(),
+            capture_handler_names: HashMap::new(),
+            for_loop_contexts,
+        }
+    }
+
+    pub fn add_handler(&mut self, event_name: String, handler_name: String) {
+        self.handler_names.entry(event_name).or_default().push(handler_name);
+    }
+
+    pub fn add_capture_handler(&mut self, event_name: String, handler_name: String) {
+        self.capture_handler_names.entry(event_name).or_default().push(handler_name);
+    }
+
+    // Runs before the other handlers of the event, so that they see the updated state
+    pub fn add_model_binding(&mut self, event_name: String, handler_name: String) {
+        self.handler_names.entry(event_name).or_default().insert(0, handler_name);
+    }
+
+    pub fn has_handler(&self, event_name: &str) -> bool {
+        self.handler_names.contains_key(event_name)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.handler_names.is_empty() && self.capture_handler_names.is_empty()
+    }
+
+    pub fn get_propagation_data(&self, event_type: &EventType) -> Vec<EventPropagationData> {
+        let handler_names = match event_type.get_name().and_then(|event_name| self.handler_names.get(event_name)) {
+            Some(handler_names) => handler_names,
+            None => return Vec::new(),
+        };
+
+        handler_names.iter()
+            .map(|handler_name| EventPropagationData { 
+                handler_name: handler_name.clone(), 
+                for_loop_contexts: self.for_loop_contexts.clone(), 
+                event: Some(event_type.clone()),
+                mouse_event: None,
+            })
+            .collect()
+    }
+
+    /*
+     * Handlers of a mouse event for the element with the given ID in a phase of the dispatch, if it is on the event's path.
+     * At the target, the capture handlers run first. Events which don't bubble (mouseenter and mouseleave) only reach the target.
+     */
+    pub fn get_dispatch_data(&self, element_id: &String, path: &[String], mouse_event: &MouseEvent, phase: EventPhase) -> Vec<EventPropagationData> {
+        let is_target = mouse_event.target_id == *element_id;
+        if !path.contains(element_id) || (!is_target && !mouse_event.event_type.bubbles()) {
+            return Vec::new();
+        }
+
+        let handler_names_by_event = match phase {
+            EventPhase::Capture => &self.capture_handler_names,
+            _ => &self.handler_names,
+        };
+        let handler_names = match mouse_event.event_type.get_name().and_then(|event_name| handler_names_by_event.get(event_name)) {
+            Some(handler_names) => handler_names,
+            None => return Vec::new(),
+        };
+
+        let current_mouse_event = mouse_event.at_current_target(element_id, phase);
+        handler_names.iter()
+            .map(|handler_name| EventPropagationData {
+                handler_name: handler_name.clone(),
+                for_loop_contexts: self.for_loop_contexts.clone(),
+                event: None,
+                mouse_event: Some(current_mouse_event.clone()),
+            })
+            .collect()
+    }
+}
+
+impl Default for EventHandlers {
+    fn default() -> Self {
+        Self::new(vec![])
+    }
+}
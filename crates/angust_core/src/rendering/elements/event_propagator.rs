@@ -1,8 +1,19 @@
-use super::{button::EventPropagationData, element::{Element, EventType}};
+use skia_safe::Point;
+
+use super::{
+    button::EventPropagationData, 
+    common_types::Position, 
+    element::{Element, ElementType, EventType}, 
+    mouse_event::{EventPhase, MouseEvent}
+};
+use crate::rendering::rendering_interface::element_renderer::ElementRenderer;
 
 
 pub fn propagate_event(element: &mut dyn Element, cursor_position: skia_safe::Point, event_type: &EventType) -> Vec<EventPropagationData> {
     let mut event_targets = Vec::new();
+    if matches!(event_type, EventType::MouseClick | EventType::MouseDown) && is_clipped_at(element, cursor_position) {
+        return event_targets; // The children are hidden there
+    }
 
     for child in element.get_children_mut().unwrap_or(&mut vec![]) {
         let child_event_targets = child.propagate_event(cursor_position, event_type);
@@ -10,4 +21,184 @@ pub fn propagate_event(element: &mut dyn Element, cursor_position: skia_safe::Po
     }
 
     event_targets
-}
\ No newline at end of file
+}
+
+/*
+ * Path of the elements under the cursor, from the given element down to the topmost one, the target of mouse events.
+ * Children are painted after their parent and previous siblings, so they are tested from last to first.
+ * Elements clipping their overflow are only entered within their clip (see ElementRenderer::get_overflow_clip). 
+ * As in the DOM, text is not a target.
+ */
+pub fn hit_test(element: &mut dyn Element, cursor_position: Point) -> Vec<String> {
+    let mut path = Vec::new();
+    collect_hit_path(element, cursor_position, &mut path);
+    path
+}
+
+fn collect_hit_path(element: &mut dyn Element, cursor_position: Point, path: &mut Vec<String>) -> bool {
+    let position = element.get_position();
+    let size = element.get_size();
+    let is_within_bounds = cursor_position.x >= position.x && cursor_position.x <= position.x + size.width &&
+        cursor_position.y >= position.y && cursor_position.y <= position.y + size.height;
+    let is_clipped = is_clipped_at(element, cursor_position);
+    if is_clipped && !is_within_bounds {
+        return false;
+    }
+
+    path.push(element.get_id());
+    if !is_clipped {
+        for child in element.get_children_mut().unwrap_or(&mut vec![]).iter_mut().rev() {
+            if collect_hit_path(child.as_mut(), cursor_position, path) {
+                return true;
+            }
+        }
+    }
+    if is_within_bounds && element.get_element_type() != ElementType::Text {
+        return true;
+    }
+
+    path.pop();
+    false
+}
+
+// Whether the element clips its overflow out of the given point, so that its children can't be hit there
+pub fn is_clipped_at(element: &dyn Element, cursor_position: Point) -> bool {
+    ElementRenderer::get_overflow_clip(element.get_position(), element.get_size(), &element.get_styles())
+        .map_or(false, |clip| !ElementRenderer::is_point_in_clip(&clip, cursor_position))
+}
+
+// Position of the last element of a path returned by hit_test
+pub fn get_target_position(element: &mut dyn Element, path: &[String]) -> Option<Position> {
+    if path.last() == Some(&element.get_id()) {
+        return Some(element.get_position());
+    }
+
+    for child in element.get_children_mut().unwrap_or(&mut vec![]) {
+        if path.contains(&child.get_id()) {
+            return get_target_position(child.as_mut(), path);
+        }
+    }
+    None
+}
+
+// Calls the function on the elements of a path returned by hit_test, from the given depth on, walking down the path once
+pub fn for_each_on_path(element: &mut dyn Element, path: &[String], start_depth: usize, function: &mut dyn FnMut(&mut dyn Element)) {
+    if path.first() != Some(&element.get_id()) {
+        return;
+    }
+    if start_depth == 0 {
+        function(element);
+    }
+
+    for child in element.get_children_mut().unwrap_or(&mut vec![]) {
+        if path.get(1) == Some(&child.get_id()) {
+            return for_each_on_path(child.as_mut(), &path[1..], start_depth.saturating_sub(1), function);
+        }
+    }
+}
+
+// Dispatches a mouse event to the child on its path, placing the element's own handlers before (capture phase) or after (bubble phase) those of the child
+pub fn dispatch_mouse_event(
+    element: &mut dyn Element, 
+    path: &[String], 
+    mouse_event: &MouseEvent, 
+    phase: EventPhase, 
+    own_event_targets: Vec<EventPropagationData>,
+) -> Vec<EventPropagationData> {
+    let own_event_targets = if element.is_disabled() { Vec::new() } else { own_event_targets };
+
+    let mut child_event_targets = Vec::new();
+    for child in element.get_children_mut().unwrap_or(&mut vec![]) {
+        if path.contains(&child.get_id()) {
+            child_event_targets.extend(child.dispatch_mouse_event(path, mouse_event, phase));
+        }
+    }
+
+    match phase {
+        EventPhase::Capture => {
+            let mut event_targets = own_event_targets;
+            event_targets.extend(child_event_targets);
+            event_targets
+        },
+        _ => {
+            child_event_targets.extend(own_event_targets);
+            child_event_targets
+        }
+    }
+}
+
+// Gives a mouse event to the open overlay of the element's subtree, if any (see Element::propagate_overlay_event)
+pub fn propagate_overlay_event(element: &mut dyn Element, cursor_position: skia_safe::Point, event_type: &EventType) -> Option<Vec<EventPropagationData>> {
+    for child in element.get_children_mut().unwrap_or(&mut vec![]) {
+        if let Some(event_targets) = child.propagate_overlay_event(cursor_position, event_type) {
+            return Some(event_targets);
+        }
+    }
+
+    None
+}
+
+/*
+ * Looks for the target of a keyboard or focus event in the element's subtree (no target meaning the element itself),
+ * returning the handlers of the elements between the target and the element, in bubbling order.
+ * The element's own handlers are appended by the caller (see should_handle_targeted_event).
+ */
+pub fn propagate_targeted_event(element: &mut dyn Element, target_id: Option<&String>, event_type: &EventType) -> Option<Vec<EventPropagationData>> {
+    if is_target(element, target_id) {
+        return Some(Vec::new());
+    }
+
+    for child in element.get_children_mut().unwrap_or(&mut vec![]) {
+        if let Some(event_targets) = child.propagate_targeted_event(target_id, event_type) {
+            return Some(event_targets);
+        }
+    }
+
+    None
+}
+
+// Whether the element should run its own handlers for an event that reached it
+pub fn should_handle_targeted_event(element: &dyn Element, target_id: Option<&String>, event_type: &EventType) -> bool {
+    event_type.bubbles() || is_target(element, target_id)
+}
+
+fn is_target(element: &dyn Element, target_id: Option<&String>) -> bool {
+    target_id.map_or(true, |id| *id == element.get_id())
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use crate::rendering::elements::{common_types::Size, container::Container};
+
+    use super::*;
+
+    fn create_container(id: &str, x: f32, y: f32, width: f32, height: f32) -> Container {
+        let mut container = Container::new();
+        container.set_id(id.to_string());
+        container.set_position(Position { x, y });
+        container.set_size(Size { width, height });
+        container
+    }
+
+    #[test]
+    fn test_hit_test_targets_topmost_element() {
+        // Arrange
+        let mut root_element = create_container("root", 0.0, 0.0, 200.0, 200.0);
+        let mut card = create_container("card", 0.0, 0.0, 100.0, 100.0);
+        card.add_child(Box::new(create_container("content", 10.0, 10.0, 50.0, 50.0)));
+        root_element.add_child(Box::new(card));
+        root_element.add_child(Box::new(create_container("overlapping", 40.0, 40.0, 100.0, 100.0)));
+
+        // Act
+        let overlapping_path = hit_test(&mut root_element, Point::new(50.0, 50.0));
+        let content_path = hit_test(&mut root_element, Point::new(20.0, 20.0));
+        let outside_path = hit_test(&mut root_element, Point::new(300.0, 300.0));
+
+        // Assert
+        assert_eq!(overlapping_path, vec!["root", "overlapping"]);
+        assert_eq!(content_path, vec!["root", "card", "content"]);
+        assert!(outside_path.is_empty());
+    }
+}
@@ -0,0 +1,33 @@
+/*
+ * Value of a checkbox, radio button, select or range, carried over when the template is reloaded (see state_transfer).
+ * Changes made by the user are kept as long as the value given by the template does not change.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormControlState {
+    pub value: String, // "true" or "false" for checkboxes and radio buttons
+    pub template_value: String,
+}
+
+impl FormControlState {
+    pub fn new(value: String) -> Self {
+        Self {
+            value: value.clone(),
+            template_value: value,
+        }
+    }
+
+    pub fn inherit(&mut self, previous_state: &FormControlState) {
+        if self.template_value == previous_state.template_value {
+            self.value = previous_state.value.clone();
+        }
+    }
+
+    // Checkboxes and radio buttons
+    pub fn is_checked(&self) -> bool {
+        self.value == "true"
+    }
+
+    pub fn set_checked(&mut self, is_checked: bool) {
+        self.value = is_checked.to_string();
+    }
+}
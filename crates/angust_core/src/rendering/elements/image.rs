@@ -1,17 +1,19 @@
 use std::{any::Any, collections::HashMap};
 
 use image::DynamicImage;
-use skia_safe::{Canvas, Point};
+use skia_safe::{Canvas, Point, Rect};
 
 use crate::{
     application::resource_loader::image_loader, 
     rendering::{
-        layout::size_estimation_system::effective_size_estimator, rendering_interface::element_renderer::ElementRenderer
+        animation::{interpolation, style_animator::StyleAnimator},
+        layout::{layout_cache::{AllocationAction, LayoutCache}, size_estimation_system::effective_size_estimator}, 
+        rendering_interface::{element_renderer::ElementRenderer, render_cache::RenderCache, skia_boundary}
     }
 };
 
 use super::{
-    button::EventPropagationData, common_types::{OptionalSize, Position, Size}, component::component::ComponentInterface, element::{Element, ElementType, EventType}, element_id_generator::ElementIDGenerator, event_propagator, styles::Styles
+    button::EventPropagationData, common_types::{OptionalSize, Position, Size}, component::component::ComponentInterface, element::{Element, ElementType, EventType}, element_id_generator::ElementIDGenerator, event_handlers::EventHandlers, event_propagator, mouse_event::{EventPhase, MouseEvent}, styles::Styles
 };
 
 
@@ -19,11 +21,16 @@ pub struct Image {
     _id: String,
     image_path: String,
     image: Option<DynamicImage>,
+    skia_image: Option<skia_safe::Image>, // Converted once, instead of on every render
+    pub event_handlers: EventHandlers,
     position: Position,
     size: Size,
     styles: Styles,
     natural_size: Size,
     requested_size: OptionalSize,
+    layout_cache: LayoutCache,
+    render_cache: RenderCache,
+    style_animator: StyleAnimator,
 }
 
 impl Image {
@@ -31,16 +38,24 @@ impl Image {
         let id = ElementIDGenerator::get();
         let image = image_loader::load_image(image_directory_relative_path, image_relative_path.clone())
             .map_or(None, |image| Some(image));
+        let skia_image = image.as_ref().and_then(skia_boundary::dynamic_image_to_skia_image);
+        let mut style_animator = StyleAnimator::default();
+        let styles = style_animator.set_styles(&Styles::default(), styles.unwrap_or_default());
 
         Self {
             _id: id,
             image_path: image_relative_path,
             image: image,
+            skia_image,
+            event_handlers: EventHandlers::default(),
             position: Position::default(),
             size: Size::default(),
-            styles: styles.unwrap_or_default(),
+            styles,
             natural_size: Size::default(),
             requested_size: OptionalSize::default(),
+            layout_cache: LayoutCache::default(),
+            render_cache: RenderCache::default(),
+            style_animator,
         }
     }
 
@@ -52,21 +67,38 @@ impl Image {
     pub fn get_image_path(&self) -> String {
         self.image_path.clone()
     }
+
+    // Displays the styles computed by the style animator on this frame
+    fn apply_animated_styles(&mut self, styles: Styles) {
+        if interpolation::affects_layout(&self.styles, &styles) {
+            self.layout_cache.mark_dirty();
+        }
+        self.styles = styles;
+        self.render_cache.invalidate();
+    }
 }
 
 impl Element for Image {
     fn render(&self, canvas: &Canvas) {
-        if let Some(image) = &self.image {
+        if let Some(skia_image) = &self.skia_image {
+            let save_count = ElementRenderer::begin_effects(canvas, self.position, self.size, &self.styles);
             ElementRenderer::render_image(
-                image,
+                skia_image,
                 canvas, 
                 self.get_position(), 
                 self.get_size(), 
             );
+            ElementRenderer::end_effects(canvas, save_count);
         }
+
+        self.render_cache.mark_painted(self.get_visual_bounds());
     }
 
-    fn update(&mut self) {}
+    fn update(&mut self, dt: f32) {
+        if let Some(styles) = self.style_animator.update(dt) {
+            self.apply_animated_styles(styles);
+        }
+    }
 
     fn handle_event(&mut self, _: Point, _: &EventType) {}
     
@@ -74,20 +106,69 @@ impl Element for Image {
         event_propagator::propagate_event(self, cursor_position, event_type)
     }
 
+    fn propagate_targeted_event(&mut self, target_id: Option<&String>, event_type: &EventType) -> Option<Vec<EventPropagationData>> {
+        event_propagator::propagate_targeted_event(self, target_id, event_type)
+    }
+
+    fn dispatch_mouse_event(&mut self, path: &[String], mouse_event: &MouseEvent, phase: EventPhase) -> Vec<EventPropagationData> {
+        let event_targets = self.event_handlers.get_dispatch_data(&self._id, path, mouse_event, phase);
+        event_propagator::dispatch_mouse_event(self, path, mouse_event, phase, event_targets)
+    }
+
+    // Animation
+    fn needs_animation_frame(&self) -> bool {
+        self.style_animator.is_animating()
+    }
+
+    fn get_style_animator(&self) -> Option<&StyleAnimator> {
+        Some(&self.style_animator)
+    }
+
+    fn inherit_style_animator(&mut self, previous_animator: &StyleAnimator, previous_styles: &Styles) {
+        if let Some(styles) = self.style_animator.inherit(previous_animator, previous_styles) {
+            self.apply_animated_styles(styles);
+        }
+    }
+
+    // Rendering cache
+    fn needs_repaint(&self) -> bool {
+        self.render_cache.needs_repaint()
+    }
+
+    fn get_visual_bounds(&self) -> Rect {
+        let visual_bounds = Rect::from_xywh(self.position.x, self.position.y, self.size.width, self.size.height);
+
+        ElementRenderer::transform_bounds(visual_bounds, self.position, self.size, &self.styles)
+    }
+
+    fn collect_damage(&self, damaged_rects: &mut Vec<Rect>) {
+        if self.render_cache.needs_repaint() {
+            self.render_cache.collect_own_damage(self.get_visual_bounds(), damaged_rects);
+        }
+    }
+
     fn set_id(&mut self, id: String) {
         self._id = id;
     }
 
     fn set_position(&mut self, position: Position) {
+        if self.position != position {
+            self.render_cache.invalidate();
+        }
         self.position = position;
     }
 
     fn set_size(&mut self, size: Size) {
+        if self.size != size {
+            self.render_cache.invalidate();
+        }
         self.size = size;
     }
 
     fn set_styles(&mut self, styles: Styles) {
-        self.styles = styles;
+        self.styles = self.style_animator.set_styles(&self.styles, styles);
+        self.layout_cache.mark_dirty();
+        self.render_cache.invalidate();
     }
 
     fn add_child(&mut self, _: Box<dyn Element>) {}
@@ -153,6 +234,14 @@ impl Element for Image {
     fn is_text_wrapper(&self) -> bool { false }
 
     fn estimate_sizes(&mut self) {
+        if !self.layout_cache.needs_estimation() {
+            if let Some((natural_size, requested_size)) = self.layout_cache.get_estimated_sizes() {
+                self.natural_size = natural_size;
+                self.requested_size = requested_size;
+            }
+            return;
+        }
+
         let estimated_image_size = self.image.as_ref()
             .map_or(Size::default(), |image| Size {
                 width: image.width() as f32,
@@ -162,11 +251,20 @@ impl Element for Image {
 
         let sizing_policy = self.get_styles().sizing_policy.unwrap_or_default();
         self.set_requested_size(OptionalSize { width: sizing_policy.width, height: sizing_policy.height });
+
+        self.layout_cache.store_estimated_sizes(self.natural_size, self.requested_size);
     }
 
     fn allocate_space(&mut self, allocated_position: Position, allocated_size: Size) {
+        if self.layout_cache.get_allocation_action(allocated_position, allocated_size) != AllocationAction::Full {
+            return;
+        }
+
         self.position = allocated_position;
         self.size = allocated_size;
+        self.render_cache.invalidate();
+
+        self.layout_cache.store_allocation(allocated_position, allocated_size);
     }
     
     fn layout(&mut self, allocated_position: Position, allocated_size: Size) {
@@ -174,6 +272,25 @@ impl Element for Image {
         self.allocate_space(allocated_position, allocated_size);
     }
 
+    // Incremental layout
+    fn needs_layout(&self) -> bool {
+        self.layout_cache.needs_layout()
+    }
+
+    fn mark_layout_dirty(&mut self) {
+        self.layout_cache.mark_dirty();
+    }
+
+    fn has_estimate_changed(&self) -> bool {
+        self.layout_cache.has_estimate_changed()
+    }
+
+    fn allocate_pending_space(&mut self) {
+        if let Some((allocated_position, allocated_size)) = self.layout_cache.get_allocation() {
+            self.allocate_space(allocated_position, allocated_size);
+        }
+    }
+
     fn react_to_state_change(&mut self, _component_id: String) {}
 }
 
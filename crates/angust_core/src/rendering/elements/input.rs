@@ -0,0 +1,632 @@
+use std::{any::Any, collections::HashMap};
+
+use skia_safe::{Canvas, Color, Point, Rect};
+
+use crate::rendering::{
+    animation::{interpolation, style_animator::StyleAnimator},
+    layout::{layout_cache::{AllocationAction, LayoutCache}, size_estimation_system::effective_size_estimator},
+    rendering_interface::{element_renderer::ElementRenderer, render_cache::RenderCache, skia_boundary}
+};
+
+use super::{
+    button::EventPropagationData,
+    common_types::{OptionalSize, Position, Size},
+    component::component::ComponentInterface,
+    element::{Element, ElementType, EventType},
+    element_id_generator::ElementIDGenerator,
+    event_handlers::EventHandlers,
+    event_propagator,
+    mouse_event::{EventPhase, MouseEvent},
+    pseudo_class_styles::{InteractionState, PseudoClassStyles},
+    styles::{Border, BorderSide, Cursor, Dimension, Padding, Styles, Unit},
+    text_editing_model::{EditResult, TextEditingState}
+};
+
+
+const DEFAULT_WIDTH: f32 = 150.0; // Of the content box, as browsers do for 20 characters or so
+const PASSWORD_MASK: char = '•';
+
+// Checkboxes and radio buttons are Checkbox elements, ranges Range elements
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InputType {
+    Text,
+    Password,
+    Checkbox,
+    Radio,
+    Range,
+}
+
+impl InputType {
+    pub fn from_attribute(input_type: &str) -> Option<Self> {
+        match input_type {
+            "text" => Some(InputType::Text),
+            "password" => Some(InputType::Password),
+            "checkbox" => Some(InputType::Checkbox),
+            "radio" => Some(InputType::Radio),
+            "range" => Some(InputType::Range),
+            _ => None,
+        }
+    }
+}
+
+/*
+ * Single-line text field, edited from the keyboard once focused. The caret is placed by clicking,
+ * and the text selected by dragging or with Shift. The text scrolls horizontally to keep the caret visible.
+ * Emits input events on every edit and change events once the edit is committed (Enter or blur).
+ */
+pub struct Input {
+    _id: String,
+    pub template_id: Option<String>, // The id attribute, not necessarily unique
+    input_type: InputType,
+    pub placeholder: String,
+    pub event_handlers: EventHandlers,
+    pub tab_index: Option<i32>,
+    pub pseudo_class_styles: PseudoClassStyles,
+
+    editing_state: TextEditingState,
+    caret_offsets: Vec<f32>, // Of the displayed text, relative to its start
+    line_metrics: (f32, f32), // Ascent and line height
+    is_selecting: bool, // While the mouse is pressed
+
+    position: Position,
+    size: Size,
+    styles: Styles,
+    natural_size: Size,
+    requested_size: OptionalSize,
+    base_styles: Styles, // Before pseudo-class rules
+    interaction_state: InteractionState,
+    layout_cache: LayoutCache,
+    render_cache: RenderCache,
+    style_animator: StyleAnimator,
+}
+
+impl Input {
+    pub fn new(input_type: InputType, value: String, styles: Option<Styles>) -> Self {
+        let id = ElementIDGenerator::get();
+        let mut style_animator = StyleAnimator::default();
+        let base_styles = styles.unwrap_or_default();
+        let styles = style_animator.set_styles(&Styles::default(), base_styles);
+
+        Self {
+            _id: id,
+            template_id: None,
+            input_type,
+            placeholder: String::new(),
+            event_handlers: EventHandlers::default(),
+            tab_index: Some(0), // Inputs are focusable by default
+            pseudo_class_styles: PseudoClassStyles::default(),
+            editing_state: TextEditingState::new(value),
+            caret_offsets: vec![0.0],
+            line_metrics: (0.0, 0.0),
+            is_selecting: false,
+            position: Position::default(),
+            size: Size::default(),
+            styles,
+            natural_size: Size::default(),
+            requested_size: OptionalSize::default(),
+            base_styles,
+            interaction_state: InteractionState::default(),
+            layout_cache: LayoutCache::default(),
+            render_cache: RenderCache::default(),
+            style_animator,
+        }
+    }
+
+    // Appearance before the element's classes and inline styles, close to the browsers' one
+    pub fn get_default_styles() -> Styles {
+        let padding_y = Dimension { value: 2.0, unit: Unit::Px };
+        let padding_x = Dimension { value: 4.0, unit: Unit::Px };
+
+        Styles {
+            padding: Some(Padding { top: padding_y, right: padding_x, bottom: padding_y, left: padding_x }),
+            border: Some(Border::uniform(BorderSide {
+                width: Dimension { value: 1.0, unit: Unit::Px },
+                color: Some(Color::from_rgb(118, 118, 118)),
+                ..Default::default()
+            })),
+            background_color: Some(Color::WHITE),
+            cursor: Some(Cursor::Text),
+            ..Default::default()
+        }
+    }
+
+    pub fn get_value(&self) -> String {
+        self.editing_state.model.get_value().clone()
+    }
+
+    pub fn get_input_type(&self) -> InputType {
+        self.input_type
+    }
+
+    // Default actions
+    /*
+     * Edits the value on key presses, returning the handlers of the input event if the value changed.
+     * Enter and blur commit the edit, returning the handlers of the change event if the value changed since the focus.
+     */
+    fn apply_default_action(&mut self, event_type: &EventType) -> Vec<EventPropagationData> {
+        match event_type {
+            EventType::Focus => {
+                self.editing_state.begin_editing();
+                Vec::new()
+            },
+            EventType::Blur => {
+                let committed_value = self.editing_state.end_editing();
+                self.get_change_event_targets(committed_value)
+            },
+            EventType::KeyDown(keyboard_event) if keyboard_event.key == "Enter" => {
+                let committed_value = self.editing_state.commit_editing();
+                self.get_change_event_targets(committed_value)
+            },
+            EventType::KeyDown(keyboard_event) => {
+                match self.editing_state.model.handle_key_down(keyboard_event, false) {
+                    EditResult::ValueChanged => {
+                        self.update_caret_offsets();
+                        self.scroll_to_caret();
+                        self.event_handlers.get_propagation_data(&EventType::Input(self.get_value()))
+                    },
+                    EditResult::SelectionChanged => {
+                        self.scroll_to_caret();
+                        Vec::new()
+                    },
+                    EditResult::Ignored => Vec::new(),
+                }
+            },
+            _ => Vec::new(),
+        }
+    }
+
+    fn get_change_event_targets(&self, committed_value: Option<String>) -> Vec<EventPropagationData> {
+        committed_value
+            .map(|value| self.event_handlers.get_propagation_data(&EventType::Change(value)))
+            .unwrap_or_default()
+    }
+
+    // Text measurement
+    fn get_displayed_text(&self) -> String {
+        match self.input_type {
+            InputType::Password => self.editing_state.model.get_value().chars().map(|_| PASSWORD_MASK).collect(),
+            _ => self.get_value(),
+        }
+    }
+
+    fn get_font_size(&self) -> f32 {
+        self.styles.font_size.unwrap_or(Dimension { value: 16.0, unit: Unit::Px }).value
+    }
+
+    fn update_caret_offsets(&mut self) {
+        self.caret_offsets = skia_boundary::get_caret_offsets(
+            &self.get_displayed_text(),
+            self.get_font_size(),
+            self.styles.font_weight.unwrap_or_default(),
+            self.styles.font_family.unwrap_or_default(),
+            self.styles.font_style.unwrap_or_default(),
+        );
+        self.render_cache.invalidate();
+    }
+
+    fn update_line_metrics(&mut self) {
+        self.line_metrics = skia_boundary::get_line_metrics(
+            self.get_font_size(),
+            self.styles.font_weight.unwrap_or_default(),
+            self.styles.font_family.unwrap_or_default(),
+            self.styles.font_style.unwrap_or_default(),
+        );
+    }
+
+    fn get_caret_offset(&self, index: usize) -> f32 {
+        self.caret_offsets.get(index).copied().unwrap_or(*self.caret_offsets.last().unwrap_or(&0.0))
+    }
+
+    // Closest caret position to a point of the input
+    fn get_caret_index_at(&self, cursor_position: Point) -> usize {
+        let (content_position, _) = ElementRenderer::get_content_box(self.position, self.size, &self.styles);
+        let offset = cursor_position.x - content_position.x + self.editing_state.scroll_offset;
+
+        self.caret_offsets.iter()
+            .enumerate()
+            .min_by(|(_, first), (_, second)| (*first - offset).abs().total_cmp(&(*second - offset).abs()))
+            .map_or(0, |(index, _)| index)
+    }
+
+    // Scrolls the text horizontally so that the caret stays within the content box
+    fn scroll_to_caret(&mut self) {
+        let (_, content_size) = ElementRenderer::get_content_box(self.position, self.size, &self.styles);
+        let caret_offset = self.get_caret_offset(self.editing_state.model.get_selection().caret);
+        let text_width = *self.caret_offsets.last().unwrap_or(&0.0);
+        let mut scroll_offset = self.editing_state.scroll_offset;
+
+        if caret_offset - scroll_offset > content_size.width {
+            scroll_offset = caret_offset - content_size.width;
+        }
+        if caret_offset < scroll_offset {
+            scroll_offset = caret_offset;
+        }
+        self.editing_state.scroll_offset = scroll_offset.min((text_width - content_size.width).max(0.0)).max(0.0);
+        self.render_cache.invalidate();
+    }
+
+    // Utils
+    fn is_position_within_bounds(&self, point: Point) -> bool {
+        point.x >= self.position.x && point.x <= self.position.x + self.size.width &&
+        point.y >= self.position.y && point.y <= self.position.y + self.size.height
+    }
+
+    // Displays the styles matching the current interaction state (eg. :focus rules)
+    fn apply_interaction_state(&mut self) {
+        if self.pseudo_class_styles.is_empty() {
+            return;
+        }
+        let styles = self.pseudo_class_styles.resolve(self.base_styles, &self.interaction_state);
+        if self.style_animator.is_target(&styles) {
+            return; // The matching rules leave the styles unchanged
+        }
+        let displayed_styles = self.style_animator.set_styles(&self.styles, styles);
+        self.apply_animated_styles(displayed_styles);
+    }
+
+    pub fn set_disabled(&mut self, is_disabled: bool) {
+        if self.interaction_state.is_disabled == is_disabled {
+            return;
+        }
+        self.interaction_state.is_disabled = is_disabled;
+        self.apply_interaction_state();
+    }
+
+    // Displays the styles computed by the style animator on this frame
+    fn apply_animated_styles(&mut self, styles: Styles) {
+        if interpolation::affects_layout(&self.styles, &styles) {
+            self.layout_cache.mark_dirty();
+        }
+        self.styles = styles;
+        self.render_cache.invalidate();
+    }
+}
+
+impl Element for Input {
+    fn render(&self, canvas: &Canvas) {
+        let save_count = ElementRenderer::begin_effects(canvas, self.position, self.size, &self.styles);
+
+        ElementRenderer::render_element(
+            canvas,
+            self.position,
+            self.size,
+            self.styles.background_color.unwrap_or(Color::TRANSPARENT),
+            &self.styles.border.unwrap_or_default(),
+            self.styles.text_color.unwrap_or(Color::BLACK),
+        );
+        ElementRenderer::render_inset_shadows(canvas, self.position, self.size, &self.styles);
+
+        let (content_position, content_size) = ElementRenderer::get_content_box(self.position, self.size, &self.styles);
+        ElementRenderer::clip(canvas, content_position, content_size);
+
+        // The line is centered vertically within the content box
+        let (ascent, line_height) = self.line_metrics;
+        let line_top = content_position.y + (content_size.height - line_height) / 2.0;
+        let text_x = content_position.x - self.editing_state.scroll_offset;
+        let selection = self.editing_state.model.get_selection();
+        let is_focused = self.interaction_state.is_focused;
+
+        if is_focused && !selection.is_collapsed() {
+            let (start, end) = selection.get_range();
+            let start_offset = self.get_caret_offset(start);
+            ElementRenderer::render_element(
+                canvas,
+                Position { x: text_x + start_offset, y: line_top },
+                Size { width: self.get_caret_offset(end) - start_offset, height: line_height },
+                Color::from_rgb(179, 215, 255),
+                &Border::default(),
+                self.styles.text_color.unwrap_or(Color::BLACK),
+            );
+        }
+
+        let is_empty = self.editing_state.model.get_char_count() == 0;
+        let (text, text_color) = if is_empty {
+            (self.placeholder.clone(), Color::from_rgb(117, 117, 117))
+        } else {
+            (self.get_displayed_text(), self.styles.text_color.unwrap_or(Color::BLACK))
+        };
+        ElementRenderer::render_multi_line_text(
+            canvas,
+            Position { x: text_x, y: line_top + ascent },
+            vec![text],
+            text_color,
+            self.get_font_size(),
+            self.styles.font_weight.unwrap_or_default(),
+            self.styles.font_family.unwrap_or_default(),
+            self.styles.font_style.unwrap_or_default(),
+        );
+
+        if is_focused {
+            ElementRenderer::render_caret(
+                canvas,
+                Position { x: text_x + self.get_caret_offset(selection.caret), y: line_top },
+                line_height,
+                self.styles.text_color.unwrap_or(Color::BLACK),
+            );
+        }
+
+        ElementRenderer::end_effects(canvas, save_count);
+        self.render_cache.mark_painted(self.get_visual_bounds());
+    }
+
+    fn update(&mut self, dt: f32) {
+        if let Some(styles) = self.style_animator.update(dt) {
+            self.apply_animated_styles(styles);
+        }
+    }
+
+    // Places the caret on click, and selects the text while dragging
+    fn handle_event(&mut self, cursor_position: Point, event_type: &EventType) {
+        match event_type {
+            EventType::MouseDown if self.is_position_within_bounds(cursor_position) && !self.interaction_state.is_disabled => {
+                let caret_index = self.get_caret_index_at(cursor_position);
+                self.editing_state.model.set_caret(caret_index, false);
+                self.is_selecting = true;
+                self.render_cache.invalidate();
+            },
+            EventType::MouseDrag if self.is_selecting => {
+                let caret_index = self.get_caret_index_at(cursor_position);
+                self.editing_state.model.set_caret(caret_index, true);
+                self.scroll_to_caret();
+            },
+            EventType::MouseUp => {
+                self.is_selecting = false;
+            },
+            _ => {}
+        }
+    }
+
+    fn propagate_event(&mut self, cursor_position: skia_safe::Point, event_type: &EventType) -> Vec<EventPropagationData> {
+        event_propagator::propagate_event(self, cursor_position, event_type)
+    }
+
+    fn propagate_targeted_event(&mut self, target_id: Option<&String>, event_type: &EventType) -> Option<Vec<EventPropagationData>> {
+        let mut event_targets = event_propagator::propagate_targeted_event(self, target_id, event_type)?;
+        if event_propagator::should_handle_targeted_event(self, target_id, event_type) {
+            event_targets.extend(self.event_handlers.get_propagation_data(event_type));
+        }
+        event_targets.extend(self.apply_default_action(event_type));
+
+        Some(event_targets)
+    }
+
+    fn dispatch_mouse_event(&mut self, path: &[String], mouse_event: &MouseEvent, phase: EventPhase) -> Vec<EventPropagationData> {
+        let event_targets = self.event_handlers.get_dispatch_data(&self._id, path, mouse_event, phase);
+        event_propagator::dispatch_mouse_event(self, path, mouse_event, phase, event_targets)
+    }
+
+    // Focus
+    fn get_tab_index(&self) -> Option<i32> {
+        self.tab_index.filter(|_| !self.interaction_state.is_disabled)
+    }
+
+    fn set_focused(&mut self, is_focused: bool) {
+        if self.interaction_state.is_focused == is_focused {
+            return;
+        }
+        self.interaction_state.is_focused = is_focused;
+        self.is_selecting = false;
+        self.apply_interaction_state();
+    }
+
+    // Pointer interaction
+    fn set_hovered(&mut self, is_hovered: bool) {
+        if self.interaction_state.is_hovered == is_hovered {
+            return;
+        }
+        self.interaction_state.is_hovered = is_hovered;
+        self.apply_interaction_state();
+    }
+
+    fn set_active(&mut self, is_active: bool) {
+        if self.interaction_state.is_active == is_active {
+            return;
+        }
+        self.interaction_state.is_active = is_active;
+        self.apply_interaction_state();
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.interaction_state.is_disabled
+    }
+
+    // Text editing
+    fn get_text_editing_state(&self) -> Option<&TextEditingState> {
+        Some(&self.editing_state)
+    }
+
+    fn inherit_text_editing_state(&mut self, previous_state: &TextEditingState) {
+        self.editing_state.inherit(previous_state);
+        self.update_caret_offsets();
+    }
+
+    // Animation
+    fn needs_animation_frame(&self) -> bool {
+        self.style_animator.is_animating()
+    }
+
+    fn get_style_animator(&self) -> Option<&StyleAnimator> {
+        Some(&self.style_animator)
+    }
+
+    fn inherit_style_animator(&mut self, previous_animator: &StyleAnimator, previous_styles: &Styles) {
+        if let Some(styles) = self.style_animator.inherit(previous_animator, previous_styles) {
+            self.apply_animated_styles(styles);
+        }
+    }
+
+    // Rendering cache
+    fn needs_repaint(&self) -> bool {
+        self.render_cache.needs_repaint()
+    }
+
+    fn get_visual_bounds(&self) -> Rect {
+        let visual_bounds = Rect::from_xywh(self.position.x, self.position.y, self.size.width, self.size.height);
+
+        ElementRenderer::transform_bounds(visual_bounds, self.position, self.size, &self.styles)
+    }
+
+    fn collect_damage(&self, damaged_rects: &mut Vec<Rect>) {
+        if self.render_cache.needs_repaint() {
+            self.render_cache.collect_own_damage(self.get_visual_bounds(), damaged_rects);
+        }
+    }
+
+    fn set_id(&mut self, id: String) {
+        self._id = id;
+    }
+
+    fn set_position(&mut self, position: Position) {
+        if self.position != position {
+            self.render_cache.invalidate();
+        }
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Size) {
+        if self.size != size {
+            self.render_cache.invalidate();
+        }
+        self.size = size;
+    }
+
+    fn set_styles(&mut self, styles: Styles) {
+        self.base_styles = styles;
+        let styles = self.pseudo_class_styles.resolve(styles, &self.interaction_state);
+        self.styles = self.style_animator.set_styles(&self.styles, styles);
+        self.layout_cache.mark_dirty();
+        self.render_cache.invalidate();
+    }
+
+    fn add_child(&mut self, _: Box<dyn Element>) {}
+
+    fn get_id(&self) -> String {
+        self._id.clone()
+    }
+
+    fn get_template_id(&self) -> Option<&String> {
+        self.template_id.as_ref()
+    }
+
+    fn get_element_type(&self) -> ElementType {
+        ElementType::Input
+    }
+
+    fn get_name(&self) -> String {
+        "input".to_string()
+    }
+
+    fn get_position(&self) -> Position {
+        self.position
+    }
+
+    fn get_size(&self) -> Size {
+        self.size
+    }
+
+    fn get_styles(&self) -> Styles {
+        self.styles
+    }
+
+    fn get_children_mut(&mut self) -> Option<&mut Vec<Box<dyn Element>>> { None }
+
+    // Component system
+    fn get_component_interface(&mut self) -> Option<&mut dyn ComponentInterface> {
+        None
+    }
+
+    fn initialize(&mut self, _: HashMap<String, Box<dyn Any>>) {
+        // Nothing for now (implemented for components only)
+    }
+
+    fn handle_route_change(&mut self, _: &String, _: &String) {
+        // Nothing for now (implemented for components only)
+    }
+
+    // Layout system
+    fn set_natural_size(&mut self, size: Size) {
+        self.natural_size = size;
+    }
+
+    fn set_requested_size(&mut self, requested_size: OptionalSize) {
+        self.requested_size = requested_size;
+    }
+
+    fn get_natural_size(&self) -> Size {
+        self.natural_size
+    }
+
+    fn get_requested_size(&self) -> OptionalSize {
+        self.requested_size
+    }
+
+    fn get_effective_size(&self) -> Size {
+        effective_size_estimator::estimate_effective_size(&self.get_requested_size(), &self.get_natural_size())
+    }
+
+    fn is_text_wrapper(&self) -> bool { false }
+
+    // One line of text, regardless of the value
+    fn estimate_sizes(&mut self) {
+        if !self.layout_cache.needs_estimation() {
+            if let Some((natural_size, requested_size)) = self.layout_cache.get_estimated_sizes() {
+                self.natural_size = natural_size;
+                self.requested_size = requested_size;
+            }
+            return;
+        }
+
+        self.update_line_metrics();
+        self.update_caret_offsets();
+
+        let border = self.styles.border.unwrap_or_default();
+        let padding = self.styles.padding.unwrap_or_default();
+        self.set_natural_size(Size {
+            width: DEFAULT_WIDTH + border.horizontal() + padding.horizontal(),
+            height: self.line_metrics.1 + border.vertical() + padding.vertical(),
+        });
+
+        let sizing_policy = self.get_styles().sizing_policy.unwrap_or_default();
+        self.set_requested_size(OptionalSize { width: sizing_policy.width, height: sizing_policy.height });
+
+        self.layout_cache.store_estimated_sizes(self.natural_size, self.requested_size);
+    }
+
+    fn allocate_space(&mut self, allocated_position: Position, allocated_size: Size) {
+        if self.layout_cache.get_allocation_action(allocated_position, allocated_size) != AllocationAction::Full {
+            return;
+        }
+
+        self.position = allocated_position;
+        self.size = allocated_size;
+        self.scroll_to_caret();
+
+        self.layout_cache.store_allocation(allocated_position, allocated_size);
+    }
+
+    fn layout(&mut self, allocated_position: Position, allocated_size: Size) {
+        self.estimate_sizes();
+        self.allocate_space(allocated_position, allocated_size);
+    }
+
+    // Incremental layout
+    fn needs_layout(&self) -> bool {
+        self.layout_cache.needs_layout()
+    }
+
+    fn mark_layout_dirty(&mut self) {
+        self.layout_cache.mark_dirty();
+    }
+
+    fn has_estimate_changed(&self) -> bool {
+        self.layout_cache.has_estimate_changed()
+    }
+
+    fn allocate_pending_space(&mut self) {
+        if let Some((allocated_position, allocated_size)) = self.layout_cache.get_allocation() {
+            self.allocate_space(allocated_position, allocated_size);
+        }
+    }
+
+    fn react_to_state_change(&mut self, _component_id: String) {}
+}
@@ -1,10 +1,22 @@
 pub mod element;
 pub mod element_id_generator;
+pub mod event_handlers;
+pub mod mouse_event;
+pub mod state_transfer;
+pub mod pseudo_class_styles;
+pub mod text_editing_model;
+pub mod form_control;
+pub mod radio_groups;
 
 pub mod container;
 pub mod button;
 pub mod text;
 pub mod image;
+pub mod input;
+pub mod textarea;
+pub mod checkbox;
+pub mod select;
+pub mod range;
 
 pub mod component;
 pub mod service;
@@ -0,0 +1,158 @@
+use std::{cell::{Cell, RefCell}, rc::Rc};
+
+use super::{common_types::Position, element::{EventType, KeyModifiers}};
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EventPhase {
+    Capture, // From the root down to the target's parent
+    Target,
+    Bubble, // From the target's parent up to the root
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+/*
+ * Payload of mouse events ($event in template handlers), named after the DOM's MouseEvent:
+ * position is the cursor's in the window, and offset its position relative to the target's top-left corner, both in logical pixels.
+ * button is the button pressed, released or held while dragging, and wheel_delta (wheel_delta_x) the distance scrolled vertically (horizontally) by the wheel.
+ * click_count is the number of consecutive clicks of the button (2 on a double click, 3 on a triple click), 0 for moves and the wheel.
+ * The event is dispatched along the path from the root
+ * to the topmost element under the cursor (the target): down the path in the capture phase, then back up in the bubble phase.
+ * As in the DOM, stop_propagation() keeps it from reaching the next elements of the path (the other handlers of the current element still run),
+ * and prevent_default() cancels the default action (eg. focusing the element, toggling a checkbox or scrolling).
+ * Clones share these flags, so handlers can call them on the payload they receive.
+ */
+#[derive(Clone, Debug)]
+pub struct MouseEvent {
+    pub event_type: EventType,
+    pub position: Position,
+    pub offset: Position,
+    pub button: Option<MouseButton>,
+    pub modifiers: KeyModifiers,
+    pub wheel_delta: f32,
+    pub wheel_delta_x: f32,
+    pub click_count: u32,
+    pub target_id: String,
+    pub current_target_id: String, // Element whose handler is running
+    pub phase: EventPhase,
+    dispatch_flags: Rc<DispatchFlags>,
+}
+
+#[derive(Debug)]
+struct DispatchFlags {
+    stopped_at: RefCell<Option<(String, EventPhase)>>,
+    is_default_prevented: Cell<bool>,
+}
+
+impl Default for DispatchFlags {
+    fn default() -> Self {
+        Self {
+            stopped_at: RefCell::new(None),
+            is_default_prevented: Cell::new(false),
+        }
+    }
+}
+
+impl MouseEvent {
+    pub fn new(event_type: EventType, position: Position, button: Option<MouseButton>, modifiers: KeyModifiers) -> Self {
+        let (wheel_delta, wheel_delta_x) = match event_type {
+            EventType::MouseRoll(delta) => (delta, 0.0),
+            EventType::MouseRollHorizontal(delta) => (0.0, delta),
+            _ => (0.0, 0.0),
+        };
+
+        Self {
+            event_type,
+            position,
+            offset: position,
+            button,
+            modifiers,
+            wheel_delta,
+            wheel_delta_x,
+            click_count: 0,
+            target_id: String::new(),
+            current_target_id: String::new(),
+            phase: EventPhase::Target,
+            dispatch_flags: Rc::new(DispatchFlags::default()),
+        }
+    }
+
+    // A new dispatch of the event (or of a related one, eg. mouseenter for a mousemove) to the given target, with its own flags
+    pub fn dispatched_to(&self, event_type: EventType, target_id: String, target_position: Position) -> Self {
+        let mut mouse_event = Self::new(event_type, self.position, self.button, self.modifiers);
+        mouse_event.click_count = self.click_count;
+        mouse_event.offset = Position { x: self.position.x - target_position.x, y: self.position.y - target_position.y };
+        mouse_event.current_target_id = target_id.clone();
+        mouse_event.target_id = target_id;
+        mouse_event
+    }
+
+    pub fn stop_propagation(&self) {
+        let mut stopped_at = self.dispatch_flags.stopped_at.borrow_mut();
+        if stopped_at.is_none() {
+            *stopped_at = Some((self.current_target_id.clone(), self.phase));
+        }
+    }
+
+    pub fn prevent_default(&self) {
+        self.dispatch_flags.is_default_prevented.set(true);
+    }
+
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.dispatch_flags.stopped_at.borrow().is_some()
+    }
+
+    pub fn is_default_prevented(&self) -> bool {
+        self.dispatch_flags.is_default_prevented.get()
+    }
+
+    // The payload given to the handlers of an element of the path
+    pub fn at_current_target(&self, current_target_id: &String, phase: EventPhase) -> Self {
+        let mut mouse_event = self.clone();
+        mouse_event.current_target_id = current_target_id.clone();
+        mouse_event.phase = if *current_target_id == self.target_id { EventPhase::Target } else { phase };
+        mouse_event
+    }
+
+    // Whether the handlers of the current target still run, which is the case until the propagation is stopped at another element or phase
+    pub fn should_run_handlers(&self) -> bool {
+        match self.dispatch_flags.stopped_at.borrow().as_ref() {
+            Some((element_id, phase)) => *element_id == self.current_target_id && *phase == self.phase,
+            None => true,
+        }
+    }
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stop_propagation_lets_current_element_handlers_run() {
+        // Arrange
+        let mouse_event = MouseEvent::new(EventType::MouseClick, Position { x: 30.0, y: 25.0 }, Some(MouseButton::Left), KeyModifiers::default())
+            .dispatched_to(EventType::MouseClick, "button".to_string(), Position { x: 10.0, y: 20.0 });
+        let at_button = mouse_event.at_current_target(&"button".to_string(), EventPhase::Bubble);
+        let at_parent = mouse_event.at_current_target(&"parent".to_string(), EventPhase::Bubble);
+
+        // Act
+        at_button.stop_propagation();
+        at_button.prevent_default();
+
+        // Assert
+        assert_eq!(at_button.phase, EventPhase::Target);
+        assert_eq!(at_button.offset, Position { x: 20.0, y: 5.0 });
+        assert!(at_button.should_run_handlers());
+        assert!(!at_parent.should_run_handlers());
+        assert!(mouse_event.is_propagation_stopped());
+        assert!(mouse_event.is_default_prevented());
+    }
+}
@@ -0,0 +1,127 @@
+use crate::parsing::css::{css_parser, stylesheet_parser::Property};
+
+use super::styles::Styles;
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PseudoClass {
+    Hover,
+    Active,
+    Focus,
+    Disabled,
+}
+
+impl PseudoClass {
+    pub fn from_css_name(name: &str) -> Option<Self> {
+        match name {
+            "hover" => Some(PseudoClass::Hover),
+            "active" => Some(PseudoClass::Active),
+            "focus" => Some(PseudoClass::Focus),
+            "disabled" => Some(PseudoClass::Disabled),
+            _ => None,
+        }
+    }
+}
+
+/*
+ * State of the user's interaction with an element, which pseudo-classes match against.
+ * As in the DOM, an element is hovered while the cursor is over it or one of its descendants, 
+ * and active from a press on it or one of its descendants until the release.
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InteractionState {
+    pub is_hovered: bool,
+    pub is_active: bool,
+    pub is_focused: bool,
+    pub is_disabled: bool,
+}
+
+impl Default for InteractionState {
+    fn default() -> Self {
+        Self {
+            is_hovered: false,
+            is_active: false,
+            is_focused: false,
+            is_disabled: false,
+        }
+    }
+}
+
+impl InteractionState {
+    pub fn matches(&self, pseudo_class: PseudoClass) -> bool {
+        match pseudo_class {
+            PseudoClass::Hover => self.is_hovered,
+            PseudoClass::Active => self.is_active,
+            PseudoClass::Focus => self.is_focused,
+            PseudoClass::Disabled => self.is_disabled,
+        }
+    }
+}
+
+/*
+ * Rules of the element's classes with a pseudo-class, eg. ".nav-button:focus { ... }".
+ * The properties of the matching rules are applied on top of the element's styles, in the stylesheet's order.
+ */
+#[derive(Clone, Debug)]
+pub struct PseudoClassStyles {
+    rules: Vec<(PseudoClass, Vec<Property>)>,
+}
+
+impl Default for PseudoClassStyles {
+    fn default() -> Self {
+        Self { rules: Vec::new() }
+    }
+}
+
+impl PseudoClassStyles {
+    pub fn add_rule(&mut self, pseudo_class: PseudoClass, properties: Vec<Property>) {
+        self.rules.push((pseudo_class, properties));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn resolve(&self, base_styles: Styles, interaction_state: &InteractionState) -> Styles {
+        let mut styles = base_styles;
+
+        for (pseudo_class, properties) in &self.rules {
+            if interaction_state.matches(*pseudo_class) {
+                css_parser::apply_properties(&mut styles, properties);
+            }
+        }
+
+        styles
+    }
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use crate::parsing::css::stylesheet_parser::Property;
+
+    use super::*;
+
+    fn create_rule(name: &str, value: &str) -> Vec<Property> {
+        vec![Property { name: name.to_string(), value: value.to_string() }]
+    }
+
+    #[test]
+    fn test_resolve_applies_matching_rules_in_order() {
+        // Arrange
+        let mut pseudo_class_styles = PseudoClassStyles::default();
+        pseudo_class_styles.add_rule(PseudoClass::Hover, create_rule("opacity", "0.8"));
+        pseudo_class_styles.add_rule(PseudoClass::Active, create_rule("opacity", "0.6"));
+        pseudo_class_styles.add_rule(PseudoClass::Disabled, create_rule("opacity", "0.4"));
+        let interaction_state = InteractionState { is_hovered: true, is_active: true, ..Default::default() };
+
+        // Act
+        let styles = pseudo_class_styles.resolve(Styles::default(), &interaction_state);
+        let idle_styles = pseudo_class_styles.resolve(Styles::default(), &InteractionState::default());
+
+        // Assert
+        assert_eq!(styles.opacity, Some(0.6));
+        assert_eq!(idle_styles.opacity, Styles::default().opacity);
+    }
+}
@@ -0,0 +1,36 @@
+use super::element::Element;
+
+
+/*
+ * Radio buttons sharing a name form a group, in which only one is checked: once the user checks one,
+ * the others are unchecked, wherever they are in the tree.
+ */
+pub fn update_radio_groups(root_element: &mut dyn Element) {
+    let mut checked_radios = Vec::new();
+    collect_checked_radios(root_element, &mut checked_radios);
+
+    for (group_name, radio_id) in checked_radios.iter() {
+        uncheck_other_radios(root_element, group_name, radio_id);
+    }
+}
+
+// (Group name, ID) of the radio buttons the user just checked
+fn collect_checked_radios(element: &mut dyn Element, checked_radios: &mut Vec<(String, String)>) {
+    if let Some(group_name) = element.take_checked_radio_group() {
+        checked_radios.push((group_name, element.get_id()));
+    }
+
+    for child in element.get_children_mut().unwrap_or(&mut vec![]) {
+        collect_checked_radios(child.as_mut(), checked_radios);
+    }
+}
+
+fn uncheck_other_radios(element: &mut dyn Element, group_name: &String, radio_id: &String) {
+    if element.get_id() != *radio_id {
+        element.uncheck_radio(group_name);
+    }
+
+    for child in element.get_children_mut().unwrap_or(&mut vec![]) {
+        uncheck_other_radios(child.as_mut(), group_name, radio_id);
+    }
+}
@@ -0,0 +1,562 @@
+use std::{any::Any, collections::HashMap};
+
+use skia_safe::{Canvas, Color, Point, Rect};
+
+use crate::rendering::{
+    animation::{interpolation, style_animator::StyleAnimator},
+    layout::{layout_cache::{AllocationAction, LayoutCache}, size_estimation_system::effective_size_estimator},
+    rendering_interface::{element_renderer::ElementRenderer, render_cache::RenderCache}
+};
+
+use super::{
+    button::EventPropagationData,
+    checkbox::DEFAULT_ACCENT_COLOR,
+    common_types::{OptionalSize, Position, Size},
+    component::component::ComponentInterface,
+    element::{Element, ElementType, EventType},
+    element_id_generator::ElementIDGenerator,
+    event_handlers::EventHandlers,
+    event_propagator,
+    form_control::FormControlState,
+    mouse_event::{EventPhase, MouseEvent},
+    pseudo_class_styles::{InteractionState, PseudoClassStyles},
+    styles::{Border, Styles}
+};
+
+
+const DEFAULT_WIDTH: f32 = 129.0; // Of the content box, as in browsers
+const DEFAULT_HEIGHT: f32 = 16.0;
+const TRACK_HEIGHT: f32 = 4.0;
+const PAGE_STEPS: f64 = 10.0; // Steps moved by PageUp and PageDown
+
+/*
+ * Slider (input type="range") between min and max, by increments of step. The value is set by clicking or dragging along the track,
+ * or from the keyboard once focused (arrows, PageUp/PageDown, Home/End). Emits input events while the value changes
+ * and a change event once it is committed (when the thumb is released, or on every key press).
+ * The track is drawn with the background color, the part before the thumb and the thumb with the text color.
+ */
+pub struct Range {
+    _id: String,
+    pub template_id: Option<String>, // The id attribute, not necessarily unique
+    min: f64,
+    max: f64,
+    step: f64,
+    pub event_handlers: EventHandlers,
+    pub tab_index: Option<i32>,
+    pub pseudo_class_styles: PseudoClassStyles,
+
+    control_state: FormControlState,
+    value_on_drag: Option<String>, // While the thumb is dragged, to emit a change event once released
+
+    position: Position,
+    size: Size,
+    styles: Styles,
+    natural_size: Size,
+    requested_size: OptionalSize,
+    base_styles: Styles, // Before pseudo-class rules
+    interaction_state: InteractionState,
+    layout_cache: LayoutCache,
+    render_cache: RenderCache,
+    style_animator: StyleAnimator,
+}
+
+impl Range {
+    pub fn new(min: f64, max: f64, step: f64, value: f64, styles: Option<Styles>) -> Self {
+        let id = ElementIDGenerator::get();
+        let mut style_animator = StyleAnimator::default();
+        let base_styles = styles.unwrap_or_default();
+        let styles = style_animator.set_styles(&Styles::default(), base_styles);
+
+        let mut range = Self {
+            _id: id,
+            template_id: None,
+            min,
+            max: max.max(min),
+            step: if step > 0.0 { step } else { 1.0 },
+            event_handlers: EventHandlers::default(),
+            tab_index: Some(0), // Ranges are focusable by default
+            pseudo_class_styles: PseudoClassStyles::default(),
+            control_state: FormControlState::new(String::new()),
+            value_on_drag: None,
+            position: Position::default(),
+            size: Size::default(),
+            styles,
+            natural_size: Size::default(),
+            requested_size: OptionalSize::default(),
+            base_styles,
+            interaction_state: InteractionState::default(),
+            layout_cache: LayoutCache::default(),
+            render_cache: RenderCache::default(),
+            style_animator,
+        };
+        range.control_state = FormControlState::new(format_value(range.snap_value(value)));
+        range
+    }
+
+    pub fn get_default_styles() -> Styles {
+        Styles {
+            background_color: Some(Color::from_rgb(229, 229, 229)),
+            ..Default::default()
+        }
+    }
+
+    pub fn get_value(&self) -> f64 {
+        self.control_state.value.parse().unwrap_or(self.min)
+    }
+
+    // Nearest multiple of the step from min, within the bounds
+    fn snap_value(&self, value: f64) -> f64 {
+        let last_value = self.min + ((self.max - self.min) / self.step).floor() * self.step;
+        let snapped_value = self.min + ((value - self.min) / self.step).round() * self.step;
+
+        snapped_value.clamp(self.min, last_value)
+    }
+
+    // Returns whether the value changed
+    fn set_value(&mut self, value: f64) -> bool {
+        let value = format_value(self.snap_value(value));
+        if value == self.control_state.value {
+            return false;
+        }
+
+        self.control_state.value = value;
+        self.render_cache.invalidate();
+        true
+    }
+
+    // Default actions
+    fn apply_default_action(&mut self, event_type: &EventType) -> Vec<EventPropagationData> {
+        let keyboard_event = match event_type {
+            EventType::KeyDown(keyboard_event) => keyboard_event,
+            _ => return Vec::new(),
+        };
+
+        let value = self.get_value();
+        let new_value = match keyboard_event.key.as_str() {
+            "ArrowLeft" | "ArrowDown" => value - self.step,
+            "ArrowRight" | "ArrowUp" => value + self.step,
+            "PageDown" => value - PAGE_STEPS * self.step,
+            "PageUp" => value + PAGE_STEPS * self.step,
+            "Home" => self.min,
+            "End" => self.max,
+            _ => return Vec::new(),
+        };
+        if !self.set_value(new_value) {
+            return Vec::new();
+        }
+
+        let mut event_targets = self.event_handlers.get_propagation_data(&EventType::Input(self.control_state.value.clone()));
+        event_targets.extend(self.event_handlers.get_propagation_data(&EventType::Change(self.control_state.value.clone())));
+        event_targets
+    }
+
+    // Pressing the track moves the thumb there, then the thumb follows the cursor until released
+    fn handle_drag(&mut self, cursor_position: Point, event_type: &EventType) -> Vec<EventPropagationData> {
+        match event_type {
+            EventType::MouseClick if self.is_position_within_bounds(cursor_position) && !self.interaction_state.is_disabled => {
+                self.value_on_drag = Some(self.control_state.value.clone());
+                self.set_value_at(cursor_position)
+            },
+            EventType::MouseDrag if self.value_on_drag.is_some() => self.set_value_at(cursor_position),
+            EventType::MouseUp if self.value_on_drag.is_some() => {
+                let value_on_drag = self.value_on_drag.take();
+                if value_on_drag.as_ref() == Some(&self.control_state.value) {
+                    return Vec::new();
+                }
+                self.event_handlers.get_propagation_data(&EventType::Change(self.control_state.value.clone()))
+            },
+            _ => Vec::new(),
+        }
+    }
+
+    fn set_value_at(&mut self, cursor_position: Point) -> Vec<EventPropagationData> {
+        let (track_start, track_width) = self.get_track_extent();
+        let ratio = if track_width > 0.0 { ((cursor_position.x - track_start) / track_width).clamp(0.0, 1.0) } else { 0.0 };
+
+        if !self.set_value(self.min + ratio as f64 * (self.max - self.min)) {
+            return Vec::new();
+        }
+        self.event_handlers.get_propagation_data(&EventType::Input(self.control_state.value.clone()))
+    }
+
+    // Geometry
+    fn get_thumb_radius(&self) -> f32 {
+        let (_, content_size) = ElementRenderer::get_content_box(self.position, self.size, &self.styles);
+        (content_size.height / 2.0).min(DEFAULT_HEIGHT / 2.0)
+    }
+
+    // Horizontal start and width of the track, along which the thumb's center moves
+    fn get_track_extent(&self) -> (f32, f32) {
+        let (content_position, content_size) = ElementRenderer::get_content_box(self.position, self.size, &self.styles);
+        let thumb_radius = self.get_thumb_radius();
+
+        (content_position.x + thumb_radius, (content_size.width - 2.0 * thumb_radius).max(0.0))
+    }
+
+    fn get_value_ratio(&self) -> f32 {
+        if self.max <= self.min {
+            return 0.0;
+        }
+        ((self.get_value() - self.min) / (self.max - self.min)) as f32
+    }
+
+    // Utils
+    fn is_position_within_bounds(&self, point: Point) -> bool {
+        point.x >= self.position.x && point.x <= self.position.x + self.size.width &&
+        point.y >= self.position.y && point.y <= self.position.y + self.size.height
+    }
+
+    // Displays the styles matching the current interaction state (eg. :focus rules)
+    fn apply_interaction_state(&mut self) {
+        if self.pseudo_class_styles.is_empty() {
+            return;
+        }
+        let styles = self.pseudo_class_styles.resolve(self.base_styles, &self.interaction_state);
+        if self.style_animator.is_target(&styles) {
+            return; // The matching rules leave the styles unchanged
+        }
+        let displayed_styles = self.style_animator.set_styles(&self.styles, styles);
+        self.apply_animated_styles(displayed_styles);
+    }
+
+    pub fn set_disabled(&mut self, is_disabled: bool) {
+        if self.interaction_state.is_disabled == is_disabled {
+            return;
+        }
+        self.interaction_state.is_disabled = is_disabled;
+        self.apply_interaction_state();
+    }
+
+    // Displays the styles computed by the style animator on this frame
+    fn apply_animated_styles(&mut self, styles: Styles) {
+        if interpolation::affects_layout(&self.styles, &styles) {
+            self.layout_cache.mark_dirty();
+        }
+        self.styles = styles;
+        self.render_cache.invalidate();
+    }
+}
+
+// Integers are displayed without decimals, as in the DOM
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value as i64)
+    } else {
+        format!("{}", (value * 1e6).round() / 1e6) // Drops the floating point noise of the steps
+    }
+}
+
+impl Element for Range {
+    fn render(&self, canvas: &Canvas) {
+        let save_count = ElementRenderer::begin_effects(canvas, self.position, self.size, &self.styles);
+
+        let border = self.styles.border.unwrap_or_default();
+        ElementRenderer::render_element(canvas, self.position, self.size, Color::TRANSPARENT, &border, self.styles.text_color.unwrap_or(Color::BLACK));
+
+        let (content_position, content_size) = ElementRenderer::get_content_box(self.position, self.size, &self.styles);
+        let (track_start, track_width) = self.get_track_extent();
+        let center_y = content_position.y + content_size.height / 2.0;
+        let thumb_x = track_start + self.get_value_ratio() * track_width;
+        let accent_color = self.styles.text_color.unwrap_or(DEFAULT_ACCENT_COLOR);
+
+        ElementRenderer::render_element(
+            canvas,
+            Position { x: track_start, y: center_y - TRACK_HEIGHT / 2.0 },
+            Size { width: track_width, height: TRACK_HEIGHT },
+            self.styles.background_color.unwrap_or(Color::TRANSPARENT),
+            &Border::default(),
+            self.styles.text_color.unwrap_or(Color::BLACK),
+        );
+        ElementRenderer::render_element(
+            canvas,
+            Position { x: track_start, y: center_y - TRACK_HEIGHT / 2.0 },
+            Size { width: thumb_x - track_start, height: TRACK_HEIGHT },
+            accent_color,
+            &Border::default(),
+            self.styles.text_color.unwrap_or(Color::BLACK),
+        );
+        ElementRenderer::render_circle(canvas, Point::new(thumb_x, center_y), self.get_thumb_radius(), accent_color, 0.0, Color::TRANSPARENT);
+
+        ElementRenderer::end_effects(canvas, save_count);
+        self.render_cache.mark_painted(self.get_visual_bounds());
+    }
+
+    fn update(&mut self, dt: f32) {
+        if let Some(styles) = self.style_animator.update(dt) {
+            self.apply_animated_styles(styles);
+        }
+    }
+
+    fn handle_event(&mut self, _: Point, _: &EventType) {}
+
+    fn propagate_event(&mut self, cursor_position: skia_safe::Point, event_type: &EventType) -> Vec<EventPropagationData> {
+        self.handle_drag(cursor_position, event_type)
+    }
+
+    fn propagate_targeted_event(&mut self, target_id: Option<&String>, event_type: &EventType) -> Option<Vec<EventPropagationData>> {
+        let mut event_targets = event_propagator::propagate_targeted_event(self, target_id, event_type)?;
+        if event_propagator::should_handle_targeted_event(self, target_id, event_type) {
+            event_targets.extend(self.event_handlers.get_propagation_data(event_type));
+        }
+        event_targets.extend(self.apply_default_action(event_type));
+
+        Some(event_targets)
+    }
+
+    fn dispatch_mouse_event(&mut self, path: &[String], mouse_event: &MouseEvent, phase: EventPhase) -> Vec<EventPropagationData> {
+        let event_targets = self.event_handlers.get_dispatch_data(&self._id, path, mouse_event, phase);
+        event_propagator::dispatch_mouse_event(self, path, mouse_event, phase, event_targets)
+    }
+
+    // Focus
+    fn get_tab_index(&self) -> Option<i32> {
+        self.tab_index.filter(|_| !self.interaction_state.is_disabled)
+    }
+
+    fn set_focused(&mut self, is_focused: bool) {
+        if self.interaction_state.is_focused == is_focused {
+            return;
+        }
+        self.interaction_state.is_focused = is_focused;
+        self.apply_interaction_state();
+    }
+
+    // Pointer interaction
+    fn set_hovered(&mut self, is_hovered: bool) {
+        if self.interaction_state.is_hovered == is_hovered {
+            return;
+        }
+        self.interaction_state.is_hovered = is_hovered;
+        self.apply_interaction_state();
+    }
+
+    fn set_active(&mut self, is_active: bool) {
+        if self.interaction_state.is_active == is_active {
+            return;
+        }
+        self.interaction_state.is_active = is_active;
+        self.apply_interaction_state();
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.interaction_state.is_disabled
+    }
+
+    // Form controls
+    fn get_form_control_state(&self) -> Option<&FormControlState> {
+        Some(&self.control_state)
+    }
+
+    fn inherit_form_control_state(&mut self, previous_state: &FormControlState) {
+        self.control_state.inherit(previous_state);
+        self.render_cache.invalidate();
+    }
+
+    // Animation
+    fn needs_animation_frame(&self) -> bool {
+        self.style_animator.is_animating()
+    }
+
+    fn get_style_animator(&self) -> Option<&StyleAnimator> {
+        Some(&self.style_animator)
+    }
+
+    fn inherit_style_animator(&mut self, previous_animator: &StyleAnimator, previous_styles: &Styles) {
+        if let Some(styles) = self.style_animator.inherit(previous_animator, previous_styles) {
+            self.apply_animated_styles(styles);
+        }
+    }
+
+    // Rendering cache
+    fn needs_repaint(&self) -> bool {
+        self.render_cache.needs_repaint()
+    }
+
+    fn get_visual_bounds(&self) -> Rect {
+        let visual_bounds = Rect::from_xywh(self.position.x, self.position.y, self.size.width, self.size.height);
+
+        ElementRenderer::transform_bounds(visual_bounds, self.position, self.size, &self.styles)
+    }
+
+    fn collect_damage(&self, damaged_rects: &mut Vec<Rect>) {
+        if self.render_cache.needs_repaint() {
+            self.render_cache.collect_own_damage(self.get_visual_bounds(), damaged_rects);
+        }
+    }
+
+    fn set_id(&mut self, id: String) {
+        self._id = id;
+    }
+
+    fn set_position(&mut self, position: Position) {
+        if self.position != position {
+            self.render_cache.invalidate();
+        }
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Size) {
+        if self.size != size {
+            self.render_cache.invalidate();
+        }
+        self.size = size;
+    }
+
+    fn set_styles(&mut self, styles: Styles) {
+        self.base_styles = styles;
+        let styles = self.pseudo_class_styles.resolve(styles, &self.interaction_state);
+        self.styles = self.style_animator.set_styles(&self.styles, styles);
+        self.layout_cache.mark_dirty();
+        self.render_cache.invalidate();
+    }
+
+    fn add_child(&mut self, _: Box<dyn Element>) {}
+
+    fn get_id(&self) -> String {
+        self._id.clone()
+    }
+
+    fn get_template_id(&self) -> Option<&String> {
+        self.template_id.as_ref()
+    }
+
+    fn get_element_type(&self) -> ElementType {
+        ElementType::Range
+    }
+
+    fn get_name(&self) -> String {
+        "input".to_string()
+    }
+
+    fn get_position(&self) -> Position {
+        self.position
+    }
+
+    fn get_size(&self) -> Size {
+        self.size
+    }
+
+    fn get_styles(&self) -> Styles {
+        self.styles
+    }
+
+    fn get_children_mut(&mut self) -> Option<&mut Vec<Box<dyn Element>>> { None }
+
+    // Component system
+    fn get_component_interface(&mut self) -> Option<&mut dyn ComponentInterface> {
+        None
+    }
+
+    fn initialize(&mut self, _: HashMap<String, Box<dyn Any>>) {
+        // Nothing for now (implemented for components only)
+    }
+
+    fn handle_route_change(&mut self, _: &String, _: &String) {
+        // Nothing for now (implemented for components only)
+    }
+
+    // Layout system
+    fn set_natural_size(&mut self, size: Size) {
+        self.natural_size = size;
+    }
+
+    fn set_requested_size(&mut self, requested_size: OptionalSize) {
+        self.requested_size = requested_size;
+    }
+
+    fn get_natural_size(&self) -> Size {
+        self.natural_size
+    }
+
+    fn get_requested_size(&self) -> OptionalSize {
+        self.requested_size
+    }
+
+    fn get_effective_size(&self) -> Size {
+        effective_size_estimator::estimate_effective_size(&self.get_requested_size(), &self.get_natural_size())
+    }
+
+    fn is_text_wrapper(&self) -> bool { false }
+
+    fn estimate_sizes(&mut self) {
+        if !self.layout_cache.needs_estimation() {
+            if let Some((natural_size, requested_size)) = self.layout_cache.get_estimated_sizes() {
+                self.natural_size = natural_size;
+                self.requested_size = requested_size;
+            }
+            return;
+        }
+
+        let border = self.styles.border.unwrap_or_default();
+        let padding = self.styles.padding.unwrap_or_default();
+        self.set_natural_size(Size {
+            width: DEFAULT_WIDTH + border.horizontal() + padding.horizontal(),
+            height: DEFAULT_HEIGHT + border.vertical() + padding.vertical(),
+        });
+
+        let sizing_policy = self.get_styles().sizing_policy.unwrap_or_default();
+        self.set_requested_size(OptionalSize { width: sizing_policy.width, height: sizing_policy.height });
+
+        self.layout_cache.store_estimated_sizes(self.natural_size, self.requested_size);
+    }
+
+    fn allocate_space(&mut self, allocated_position: Position, allocated_size: Size) {
+        if self.layout_cache.get_allocation_action(allocated_position, allocated_size) != AllocationAction::Full {
+            return;
+        }
+
+        self.position = allocated_position;
+        self.size = allocated_size;
+
+        self.layout_cache.store_allocation(allocated_position, allocated_size);
+    }
+
+    fn layout(&mut self, allocated_position: Position, allocated_size: Size) {
+        self.estimate_sizes();
+        self.allocate_space(allocated_position, allocated_size);
+    }
+
+    // Incremental layout
+    fn needs_layout(&self) -> bool {
+        self.layout_cache.needs_layout()
+    }
+
+    fn mark_layout_dirty(&mut self) {
+        self.layout_cache.mark_dirty();
+    }
+
+    fn has_estimate_changed(&self) -> bool {
+        self.layout_cache.has_estimate_changed()
+    }
+
+    fn allocate_pending_space(&mut self) {
+        if let Some((allocated_position, allocated_size)) = self.layout_cache.get_allocation() {
+            self.allocate_space(allocated_position, allocated_size);
+        }
+    }
+
+    fn react_to_state_change(&mut self, _component_id: String) {}
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_snaps_to_step_within_bounds() {
+        // Arrange
+        let mut range = Range::new(0.0, 1.0, 0.25, 0.3, None);
+
+        // Act
+        let initial_value = range.get_value();
+        range.set_value(0.9);
+        let value_above_max = range.set_value(5.0);
+
+        // Assert
+        assert_eq!(initial_value, 0.25);
+        assert_eq!(range.get_value(), 1.0);
+        assert!(!value_above_max);
+    }
+}
@@ -0,0 +1,704 @@
+use std::{any::Any, collections::HashMap};
+
+use skia_safe::{Canvas, Color, Point, Rect};
+
+use crate::rendering::{
+    animation::{interpolation, style_animator::StyleAnimator},
+    layout::{layout_cache::{AllocationAction, LayoutCache}, size_estimation_system::effective_size_estimator},
+    rendering_interface::{element_renderer::ElementRenderer, render_cache::RenderCache, skia_boundary}
+};
+
+use super::{
+    button::EventPropagationData,
+    checkbox::DEFAULT_ACCENT_COLOR,
+    common_types::{OptionalSize, Position, Size},
+    component::component::ComponentInterface,
+    element::{Element, ElementType, EventType},
+    element_id_generator::ElementIDGenerator,
+    event_handlers::EventHandlers,
+    event_propagator,
+    form_control::FormControlState,
+    input::Input,
+    mouse_event::{EventPhase, MouseEvent},
+    pseudo_class_styles::{InteractionState, PseudoClassStyles},
+    styles::{Border, Dimension, Styles, Unit}
+};
+
+
+const MAX_VISIBLE_OPTIONS: usize = 8; // The popup scrolls beyond
+const OPTION_PADDING_Y: f32 = 2.0;
+const OPTION_PADDING_X: f32 = 4.0;
+const ARROW_WIDTH: f32 = 8.0;
+const ARROW_MARGIN: f32 = 6.0; // Between the label and the arrow, and after the arrow
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelectOption {
+    pub value: String,
+    pub label: String,
+}
+
+/*
+ * Dropdown list (select), showing the label of the selected option. Clicking it or pressing Enter or Space once focused
+ * opens a popup listing the options, drawn above the rest of the UI (see Element::render_overlay).
+ * While open, the arrow keys move the highlighted option, Enter or Space selects it and Escape closes the popup;
+ * clicking an option selects it, and clicking anywhere else closes the popup.
+ * While closed, the arrow keys directly change the selection. Emits input and change events with the selected option's value.
+ */
+pub struct Select {
+    _id: String,
+    pub template_id: Option<String>, // The id attribute, not necessarily unique
+    options: Vec<SelectOption>,
+    pub event_handlers: EventHandlers,
+    pub tab_index: Option<i32>,
+    pub pseudo_class_styles: PseudoClassStyles,
+
+    control_state: FormControlState, // Value of the selected option
+    is_open: bool,
+    highlighted_index: usize,
+    first_visible_index: usize, // Of the popup, scrolled with the wheel or the highlight
+    line_metrics: (f32, f32), // Ascent and line height
+
+    position: Position,
+    size: Size,
+    styles: Styles,
+    natural_size: Size,
+    requested_size: OptionalSize,
+    base_styles: Styles, // Before pseudo-class rules
+    interaction_state: InteractionState,
+    layout_cache: LayoutCache,
+    render_cache: RenderCache,
+    style_animator: StyleAnimator,
+}
+
+impl Select {
+    // Selects the option with the given value, or the first one
+    pub fn new(options: Vec<SelectOption>, value: Option<String>, styles: Option<Styles>) -> Self {
+        let id = ElementIDGenerator::get();
+        let mut style_animator = StyleAnimator::default();
+        let base_styles = styles.unwrap_or_default();
+        let styles = style_animator.set_styles(&Styles::default(), base_styles);
+
+        let selected_value = value
+            .filter(|value| options.iter().any(|option| option.value == *value))
+            .or_else(|| options.first().map(|option| option.value.clone()))
+            .unwrap_or_default();
+
+        Self {
+            _id: id,
+            template_id: None,
+            options,
+            event_handlers: EventHandlers::default(),
+            tab_index: Some(0), // Selects are focusable by default
+            pseudo_class_styles: PseudoClassStyles::default(),
+            control_state: FormControlState::new(selected_value),
+            is_open: false,
+            highlighted_index: 0,
+            first_visible_index: 0,
+            line_metrics: (0.0, 0.0),
+            position: Position::default(),
+            size: Size::default(),
+            styles,
+            natural_size: Size::default(),
+            requested_size: OptionalSize::default(),
+            base_styles,
+            interaction_state: InteractionState::default(),
+            layout_cache: LayoutCache::default(),
+            render_cache: RenderCache::default(),
+            style_animator,
+        }
+    }
+
+    pub fn get_default_styles() -> Styles {
+        Input::get_default_styles()
+    }
+
+    pub fn get_value(&self) -> String {
+        self.control_state.value.clone()
+    }
+
+    fn get_selected_index(&self) -> Option<usize> {
+        self.options.iter().position(|option| option.value == self.control_state.value)
+    }
+
+    // Returns the handlers of the input and change events if the selection changed
+    fn select_index(&mut self, index: usize) -> Vec<EventPropagationData> {
+        let value = match self.options.get(index) {
+            Some(option) => option.value.clone(),
+            None => return Vec::new(),
+        };
+        if value == self.control_state.value {
+            return Vec::new();
+        }
+
+        self.control_state.value = value.clone();
+        self.render_cache.invalidate();
+
+        let mut event_targets = self.event_handlers.get_propagation_data(&EventType::Input(value.clone()));
+        event_targets.extend(self.event_handlers.get_propagation_data(&EventType::Change(value)));
+        event_targets
+    }
+
+    // Popup
+    fn open(&mut self) {
+        if self.options.is_empty() {
+            return;
+        }
+        self.is_open = true;
+        self.highlighted_index = self.get_selected_index().unwrap_or(0);
+        self.scroll_to_highlight();
+        self.render_cache.invalidate();
+    }
+
+    fn close(&mut self) {
+        if !self.is_open {
+            return;
+        }
+        self.is_open = false;
+        self.render_cache.invalidate();
+    }
+
+    fn move_highlight(&mut self, offset: isize) {
+        let last_index = self.options.len().saturating_sub(1) as isize;
+        self.highlighted_index = (self.highlighted_index as isize + offset).clamp(0, last_index) as usize;
+        self.scroll_to_highlight();
+        self.render_cache.invalidate();
+    }
+
+    fn scroll_to_highlight(&mut self) {
+        let visible_count = self.get_visible_option_count();
+        if self.highlighted_index < self.first_visible_index {
+            self.first_visible_index = self.highlighted_index;
+        } else if self.highlighted_index >= self.first_visible_index + visible_count {
+            self.first_visible_index = self.highlighted_index + 1 - visible_count;
+        }
+    }
+
+    fn scroll_popup(&mut self, offset: isize) {
+        let max_first_index = (self.options.len() - self.get_visible_option_count()) as isize;
+        self.first_visible_index = (self.first_visible_index as isize + offset).clamp(0, max_first_index) as usize;
+        self.render_cache.invalidate();
+    }
+
+    // Default actions
+    fn apply_default_action(&mut self, event_type: &EventType) -> Vec<EventPropagationData> {
+        let keyboard_event = match event_type {
+            EventType::KeyDown(keyboard_event) => keyboard_event,
+            EventType::Blur => {
+                self.close();
+                return Vec::new();
+            },
+            _ => return Vec::new(),
+        };
+
+        if !self.is_open {
+            let selected_index = self.get_selected_index().unwrap_or(0);
+            return match keyboard_event.key.as_str() {
+                "Enter" | " " => {
+                    self.open();
+                    Vec::new()
+                },
+                "ArrowDown" => self.select_index(selected_index + 1),
+                "ArrowUp" if selected_index > 0 => self.select_index(selected_index - 1),
+                _ => Vec::new(),
+            };
+        }
+
+        match keyboard_event.key.as_str() {
+            "Enter" | " " => {
+                self.close();
+                self.select_index(self.highlighted_index)
+            },
+            "Escape" => {
+                self.close();
+                Vec::new()
+            },
+            "ArrowDown" => {
+                self.move_highlight(1);
+                Vec::new()
+            },
+            "ArrowUp" => {
+                self.move_highlight(-1);
+                Vec::new()
+            },
+            "PageDown" => {
+                self.move_highlight(MAX_VISIBLE_OPTIONS as isize);
+                Vec::new()
+            },
+            "PageUp" => {
+                self.move_highlight(-(MAX_VISIBLE_OPTIONS as isize));
+                Vec::new()
+            },
+            _ => Vec::new(),
+        }
+    }
+
+    // Geometry
+    fn get_font_size(&self) -> f32 {
+        self.styles.font_size.unwrap_or(Dimension { value: 16.0, unit: Unit::Px }).value
+    }
+
+    fn update_line_metrics(&mut self) {
+        self.line_metrics = skia_boundary::get_line_metrics(
+            self.get_font_size(),
+            self.styles.font_weight.unwrap_or_default(),
+            self.styles.font_family.unwrap_or_default(),
+            self.styles.font_style.unwrap_or_default(),
+        );
+    }
+
+    fn get_option_height(&self) -> f32 {
+        self.line_metrics.1 + 2.0 * OPTION_PADDING_Y
+    }
+
+    fn get_visible_option_count(&self) -> usize {
+        self.options.len().min(MAX_VISIBLE_OPTIONS)
+    }
+
+    // Below the select, as wide as it
+    fn get_popup_bounds(&self) -> Rect {
+        let border = self.styles.border.unwrap_or_default();
+        let height = self.get_visible_option_count() as f32 * self.get_option_height() + border.vertical();
+
+        Rect::from_xywh(self.position.x, self.position.y + self.size.height, self.size.width, height)
+    }
+
+    fn get_option_index_at(&self, point: Point) -> Option<usize> {
+        let popup_bounds = self.get_popup_bounds();
+        if !popup_bounds.contains(point) {
+            return None;
+        }
+
+        let border_top = self.styles.border.unwrap_or_default().top.get_width();
+        let row = ((point.y - popup_bounds.top - border_top) / self.get_option_height()).floor().max(0.0) as usize;
+        let index = self.first_visible_index + row;
+
+        if index < self.options.len() { Some(index) } else { None }
+    }
+
+    // Utils
+    fn is_position_within_bounds(&self, point: Point) -> bool {
+        point.x >= self.position.x && point.x <= self.position.x + self.size.width &&
+        point.y >= self.position.y && point.y <= self.position.y + self.size.height
+    }
+
+    // Displays the styles matching the current interaction state (eg. :focus rules)
+    fn apply_interaction_state(&mut self) {
+        if self.pseudo_class_styles.is_empty() {
+            return;
+        }
+        let styles = self.pseudo_class_styles.resolve(self.base_styles, &self.interaction_state);
+        if self.style_animator.is_target(&styles) {
+            return; // The matching rules leave the styles unchanged
+        }
+        let displayed_styles = self.style_animator.set_styles(&self.styles, styles);
+        self.apply_animated_styles(displayed_styles);
+    }
+
+    pub fn set_disabled(&mut self, is_disabled: bool) {
+        if self.interaction_state.is_disabled == is_disabled {
+            return;
+        }
+        self.interaction_state.is_disabled = is_disabled;
+        self.apply_interaction_state();
+    }
+
+    // Displays the styles computed by the style animator on this frame
+    fn apply_animated_styles(&mut self, styles: Styles) {
+        if interpolation::affects_layout(&self.styles, &styles) {
+            self.layout_cache.mark_dirty();
+        }
+        self.styles = styles;
+        self.render_cache.invalidate();
+    }
+}
+
+impl Element for Select {
+    fn render(&self, canvas: &Canvas) {
+        let save_count = ElementRenderer::begin_effects(canvas, self.position, self.size, &self.styles);
+
+        ElementRenderer::render_element(
+            canvas,
+            self.position,
+            self.size,
+            self.styles.background_color.unwrap_or(Color::TRANSPARENT),
+            &self.styles.border.unwrap_or_default(),
+            self.styles.text_color.unwrap_or(Color::BLACK),
+        );
+        ElementRenderer::render_inset_shadows(canvas, self.position, self.size, &self.styles);
+
+        let (content_position, content_size) = ElementRenderer::get_content_box(self.position, self.size, &self.styles);
+        let text_color = self.styles.text_color.unwrap_or(Color::BLACK);
+        let arrow_center = Point::new(
+            content_position.x + content_size.width - ARROW_MARGIN - ARROW_WIDTH / 2.0,
+            content_position.y + content_size.height / 2.0,
+        );
+        ElementRenderer::render_dropdown_arrow(canvas, arrow_center, ARROW_WIDTH, text_color);
+
+        let label_width = (content_size.width - ARROW_WIDTH - 2.0 * ARROW_MARGIN).max(0.0);
+        ElementRenderer::clip(canvas, content_position, Size { width: label_width, height: content_size.height });
+
+        let (ascent, line_height) = self.line_metrics;
+        let label = self.get_selected_index().map(|index| self.options[index].label.clone()).unwrap_or_default();
+        ElementRenderer::render_multi_line_text(
+            canvas,
+            Position { x: content_position.x, y: content_position.y + (content_size.height - line_height) / 2.0 + ascent },
+            vec![label],
+            text_color,
+            self.get_font_size(),
+            self.styles.font_weight.unwrap_or_default(),
+            self.styles.font_family.unwrap_or_default(),
+            self.styles.font_style.unwrap_or_default(),
+        );
+
+        ElementRenderer::end_effects(canvas, save_count);
+        self.render_cache.mark_painted(self.get_visual_bounds());
+    }
+
+    fn update(&mut self, dt: f32) {
+        if let Some(styles) = self.style_animator.update(dt) {
+            self.apply_animated_styles(styles);
+        }
+    }
+
+    fn handle_event(&mut self, _: Point, _: &EventType) {}
+
+    // Clicks while open are taken by the popup (see propagate_overlay_event)
+    fn propagate_event(&mut self, cursor_position: skia_safe::Point, event_type: &EventType) -> Vec<EventPropagationData> {
+        if *event_type == EventType::MouseClick && self.is_position_within_bounds(cursor_position) && !self.interaction_state.is_disabled {
+            self.open();
+        }
+        Vec::new()
+    }
+
+    fn propagate_targeted_event(&mut self, target_id: Option<&String>, event_type: &EventType) -> Option<Vec<EventPropagationData>> {
+        let mut event_targets = event_propagator::propagate_targeted_event(self, target_id, event_type)?;
+        if event_propagator::should_handle_targeted_event(self, target_id, event_type) {
+            event_targets.extend(self.event_handlers.get_propagation_data(event_type));
+        }
+        event_targets.extend(self.apply_default_action(event_type));
+
+        Some(event_targets)
+    }
+
+    fn dispatch_mouse_event(&mut self, path: &[String], mouse_event: &MouseEvent, phase: EventPhase) -> Vec<EventPropagationData> {
+        let event_targets = self.event_handlers.get_dispatch_data(&self._id, path, mouse_event, phase);
+        event_propagator::dispatch_mouse_event(self, path, mouse_event, phase, event_targets)
+    }
+
+    // Overlays
+    fn render_overlay(&self, canvas: &Canvas) {
+        if !self.is_open {
+            return;
+        }
+
+        let popup_bounds = self.get_popup_bounds();
+        let border = self.styles.border.unwrap_or_default();
+        ElementRenderer::render_element(
+            canvas,
+            Position { x: popup_bounds.left, y: popup_bounds.top },
+            Size { width: popup_bounds.width(), height: popup_bounds.height() },
+            self.styles.background_color.unwrap_or(Color::WHITE),
+            &border,
+            self.styles.text_color.unwrap_or(Color::BLACK),
+        );
+
+        let option_height = self.get_option_height();
+        let (ascent, _) = self.line_metrics;
+        let options_x = popup_bounds.left + border.left.get_width();
+        let options_width = popup_bounds.width() - border.horizontal();
+        let visible_options = self.options.iter().enumerate().skip(self.first_visible_index).take(self.get_visible_option_count());
+
+        for (row, (index, option)) in visible_options.enumerate() {
+            let option_y = popup_bounds.top + border.top.get_width() + row as f32 * option_height;
+            let is_highlighted = index == self.highlighted_index;
+            if is_highlighted {
+                ElementRenderer::render_element(
+                    canvas,
+                    Position { x: options_x, y: option_y },
+                    Size { width: options_width, height: option_height },
+                    DEFAULT_ACCENT_COLOR,
+                    &Border::default(),
+                    self.styles.text_color.unwrap_or(Color::BLACK),
+                );
+            }
+
+            ElementRenderer::render_multi_line_text(
+                canvas,
+                Position { x: options_x + OPTION_PADDING_X, y: option_y + OPTION_PADDING_Y + ascent },
+                vec![option.label.clone()],
+                if is_highlighted { Color::WHITE } else { self.styles.text_color.unwrap_or(Color::BLACK) },
+                self.get_font_size(),
+                self.styles.font_weight.unwrap_or_default(),
+                self.styles.font_family.unwrap_or_default(),
+                self.styles.font_style.unwrap_or_default(),
+            );
+        }
+    }
+
+    // While open, takes every click (selecting the option clicked, if any, then closing) and the wheel over the popup
+    fn propagate_overlay_event(&mut self, cursor_position: Point, event_type: &EventType) -> Option<Vec<EventPropagationData>> {
+        if !self.is_open {
+            return None;
+        }
+
+        match event_type {
+            EventType::MouseClick => {
+                let clicked_index = self.get_option_index_at(cursor_position);
+                self.close();
+                Some(clicked_index.map(|index| self.select_index(index)).unwrap_or_default())
+            },
+            EventType::MouseRoll(delta) if self.get_popup_bounds().contains(cursor_position) => {
+                self.scroll_popup(if *delta > 0.0 { -1 } else { 1 });
+                Some(Vec::new())
+            },
+            _ => None,
+        }
+    }
+
+    // Focus
+    fn get_tab_index(&self) -> Option<i32> {
+        self.tab_index.filter(|_| !self.interaction_state.is_disabled)
+    }
+
+    fn set_focused(&mut self, is_focused: bool) {
+        if self.interaction_state.is_focused == is_focused {
+            return;
+        }
+        self.interaction_state.is_focused = is_focused;
+        self.apply_interaction_state();
+    }
+
+    // Pointer interaction
+    fn set_hovered(&mut self, is_hovered: bool) {
+        if self.interaction_state.is_hovered == is_hovered {
+            return;
+        }
+        self.interaction_state.is_hovered = is_hovered;
+        self.apply_interaction_state();
+    }
+
+    fn set_active(&mut self, is_active: bool) {
+        if self.interaction_state.is_active == is_active {
+            return;
+        }
+        self.interaction_state.is_active = is_active;
+        self.apply_interaction_state();
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.interaction_state.is_disabled
+    }
+
+    // Form controls
+    fn get_form_control_state(&self) -> Option<&FormControlState> {
+        Some(&self.control_state)
+    }
+
+    fn inherit_form_control_state(&mut self, previous_state: &FormControlState) {
+        self.control_state.inherit(previous_state);
+        self.render_cache.invalidate();
+    }
+
+    // Animation
+    fn needs_animation_frame(&self) -> bool {
+        self.style_animator.is_animating()
+    }
+
+    fn get_style_animator(&self) -> Option<&StyleAnimator> {
+        Some(&self.style_animator)
+    }
+
+    fn inherit_style_animator(&mut self, previous_animator: &StyleAnimator, previous_styles: &Styles) {
+        if let Some(styles) = self.style_animator.inherit(previous_animator, previous_styles) {
+            self.apply_animated_styles(styles);
+        }
+    }
+
+    // Rendering cache
+    fn needs_repaint(&self) -> bool {
+        self.render_cache.needs_repaint()
+    }
+
+    // Includes the popup while open, so that opening and closing it damages the area below
+    fn get_visual_bounds(&self) -> Rect {
+        let mut visual_bounds = Rect::from_xywh(self.position.x, self.position.y, self.size.width, self.size.height);
+        visual_bounds = ElementRenderer::transform_bounds(visual_bounds, self.position, self.size, &self.styles);
+        if self.is_open {
+            visual_bounds.join(self.get_popup_bounds());
+        }
+
+        visual_bounds
+    }
+
+    fn collect_damage(&self, damaged_rects: &mut Vec<Rect>) {
+        if self.render_cache.needs_repaint() {
+            self.render_cache.collect_own_damage(self.get_visual_bounds(), damaged_rects);
+        }
+    }
+
+    fn set_id(&mut self, id: String) {
+        self._id = id;
+    }
+
+    fn set_position(&mut self, position: Position) {
+        if self.position != position {
+            self.render_cache.invalidate();
+        }
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Size) {
+        if self.size != size {
+            self.render_cache.invalidate();
+        }
+        self.size = size;
+    }
+
+    fn set_styles(&mut self, styles: Styles) {
+        self.base_styles = styles;
+        let styles = self.pseudo_class_styles.resolve(styles, &self.interaction_state);
+        self.styles = self.style_animator.set_styles(&self.styles, styles);
+        self.layout_cache.mark_dirty();
+        self.render_cache.invalidate();
+    }
+
+    fn add_child(&mut self, _: Box<dyn Element>) {}
+
+    fn get_id(&self) -> String {
+        self._id.clone()
+    }
+
+    fn get_template_id(&self) -> Option<&String> {
+        self.template_id.as_ref()
+    }
+
+    fn get_element_type(&self) -> ElementType {
+        ElementType::Select
+    }
+
+    fn get_name(&self) -> String {
+        "select".to_string()
+    }
+
+    fn get_position(&self) -> Position {
+        self.position
+    }
+
+    fn get_size(&self) -> Size {
+        self.size
+    }
+
+    fn get_styles(&self) -> Styles {
+        self.styles
+    }
+
+    fn get_children_mut(&mut self) -> Option<&mut Vec<Box<dyn Element>>> { None }
+
+    // Component system
+    fn get_component_interface(&mut self) -> Option<&mut dyn ComponentInterface> {
+        None
+    }
+
+    fn initialize(&mut self, _: HashMap<String, Box<dyn Any>>) {
+        // Nothing for now (implemented for components only)
+    }
+
+    fn handle_route_change(&mut self, _: &String, _: &String) {
+        // Nothing for now (implemented for components only)
+    }
+
+    // Layout system
+    fn set_natural_size(&mut self, size: Size) {
+        self.natural_size = size;
+    }
+
+    fn set_requested_size(&mut self, requested_size: OptionalSize) {
+        self.requested_size = requested_size;
+    }
+
+    fn get_natural_size(&self) -> Size {
+        self.natural_size
+    }
+
+    fn get_requested_size(&self) -> OptionalSize {
+        self.requested_size
+    }
+
+    fn get_effective_size(&self) -> Size {
+        effective_size_estimator::estimate_effective_size(&self.get_requested_size(), &self.get_natural_size())
+    }
+
+    fn is_text_wrapper(&self) -> bool { false }
+
+    // Wide enough for the longest label, as in browsers
+    fn estimate_sizes(&mut self) {
+        if !self.layout_cache.needs_estimation() {
+            if let Some((natural_size, requested_size)) = self.layout_cache.get_estimated_sizes() {
+                self.natural_size = natural_size;
+                self.requested_size = requested_size;
+            }
+            return;
+        }
+
+        self.update_line_metrics();
+
+        let label_width = self.options.iter()
+            .map(|option| skia_boundary::estimate_text_size(
+                &option.label,
+                self.get_font_size(),
+                self.styles.font_weight.unwrap_or_default(),
+                self.styles.font_family.unwrap_or_default(),
+                self.styles.font_style.unwrap_or_default(),
+            ).width)
+            .fold(0.0, f32::max);
+
+        let border = self.styles.border.unwrap_or_default();
+        let padding = self.styles.padding.unwrap_or_default();
+        self.set_natural_size(Size {
+            width: label_width + ARROW_WIDTH + 2.0 * ARROW_MARGIN + border.horizontal() + padding.horizontal(),
+            height: self.line_metrics.1 + border.vertical() + padding.vertical(),
+        });
+
+        let sizing_policy = self.get_styles().sizing_policy.unwrap_or_default();
+        self.set_requested_size(OptionalSize { width: sizing_policy.width, height: sizing_policy.height });
+
+        self.layout_cache.store_estimated_sizes(self.natural_size, self.requested_size);
+    }
+
+    fn allocate_space(&mut self, allocated_position: Position, allocated_size: Size) {
+        if self.layout_cache.get_allocation_action(allocated_position, allocated_size) != AllocationAction::Full {
+            return;
+        }
+
+        self.position = allocated_position;
+        self.size = allocated_size;
+
+        self.layout_cache.store_allocation(allocated_position, allocated_size);
+    }
+
+    fn layout(&mut self, allocated_position: Position, allocated_size: Size) {
+        self.estimate_sizes();
+        self.allocate_space(allocated_position, allocated_size);
+    }
+
+    // Incremental layout
+    fn needs_layout(&self) -> bool {
+        self.layout_cache.needs_layout()
+    }
+
+    fn mark_layout_dirty(&mut self) {
+        self.layout_cache.mark_dirty();
+    }
+
+    fn has_estimate_changed(&self) -> bool {
+        self.layout_cache.has_estimate_changed()
+    }
+
+    fn allocate_pending_space(&mut self) {
+        if let Some((allocated_position, allocated_size)) = self.layout_cache.get_allocation() {
+            self.allocate_space(allocated_position, allocated_size);
+        }
+    }
+
+    fn react_to_state_change(&mut self, _component_id: String) {}
+}
@@ -0,0 +1,38 @@
+use super::element::{Element, ElementType};
+
+
+/*
+ * Carries the state of a replaced element tree (eg. on template reload) over to the new one, pairing elements by position.
+ * Elements are only paired while their types and names match. Paired elements keep their IDs,
+ * so that focus survives the reload; custom components keep theirs, as state changes are routed by them.
+ * Scroll positions are kept too, eg. so that a list doesn't jump back to the top when items are loaded.
+ */
+pub fn transfer_element_state(previous_element: &mut dyn Element, element: &mut dyn Element) {
+    if previous_element.get_element_type() != element.get_element_type() || previous_element.get_name() != element.get_name() {
+        return;
+    }
+
+    if element.get_element_type() != ElementType::CustomComponent {
+        element.set_id(previous_element.get_id());
+    }
+
+    let previous_styles = previous_element.get_styles();
+    if let Some(previous_animator) = previous_element.get_style_animator() {
+        element.inherit_style_animator(previous_animator, &previous_styles);
+    }
+    if let Some(previous_editing_state) = previous_element.get_text_editing_state() {
+        element.inherit_text_editing_state(previous_editing_state);
+    }
+    if let Some(previous_control_state) = previous_element.get_form_control_state() {
+        element.inherit_form_control_state(previous_control_state);
+    }
+    if let Some(previous_scrollbar_state) = previous_element.get_scrollbar_state() {
+        element.inherit_scrollbar_state(previous_scrollbar_state);
+    }
+
+    if let (Some(previous_children), Some(children)) = (previous_element.get_children_mut(), element.get_children_mut()) {
+        for (previous_child, child) in previous_children.iter_mut().zip(children.iter_mut()) {
+            transfer_element_state(previous_child.as_mut(), child.as_mut());
+        }
+    }
+}
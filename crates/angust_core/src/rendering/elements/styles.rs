@@ -1,7 +1,7 @@
 use skia_safe::Color;
 
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Styles {
     pub display: Option<DisplayType>,
     pub flex_direction: Option<FlexDirection>,
@@ -20,6 +20,7 @@ pub struct Styles {
 
     pub border: Option<Border>,
     pub background_color: Option<Color>,
+    pub box_shadows: Option<BoxShadows>,
 
     pub text_color: Option<Color>,
     pub white_space: Option<WhiteSpace>,
@@ -27,6 +28,15 @@ pub struct Styles {
     pub font_family: Option<FontFamily>,
     pub font_weight: Option<FontWeight>,
     pub font_style: Option<FontStyle>,
+
+    pub cursor: Option<Cursor>,
+
+    pub opacity: Option<f32>,
+    pub filters: Option<Filters>,
+    pub backdrop_filters: Option<Filters>,
+    pub transform: Option<Transform>,
+    pub transitions: Option<Transitions>,
+    pub animation: Option<Animation>,
 }
 
 impl Default for Styles {
@@ -47,6 +57,7 @@ impl Default for Styles {
             spacing: Some(Spacing::default()),
             background_color: Some(Color::TRANSPARENT),
             border: Some(Border::default()),
+            box_shadows: None,
             // Cascading properties
             text_color: None,
             white_space: None,
@@ -54,12 +65,20 @@ impl Default for Styles {
             font_family: None,
             font_weight: None,
             font_style: None,
+            cursor: None,
+            // Animation properties
+            opacity: Some(1.0),
+            filters: None,
+            backdrop_filters: None,
+            transform: None,
+            transitions: None,
+            animation: None,
         }
     }
 }
 
 // Layout properties
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum DisplayType {
     Block,          // The element takes up the full width of its container, starting on a new line.
     InlineBlock,    // The element does not start on a new line but can have width and height set.
@@ -112,7 +131,7 @@ impl Default for JustifyContent {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum AlignItems {
     FlexStart,      // Items are aligned at the start of the cross axis.
     FlexEnd,        // Items are aligned at the end of the cross axis.
@@ -127,7 +146,7 @@ impl Default for AlignItems {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum AlignContent {
     FlexStart,      // Lines are packed at the start of the container.
     FlexEnd,        // Lines are packed at the end of the container.
@@ -159,7 +178,7 @@ impl Default for Overflow {
 }
 
 // Dimension properties
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct SizingPolicy {
     pub width: Option<Dimension>,
     pub height: Option<Dimension>,
@@ -182,7 +201,7 @@ impl Default for SizingPolicy {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Dimension {
     pub value: f32,
     pub unit: Unit,
@@ -212,7 +231,7 @@ impl Default for Unit {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Spacing {
     pub spacing_x: Dimension,
     pub spacing_y: Dimension,
@@ -227,7 +246,7 @@ impl Default for Spacing {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Margin {
     pub top: Dimension,
     pub right: Dimension,
@@ -256,7 +275,7 @@ impl Margin {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Padding {
     pub top: Dimension,
     pub right: Dimension,
@@ -286,24 +305,101 @@ impl Padding {
 }
 
 // Appearance properties
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Border {
-    pub width: Dimension,
-    pub color: Color,
+    pub top: BorderSide,
+    pub right: BorderSide,
+    pub bottom: BorderSide,
+    pub left: BorderSide,
     pub radius: BorderRadius,
 }
 
 impl Default for Border {
     fn default() -> Self {
         Self {
-            width: Dimension::default(),
-            color: Color::BLACK,
+            top: BorderSide::default(),
+            right: BorderSide::default(),
+            bottom: BorderSide::default(),
+            left: BorderSide::default(),
             radius: BorderRadius::default(),
         }
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+impl Border {
+    // Same width, color and style on all four sides
+    pub fn uniform(side: BorderSide) -> Self {
+        Self {
+            top: side,
+            right: side,
+            bottom: side,
+            left: side,
+            radius: BorderRadius::default(),
+        }
+    }
+
+    // In top, right, bottom, left order
+    pub fn get_sides(&self) -> [BorderSide; 4] {
+        [self.top, self.right, self.bottom, self.left]
+    }
+
+    pub fn get_sides_mut(&mut self) -> [&mut BorderSide; 4] {
+        [&mut self.top, &mut self.right, &mut self.bottom, &mut self.left]
+    }
+
+    pub fn is_uniform(&self) -> bool {
+        self.get_sides().iter().all(|side| *side == self.top)
+    }
+
+    pub fn horizontal(&self) -> f32 {
+        self.left.get_width() + self.right.get_width()
+    }
+
+    pub fn vertical(&self) -> f32 {
+        self.top.get_width() + self.bottom.get_width()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BorderSide {
+    pub width: Dimension,
+    pub color: Option<Color>, // None for the element's text color (currentColor)
+    pub style: BorderStyle,
+}
+
+impl Default for BorderSide {
+    fn default() -> Self {
+        Self {
+            width: Dimension::default(),
+            color: None,
+            style: BorderStyle::default(),
+        }
+    }
+}
+
+impl BorderSide {
+    // Width taken up by the side, none when its style hides it (as in CSS)
+    pub fn get_width(&self) -> f32 {
+        if self.style == BorderStyle::None { 0.0 } else { self.width.value }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BorderStyle {
+    None,
+    Solid,
+    Dashed,
+    Dotted,
+}
+
+// Solid rather than none (as in CSS), so that a border-width alone draws a border
+impl Default for BorderStyle {
+    fn default() -> Self {
+        Self::Solid
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct BorderRadius {
     pub top_left: Dimension,
     pub top_right: Dimension,
@@ -322,6 +418,44 @@ impl Default for BorderRadius {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoxShadow {
+    pub offset_x: Dimension,
+    pub offset_y: Dimension,
+    pub blur_radius: Dimension,
+    pub spread_radius: Dimension,
+    pub color: Option<Color>, // None for the element's text color (currentColor)
+    pub inset: bool,
+}
+
+impl Default for BoxShadow {
+    fn default() -> Self {
+        Self {
+            offset_x: Dimension::default(),
+            offset_y: Dimension::default(),
+            blur_radius: Dimension::default(),
+            spread_radius: Dimension::default(),
+            color: None,
+            inset: false,
+        }
+    }
+}
+
+pub const MAX_BOX_SHADOWS: usize = 4;
+
+pub type BoxShadows = FixedList<BoxShadow, MAX_BOX_SHADOWS>;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Filter {
+    Blur(Dimension), // Standard deviation of the Gaussian blur
+    Grayscale(f32), // From 0 (unchanged) to 1 (fully gray)
+    Brightness(f32), // 1 being unchanged
+}
+
+pub const MAX_FILTERS: usize = 4;
+
+pub type Filters = FixedList<Filter, MAX_FILTERS>;
+
 #[derive(Clone, Copy, Debug)]
 pub struct Directions {
     pub horizontal: bool,
@@ -353,7 +487,7 @@ impl Default for WhiteSpace {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum FontFamily {
     Arial,
     Helvetica,
@@ -380,7 +514,7 @@ impl FontFamily {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum FontWeight {
     FW100,
     FW200,
@@ -415,7 +549,7 @@ impl FontWeight {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum FontStyle {
     Normal,
     Italic,
@@ -438,3 +572,253 @@ impl FontStyle {
     }
 }
 
+// Interaction properties
+// Shape of the mouse cursor over the element, named as in CSS (eg. Cursor::EwResize for "ew-resize")
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Cursor {
+    Default,
+    Pointer,
+    Text,
+    Move,
+    Grab,
+    Grabbing,
+    NotAllowed,
+    Wait,
+    Progress,
+    Help,
+    Crosshair,
+    EwResize,
+    NsResize,
+    NeswResize,
+    NwseResize,
+    ColResize,
+    RowResize,
+    ZoomIn,
+    ZoomOut,
+}
+
+impl Default for Cursor {
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
+// Animation properties
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    pub translate_x: Dimension,
+    pub translate_y: Dimension,
+    pub scale_x: f32,
+    pub scale_y: f32,
+    pub rotate: f32, // Degrees, clockwise
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self {
+            translate_x: Dimension::default(),
+            translate_y: Dimension::default(),
+            scale_x: 1.0,
+            scale_y: 1.0,
+            rotate: 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnimatableProperty {
+    BackgroundColor,
+    Color,
+    BorderColor,
+    BorderWidth,
+    Width,
+    Height,
+    MinWidth,
+    MaxWidth,
+    MinHeight,
+    MaxHeight,
+    Opacity,
+    Transform,
+}
+
+impl AnimatableProperty {
+    pub const ALL: [AnimatableProperty; 12] = [
+        Self::BackgroundColor, Self::Color, Self::BorderColor, Self::BorderWidth,
+        Self::Width, Self::Height, Self::MinWidth, Self::MaxWidth, Self::MinHeight, Self::MaxHeight,
+        Self::Opacity, Self::Transform,
+    ];
+
+    pub fn from_css_name(name: &str) -> Option<Self> {
+        match name {
+            "background-color" => Some(Self::BackgroundColor),
+            "color" => Some(Self::Color),
+            "border-color" => Some(Self::BorderColor),
+            "border-width" => Some(Self::BorderWidth),
+            "width" => Some(Self::Width),
+            "height" => Some(Self::Height),
+            "min-width" => Some(Self::MinWidth),
+            "max-width" => Some(Self::MaxWidth),
+            "min-height" => Some(Self::MinHeight),
+            "max-height" => Some(Self::MaxHeight),
+            "opacity" => Some(Self::Opacity),
+            "transform" => Some(Self::Transform),
+            _ => None,
+        }
+    }
+
+    // Whether animating the property requires the element to be laid out again
+    pub fn affects_layout(&self) -> bool {
+        matches!(self, Self::Width | Self::Height | Self::MinWidth | Self::MaxWidth | Self::MinHeight | Self::MaxHeight)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransitionProperty {
+    All,
+    Property(AnimatableProperty),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transition {
+    pub property: TransitionProperty,
+    pub duration: f32, // Seconds
+    pub timing_function: TimingFunction,
+    pub delay: f32, // Seconds
+}
+
+impl Default for Transition {
+    fn default() -> Self {
+        Self {
+            property: TransitionProperty::All,
+            duration: 0.0,
+            timing_function: TimingFunction::default(),
+            delay: 0.0,
+        }
+    }
+}
+
+pub const MAX_TRANSITIONS: usize = 8;
+
+pub type Transitions = FixedList<Transition, MAX_TRANSITIONS>;
+
+impl Transitions {
+    // As in CSS, the last matching transition wins
+    pub fn get(&self, property: AnimatableProperty) -> Option<&Transition> {
+        self.iter()
+            .filter(|transition| match transition.property {
+                TransitionProperty::All => true,
+                TransitionProperty::Property(transition_property) => transition_property == property,
+            })
+            .last()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Animation {
+    pub keyframes_id: usize, // See keyframes_registry
+    pub duration: f32, // Seconds
+    pub timing_function: TimingFunction,
+    pub delay: f32, // Seconds
+    pub iteration_count: IterationCount,
+    pub direction: AnimationDirection,
+    pub fill_mode: AnimationFillMode,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TimingFunction {
+    Linear,
+    Ease,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    CubicBezier(f32, f32, f32, f32),
+    Steps(u32),
+}
+
+impl Default for TimingFunction {
+    fn default() -> Self {
+        Self::Ease
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IterationCount {
+    Finite(f32),
+    Infinite,
+}
+
+impl Default for IterationCount {
+    fn default() -> Self {
+        Self::Finite(1.0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnimationDirection {
+    Normal,
+    Reverse,
+    Alternate,
+    AlternateReverse,
+}
+
+impl Default for AnimationDirection {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnimationFillMode {
+    None,
+    Forwards,
+    Backwards,
+    Both,
+}
+
+impl Default for AnimationFillMode {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+// Utils
+/*
+ * List of at most N items, stored inline so that Styles stays Copy.
+ * Pushing to a full list fails, leaving it unchanged (the parsers then reject the whole value).
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FixedList<T: Copy, const N: usize> {
+    items: [Option<T>; N],
+}
+
+impl<T: Copy, const N: usize> Default for FixedList<T, N> {
+    fn default() -> Self {
+        Self {
+            items: [None; N],
+        }
+    }
+}
+
+impl<T: Copy, const N: usize> FixedList<T, N> {
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        match self.items.iter_mut().find(|slot| slot.is_none()) {
+            Some(slot) => {
+                *slot = Some(item);
+                Ok(())
+            },
+            None => Err(item),
+        }
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.items.iter().flatten()
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items[0].is_none()
+    }
+}
@@ -1,14 +1,15 @@
 use std::{any::Any, collections::HashMap};
 
-use skia_safe::{Canvas, Color, Point};
+use skia_safe::{Canvas, Color, Point, Rect};
 
 use crate::rendering::{
-    layout::size_estimation_system::text_size_estimator, 
-    rendering_interface::element_renderer::ElementRenderer
+    animation::{interpolation, style_animator::StyleAnimator}, 
+    layout::{layout_cache::{AllocationAction, LayoutCache}, size_estimation_system::text_size_estimator}, 
+    rendering_interface::{element_renderer::ElementRenderer, render_cache::RenderCache}
 };
 
 use super::{
-    button::EventPropagationData, common_types::{OptionalSize, Position, Size}, component::component::ComponentInterface, element::{Element, ElementType, EventType}, element_id_generator::ElementIDGenerator, event_propagator, styles::{Dimension, Styles, Unit, WhiteSpace}
+    button::EventPropagationData, common_types::{OptionalSize, Position, Size}, component::component::ComponentInterface, element::{Element, ElementType, EventType}, element_id_generator::ElementIDGenerator, event_propagator, mouse_event::{EventPhase, MouseEvent}, styles::{Dimension, Styles, Unit, WhiteSpace}
 };
 
 
@@ -20,6 +21,9 @@ pub struct Text {
     size: Size,
     styles: Styles,
     natural_size: Size,
+    layout_cache: LayoutCache,
+    render_cache: RenderCache,
+    style_animator: StyleAnimator,
 }
 
 impl Text {
@@ -33,26 +37,44 @@ impl Text {
             size: Size::default(),
             styles: Styles::default(),
             natural_size: Size::default(),
+            layout_cache: LayoutCache::default(),
+            render_cache: RenderCache::default(),
+            style_animator: StyleAnimator::default(),
         }
     }
 
     pub fn set_styles(&mut self, styles: Styles) -> &mut Self {
-        self.styles = styles;
+        self.styles = self.style_animator.set_styles(&self.styles, styles);
+        self.layout_cache.mark_dirty();
+        self.render_cache.invalidate();
         self
     }
 
     pub fn set_content(&mut self, content: String) -> &mut Self {
         self.content = content;
+        self.layout_cache.mark_dirty();
+        self.render_cache.invalidate();
         self
     }
 
     pub fn get_content(&self) -> String {
         self.content.clone()
     }
+
+    // Displays the styles computed by the style animator on this frame
+    fn apply_animated_styles(&mut self, styles: Styles) {
+        if interpolation::affects_layout(&self.styles, &styles) {
+            self.layout_cache.mark_dirty();
+        }
+        self.styles = styles;
+        self.render_cache.invalidate();
+    }
 }
 
 impl Element for Text {
     fn render(&self, canvas: &Canvas) {
+        let save_count = ElementRenderer::begin_effects(canvas, self.position, self.size, &self.styles);
+
         ElementRenderer::render_multi_line_text(
             canvas, 
             self.get_position(), 
@@ -63,9 +85,16 @@ impl Element for Text {
             self.get_styles().font_family.unwrap_or_default(),
             self.get_styles().font_style.unwrap_or_default(),
         );
+
+        ElementRenderer::end_effects(canvas, save_count);
+        self.render_cache.mark_painted(self.get_visual_bounds());
     }
 
-    fn update(&mut self) {}
+    fn update(&mut self, dt: f32) {
+        if let Some(styles) = self.style_animator.update(dt) {
+            self.apply_animated_styles(styles);
+        }
+    }
 
     fn handle_event(&mut self, _: Point, _: &EventType) {}
     
@@ -73,20 +102,73 @@ impl Element for Text {
         event_propagator::propagate_event(self, cursor_position, event_type)
     }
 
+    fn propagate_targeted_event(&mut self, target_id: Option<&String>, event_type: &EventType) -> Option<Vec<EventPropagationData>> {
+        event_propagator::propagate_targeted_event(self, target_id, event_type)
+    }
+
+    fn dispatch_mouse_event(&mut self, path: &[String], mouse_event: &MouseEvent, phase: EventPhase) -> Vec<EventPropagationData> {
+        event_propagator::dispatch_mouse_event(self, path, mouse_event, phase, Vec::new())
+    }
+
+    // Animation
+    fn needs_animation_frame(&self) -> bool {
+        self.style_animator.is_animating()
+    }
+
+    fn get_style_animator(&self) -> Option<&StyleAnimator> {
+        Some(&self.style_animator)
+    }
+
+    fn inherit_style_animator(&mut self, previous_animator: &StyleAnimator, previous_styles: &Styles) {
+        if let Some(styles) = self.style_animator.inherit(previous_animator, previous_styles) {
+            self.apply_animated_styles(styles);
+        }
+    }
+
+    // Rendering cache
+    fn needs_repaint(&self) -> bool {
+        self.render_cache.needs_repaint()
+    }
+
+    // The position is the baseline of the first line; lines may overflow the allocated size
+    fn get_visual_bounds(&self) -> Rect {
+        let line_height = self.get_styles().font_size.unwrap_or(Dimension { value: 16.0, unit: Unit::Px }).value;
+        let width = self.size.width.max(self.natural_size.width);
+        let height = self.size.height.max(line_height * self.lines.len() as f32);
+
+        let visual_bounds = Rect::from_xywh(self.position.x, self.position.y - line_height, width, height + line_height / 2.0);
+
+        ElementRenderer::transform_bounds(visual_bounds, self.position, self.size, &self.styles)
+    }
+
+    fn collect_damage(&self, damaged_rects: &mut Vec<Rect>) {
+        if self.render_cache.needs_repaint() {
+            self.render_cache.collect_own_damage(self.get_visual_bounds(), damaged_rects);
+        }
+    }
+
     fn set_id(&mut self, id: String) {
         self._id = id;
     }
 
     fn set_position(&mut self, position: Position) {
+        if self.position != position {
+            self.render_cache.invalidate();
+        }
         self.position = position;
     }
 
     fn set_size(&mut self, size: Size) {
+        if self.size != size {
+            self.render_cache.invalidate();
+        }
         self.size = size;
     }
 
     fn set_styles(&mut self, styles: Styles) {
-        self.styles = styles;
+        self.styles = self.style_animator.set_styles(&self.styles, styles);
+        self.layout_cache.mark_dirty();
+        self.render_cache.invalidate();
     }
     
     fn add_child(&mut self, _: Box<dyn Element>) {}
@@ -148,12 +230,26 @@ impl Element for Text {
     fn is_text_wrapper(&self) -> bool { false }
 
     fn estimate_sizes(&mut self) {
+        if !self.layout_cache.needs_estimation() {
+            if let Some((natural_size, _)) = self.layout_cache.get_estimated_sizes() {
+                self.natural_size = natural_size;
+            }
+            return;
+        }
+
         let estimated_text_size = text_size_estimator::estimate_text_element_size(self);
         self.set_natural_size(estimated_text_size);
+
+        self.layout_cache.store_estimated_sizes(self.natural_size, OptionalSize::default());
     }
 
     fn allocate_space(&mut self, allocated_position: Position, allocated_size: Size) {
+        if self.layout_cache.get_allocation_action(allocated_position, allocated_size) != AllocationAction::Full {
+            return;
+        }
+
         let line_height = self.get_styles().font_size.unwrap_or(Dimension { value: 16.0, unit: Unit::Px }).value;
+        self.render_cache.invalidate();
         
         self.position = {
             let x = allocated_position.x;
@@ -167,6 +263,8 @@ impl Element for Text {
         } else {
             self.lines = vec![self.content.clone()];
         }
+
+        self.layout_cache.store_allocation(allocated_position, allocated_size);
     }
     
     fn layout(&mut self, allocated_position: Position, allocated_size: Size) {
@@ -174,5 +272,24 @@ impl Element for Text {
         self.allocate_space(allocated_position, allocated_size);
     }
 
+    // Incremental layout
+    fn needs_layout(&self) -> bool {
+        self.layout_cache.needs_layout()
+    }
+
+    fn mark_layout_dirty(&mut self) {
+        self.layout_cache.mark_dirty();
+    }
+
+    fn has_estimate_changed(&self) -> bool {
+        self.layout_cache.has_estimate_changed()
+    }
+
+    fn allocate_pending_space(&mut self) {
+        if let Some((allocated_position, allocated_size)) = self.layout_cache.get_allocation() {
+            self.allocate_space(allocated_position, allocated_size);
+        }
+    }
+
     fn react_to_state_change(&mut self, _component_id: String) {}
 }
\ No newline at end of file
@@ -0,0 +1,340 @@
+use super::element::KeyboardEvent;
+
+
+/*
+ * Value and selection of an editable text (inputs and text areas), edited through keyboard events.
+ * Positions are indices of characters (not bytes); the caret sits before the character at its index.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextEditingModel {
+    value: String,
+    selection: TextSelection,
+}
+
+// The anchor is where the selection started, the caret where it ends; they are equal when nothing is selected
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextSelection {
+    pub anchor: usize,
+    pub caret: usize,
+}
+
+impl TextSelection {
+    pub fn collapsed(index: usize) -> Self {
+        Self { anchor: index, caret: index }
+    }
+
+    pub fn is_collapsed(&self) -> bool {
+        self.anchor == self.caret
+    }
+
+    pub fn get_range(&self) -> (usize, usize) {
+        (self.anchor.min(self.caret), self.anchor.max(self.caret))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CaretMovement {
+    Left,
+    Right,
+    WordLeft,
+    WordRight,
+    LineStart,
+    LineEnd,
+    TextStart,
+    TextEnd,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EditResult {
+    Ignored,            // The key has no editing meaning
+    SelectionChanged,   // The caret moved or the selection changed
+    ValueChanged,
+}
+
+impl TextEditingModel {
+    pub fn new(value: String) -> Self {
+        let char_count = value.chars().count();
+        Self {
+            value,
+            selection: TextSelection::collapsed(char_count),
+        }
+    }
+
+    // Getters and setters
+    pub fn get_value(&self) -> &String {
+        &self.value
+    }
+
+    // Replaces the value (eg. from the component state), keeping the selection where possible
+    pub fn set_value(&mut self, value: String) {
+        self.value = value;
+        let char_count = self.get_char_count();
+        self.selection.anchor = self.selection.anchor.min(char_count);
+        self.selection.caret = self.selection.caret.min(char_count);
+    }
+
+    pub fn get_selection(&self) -> TextSelection {
+        self.selection
+    }
+
+    pub fn get_selected_text(&self) -> String {
+        let (start, end) = self.selection.get_range();
+        self.value.chars().skip(start).take(end - start).collect()
+    }
+
+    pub fn get_char_count(&self) -> usize {
+        self.value.chars().count()
+    }
+
+    pub fn set_caret(&mut self, index: usize, extend_selection: bool) {
+        self.selection.caret = index.min(self.get_char_count());
+        if !extend_selection {
+            self.selection.anchor = self.selection.caret;
+        }
+    }
+
+    pub fn select_all(&mut self) {
+        self.selection = TextSelection { anchor: 0, caret: self.get_char_count() };
+    }
+
+    // Editing
+    pub fn insert_text(&mut self, text: &str) {
+        let (start, end) = self.selection.get_range();
+        let start_byte = self.to_byte_index(start);
+        let end_byte = self.to_byte_index(end);
+
+        self.value.replace_range(start_byte..end_byte, text);
+        self.selection = TextSelection::collapsed(start + text.chars().count());
+    }
+
+    pub fn delete_backward(&mut self, by_word: bool) -> bool {
+        if self.selection.is_collapsed() {
+            let caret = self.selection.caret;
+            let start = if by_word { self.find_word_start(caret) } else { caret.saturating_sub(1) };
+            self.selection.anchor = start;
+        }
+        self.delete_selection()
+    }
+
+    pub fn delete_forward(&mut self, by_word: bool) -> bool {
+        if self.selection.is_collapsed() {
+            let caret = self.selection.caret;
+            let end = if by_word { self.find_word_end(caret) } else { (caret + 1).min(self.get_char_count()) };
+            self.selection.anchor = end;
+        }
+        self.delete_selection()
+    }
+
+    fn delete_selection(&mut self) -> bool {
+        if self.selection.is_collapsed() {
+            return false;
+        }
+
+        self.insert_text("");
+        true
+    }
+
+    // Caret movement (lines are separated by '\n'; moving between visual lines is up to the element)
+    pub fn move_caret(&mut self, movement: CaretMovement, extend_selection: bool) {
+        let (start, end) = self.selection.get_range();
+        let caret = self.selection.caret;
+
+        let index = match movement {
+            // Without shift, an arrow collapses the selection to its edge
+            CaretMovement::Left if !extend_selection && !self.selection.is_collapsed() => start,
+            CaretMovement::Right if !extend_selection && !self.selection.is_collapsed() => end,
+            CaretMovement::Left => caret.saturating_sub(1),
+            CaretMovement::Right => caret + 1,
+            CaretMovement::WordLeft => self.find_word_start(caret),
+            CaretMovement::WordRight => self.find_word_end(caret),
+            CaretMovement::LineStart => self.find_line_start(caret),
+            CaretMovement::LineEnd => self.find_line_end(caret),
+            CaretMovement::TextStart => 0,
+            CaretMovement::TextEnd => self.get_char_count(),
+        };
+
+        self.set_caret(index, extend_selection);
+    }
+
+    /*
+     * Applies the default action of a key press: typing, deleting, moving the caret and selecting (Shift),
+     * by words with Ctrl (Alt on macOS), select all with Ctrl/Cmd+A. Enter inserts a line break only if multi_line.
+     */
+    pub fn handle_key_down(&mut self, keyboard_event: &KeyboardEvent, multi_line: bool) -> EditResult {
+        let modifiers = keyboard_event.modifiers;
+        let by_word = if cfg!(target_os = "macos") { modifiers.alt } else { modifiers.ctrl };
+        let is_shortcut = modifiers.ctrl || modifiers.meta;
+        let previous_model = self.clone();
+
+        match keyboard_event.key.as_str() {
+            "Backspace" => { self.delete_backward(by_word); },
+            "Delete" => { self.delete_forward(by_word); },
+            "ArrowLeft" => self.move_caret(if by_word { CaretMovement::WordLeft } else { CaretMovement::Left }, modifiers.shift),
+            "ArrowRight" => self.move_caret(if by_word { CaretMovement::WordRight } else { CaretMovement::Right }, modifiers.shift),
+            "Home" => self.move_caret(if modifiers.ctrl { CaretMovement::TextStart } else { CaretMovement::LineStart }, modifiers.shift),
+            "End" => self.move_caret(if modifiers.ctrl { CaretMovement::TextEnd } else { CaretMovement::LineEnd }, modifiers.shift),
+            "Enter" if multi_line => self.insert_text("\n"),
+            "a" | "A" if is_shortcut => self.select_all(),
+            _ => {
+                let typed_text = match &keyboard_event.text {
+                    Some(text) if !is_shortcut => text.chars().filter(|character| !character.is_control()).collect::<String>(),
+                    _ => String::new(),
+                };
+                if typed_text.is_empty() {
+                    return EditResult::Ignored;
+                }
+                self.insert_text(&typed_text);
+            }
+        }
+
+        if self.value != previous_model.value {
+            EditResult::ValueChanged
+        } else if self.selection != previous_model.selection {
+            EditResult::SelectionChanged
+        } else {
+            EditResult::Ignored
+        }
+    }
+
+    // Utils
+    fn to_byte_index(&self, char_index: usize) -> usize {
+        self.value.char_indices().nth(char_index).map_or(self.value.len(), |(byte_index, _)| byte_index)
+    }
+
+    fn get_chars(&self) -> Vec<char> {
+        self.value.chars().collect()
+    }
+
+    // Skips the whitespace before the caret, then the word
+    fn find_word_start(&self, index: usize) -> usize {
+        let chars = self.get_chars();
+        let mut index = index.min(chars.len());
+        while index > 0 && chars[index - 1].is_whitespace() {
+            index -= 1;
+        }
+        while index > 0 && !chars[index - 1].is_whitespace() {
+            index -= 1;
+        }
+        index
+    }
+
+    fn find_word_end(&self, index: usize) -> usize {
+        let chars = self.get_chars();
+        let mut index = index.min(chars.len());
+        while index < chars.len() && chars[index].is_whitespace() {
+            index += 1;
+        }
+        while index < chars.len() && !chars[index].is_whitespace() {
+            index += 1;
+        }
+        index
+    }
+
+    fn find_line_start(&self, index: usize) -> usize {
+        let chars = self.get_chars();
+        let mut index = index.min(chars.len());
+        while index > 0 && chars[index - 1] != '\n' {
+            index -= 1;
+        }
+        index
+    }
+
+    fn find_line_end(&self, index: usize) -> usize {
+        let chars = self.get_chars();
+        let mut index = index.min(chars.len());
+        while index < chars.len() && chars[index] != '\n' {
+            index += 1;
+        }
+        index
+    }
+}
+
+/*
+ * Editing state of an input or text area, carried over when the template is reloaded (see state_transfer).
+ * Edits are kept as long as the value given by the template does not change.
+ */
+#[derive(Clone, Debug)]
+pub struct TextEditingState {
+    pub model: TextEditingModel,
+    pub template_value: String,
+    pub value_on_focus: Option<String>, // To emit a change event on blur if the value differs
+    pub scroll_offset: f32,
+}
+
+impl TextEditingState {
+    pub fn new(value: String) -> Self {
+        Self {
+            model: TextEditingModel::new(value.clone()),
+            template_value: value,
+            value_on_focus: None,
+            scroll_offset: 0.0,
+        }
+    }
+
+    // Remembers the value when the element gains focus, to tell whether it changed once the edit is committed
+    pub fn begin_editing(&mut self) {
+        if self.value_on_focus.is_none() {
+            self.value_on_focus = Some(self.model.get_value().clone());
+        }
+    }
+
+    // Returns the value to emit in a change event, if it changed since the focus (or the previous commit)
+    pub fn commit_editing(&mut self) -> Option<String> {
+        let value = self.model.get_value().clone();
+        if self.value_on_focus.as_ref().map_or(true, |value_on_focus| *value_on_focus == value) {
+            return None;
+        }
+
+        self.value_on_focus = Some(value.clone());
+        Some(value)
+    }
+
+    pub fn end_editing(&mut self) -> Option<String> {
+        let committed_value = self.commit_editing();
+        self.value_on_focus = None;
+        committed_value
+    }
+
+    pub fn inherit(&mut self, previous_state: &TextEditingState) {
+        let template_value = self.template_value.clone();
+        *self = previous_state.clone();
+
+        if template_value != previous_state.template_value {
+            self.model.set_value(template_value.clone());
+        }
+        self.template_value = template_value;
+    }
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_key_event(key: &str, text: Option<&str>) -> KeyboardEvent {
+        let mut keyboard_event = KeyboardEvent::new(key, "");
+        keyboard_event.text = text.map(|text| text.to_string());
+        keyboard_event
+    }
+
+    #[test]
+    fn test_editing_replaces_selection_and_moves_caret() {
+        // Arrange
+        let mut model = TextEditingModel::new("héllo world".to_string());
+        model.set_caret(0, false);
+        model.move_caret(CaretMovement::WordRight, true);
+
+        // Act
+        let typing_result = model.handle_key_down(&create_key_event("H", Some("H")), false);
+        let backspace_result = model.handle_key_down(&create_key_event("Backspace", None), false);
+        let enter_result = model.handle_key_down(&create_key_event("Enter", None), false);
+
+        // Assert
+        assert_eq!(typing_result, EditResult::ValueChanged);
+        assert_eq!(backspace_result, EditResult::ValueChanged);
+        assert_eq!(enter_result, EditResult::Ignored);
+        assert_eq!(model.get_value(), " world");
+        assert_eq!(model.get_selection(), TextSelection::collapsed(0));
+    }
+}
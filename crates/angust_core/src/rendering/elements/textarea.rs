@@ -0,0 +1,802 @@
+use std::{any::Any, collections::HashMap};
+
+use skia_safe::{Canvas, Color, Point, Rect};
+
+use crate::rendering::{
+    animation::{interpolation, style_animator::StyleAnimator},
+    layout::{layout_cache::{AllocationAction, LayoutCache}, size_estimation_system::effective_size_estimator},
+    rendering_interface::{element_renderer::ElementRenderer, render_cache::RenderCache, skia_boundary}
+};
+
+use super::{
+    button::EventPropagationData,
+    common_types::{OptionalSize, Position, Size},
+    component::component::ComponentInterface,
+    element::{Element, ElementType, EventType},
+    element_id_generator::ElementIDGenerator,
+    event_handlers::EventHandlers,
+    event_propagator,
+    mouse_event::{EventPhase, MouseEvent},
+    input::Input,
+    pseudo_class_styles::{InteractionState, PseudoClassStyles},
+    styles::{Border, Dimension, Styles, Unit},
+    text_editing_model::{EditResult, TextEditingState}
+};
+
+
+const DEFAULT_WIDTH: f32 = 200.0; // Of the content box
+const DEFAULT_ROWS: usize = 2;
+const WHEEL_SCROLL_FACTOR: f32 = 4.0;
+
+// Wrapped line of the value
+struct TextLine {
+    start: usize, // Indices of characters in the value
+    end: usize,
+    caret_offsets: Vec<f32>,
+}
+
+/*
+ * Multi-line text field, wrapping its value to its width. Edited like inputs, with Enter inserting line breaks,
+ * the arrow keys and PageUp/PageDown moving the caret between lines. Scrolls vertically to keep the caret visible,
+ * or with the mouse wheel. Emits input events on every edit and change events when it loses focus.
+ */
+pub struct Textarea {
+    _id: String,
+    pub template_id: Option<String>, // The id attribute, not necessarily unique
+    pub placeholder: String,
+    pub rows: usize, // Visible lines, determining the natural height
+    pub event_handlers: EventHandlers,
+    pub tab_index: Option<i32>,
+    pub pseudo_class_styles: PseudoClassStyles,
+
+    editing_state: TextEditingState,
+    lines: Vec<TextLine>,
+    line_metrics: (f32, f32), // Ascent and line height
+    preferred_caret_x: Option<f32>, // Kept while moving the caret between lines
+    is_selecting: bool, // While the mouse is pressed
+
+    position: Position,
+    size: Size,
+    styles: Styles,
+    natural_size: Size,
+    requested_size: OptionalSize,
+    base_styles: Styles, // Before pseudo-class rules
+    interaction_state: InteractionState,
+    layout_cache: LayoutCache,
+    render_cache: RenderCache,
+    style_animator: StyleAnimator,
+}
+
+impl Textarea {
+    pub fn new(value: String, styles: Option<Styles>) -> Self {
+        let id = ElementIDGenerator::get();
+        let mut style_animator = StyleAnimator::default();
+        let base_styles = styles.unwrap_or_default();
+        let styles = style_animator.set_styles(&Styles::default(), base_styles);
+
+        Self {
+            _id: id,
+            template_id: None,
+            placeholder: String::new(),
+            rows: DEFAULT_ROWS,
+            event_handlers: EventHandlers::default(),
+            tab_index: Some(0), // Text areas are focusable by default
+            pseudo_class_styles: PseudoClassStyles::default(),
+            editing_state: TextEditingState::new(value),
+            lines: Vec::new(),
+            line_metrics: (0.0, 0.0),
+            preferred_caret_x: None,
+            is_selecting: false,
+            position: Position::default(),
+            size: Size::default(),
+            styles,
+            natural_size: Size::default(),
+            requested_size: OptionalSize::default(),
+            base_styles,
+            interaction_state: InteractionState::default(),
+            layout_cache: LayoutCache::default(),
+            render_cache: RenderCache::default(),
+            style_animator,
+        }
+    }
+
+    // Same appearance as inputs
+    pub fn get_default_styles() -> Styles {
+        Input::get_default_styles()
+    }
+
+    pub fn get_value(&self) -> String {
+        self.editing_state.model.get_value().clone()
+    }
+
+    // Default actions
+    /*
+     * Edits the value on key presses, returning the handlers of the input event if the value changed.
+     * Blur commits the edit, returning the handlers of the change event if the value changed since the focus.
+     */
+    fn apply_default_action(&mut self, event_type: &EventType) -> Vec<EventPropagationData> {
+        match event_type {
+            EventType::Focus => {
+                self.editing_state.begin_editing();
+                Vec::new()
+            },
+            EventType::Blur => {
+                let committed_value = self.editing_state.end_editing();
+                committed_value
+                    .map(|value| self.event_handlers.get_propagation_data(&EventType::Change(value)))
+                    .unwrap_or_default()
+            },
+            EventType::KeyDown(keyboard_event) => {
+                let modifiers = keyboard_event.modifiers;
+                let page_rows = self.get_visible_rows() as isize;
+                let line_delta = match keyboard_event.key.as_str() {
+                    "ArrowUp" => Some(-1),
+                    "ArrowDown" => Some(1),
+                    "PageUp" => Some(-page_rows),
+                    "PageDown" => Some(page_rows),
+                    _ => None,
+                };
+                if let Some(line_delta) = line_delta {
+                    self.move_caret_vertically(line_delta, modifiers.shift);
+                    self.scroll_to_caret();
+                    return Vec::new();
+                }
+                self.preferred_caret_x = None;
+
+                // Home and End go to the start and end of the wrapped line
+                if (keyboard_event.key == "Home" || keyboard_event.key == "End") && !modifiers.ctrl {
+                    let line_index = self.get_line_index(self.editing_state.model.get_selection().caret);
+                    let caret_index = if keyboard_event.key == "Home" {
+                        self.lines.get(line_index).map_or(0, |line| line.start)
+                    } else {
+                        self.get_line_caret_end(line_index)
+                    };
+                    self.editing_state.model.set_caret(caret_index, modifiers.shift);
+                    self.scroll_to_caret();
+                    return Vec::new();
+                }
+
+                match self.editing_state.model.handle_key_down(keyboard_event, true) {
+                    EditResult::ValueChanged => {
+                        self.update_lines();
+                        self.scroll_to_caret();
+                        self.event_handlers.get_propagation_data(&EventType::Input(self.get_value()))
+                    },
+                    EditResult::SelectionChanged => {
+                        self.scroll_to_caret();
+                        Vec::new()
+                    },
+                    EditResult::Ignored => Vec::new(),
+                }
+            },
+            _ => Vec::new(),
+        }
+    }
+
+    /*
+     * Moves the caret by a number of lines, as close as possible to its horizontal position before moving vertically.
+     * Moving past the first (last) line goes to the start (end) of the text.
+     */
+    fn move_caret_vertically(&mut self, line_delta: isize, extend_selection: bool) {
+        if self.lines.is_empty() {
+            return;
+        }
+
+        let caret = self.editing_state.model.get_selection().caret;
+        let line_index = self.get_line_index(caret);
+        let caret_x = self.preferred_caret_x.unwrap_or(self.get_caret_x(caret));
+        let target_line_index = line_index as isize + line_delta;
+
+        let caret_index = if target_line_index < 0 {
+            0
+        } else if target_line_index >= self.lines.len() as isize {
+            self.editing_state.model.get_char_count()
+        } else {
+            self.get_caret_index_in_line(target_line_index as usize, caret_x)
+        };
+
+        self.editing_state.model.set_caret(caret_index, extend_selection);
+        self.preferred_caret_x = Some(caret_x);
+    }
+
+    // Text measurement
+    fn get_font_size(&self) -> f32 {
+        self.styles.font_size.unwrap_or(Dimension { value: 16.0, unit: Unit::Px }).value
+    }
+
+    // Wraps the value to the content width
+    fn update_lines(&mut self) {
+        let (_, content_size) = ElementRenderer::get_content_box(self.position, self.size, &self.styles);
+        let value = self.get_value();
+        let chars: Vec<char> = value.chars().collect();
+
+        let line_ranges = skia_boundary::determine_editable_text_lines(
+            &value,
+            self.get_font_size(),
+            self.styles.font_weight.unwrap_or_default(),
+            self.styles.font_family.unwrap_or_default(),
+            self.styles.font_style.unwrap_or_default(),
+            content_size.width,
+        );
+
+        self.lines = line_ranges.into_iter()
+            .map(|(start, end)| TextLine {
+                start,
+                end,
+                caret_offsets: skia_boundary::get_caret_offsets(
+                    &chars[start..end].iter().collect::<String>(),
+                    self.get_font_size(),
+                    self.styles.font_weight.unwrap_or_default(),
+                    self.styles.font_family.unwrap_or_default(),
+                    self.styles.font_style.unwrap_or_default(),
+                ),
+            })
+            .collect();
+        self.render_cache.invalidate();
+    }
+
+    fn update_line_metrics(&mut self) {
+        self.line_metrics = skia_boundary::get_line_metrics(
+            self.get_font_size(),
+            self.styles.font_weight.unwrap_or_default(),
+            self.styles.font_family.unwrap_or_default(),
+            self.styles.font_style.unwrap_or_default(),
+        );
+    }
+
+    // A caret between two wrapped lines is displayed at the start of the second one
+    fn get_line_index(&self, caret_index: usize) -> usize {
+        self.lines.iter().rposition(|line| line.start <= caret_index).unwrap_or(0)
+    }
+
+    // Last caret position displayed on the line
+    fn get_line_caret_end(&self, line_index: usize) -> usize {
+        let line = match self.lines.get(line_index) {
+            Some(line) => line,
+            None => return 0,
+        };
+        let is_wrapped = self.lines.get(line_index + 1).map_or(false, |next_line| next_line.start == line.end);
+
+        if is_wrapped && line.end > line.start { line.end - 1 } else { line.end }
+    }
+
+    fn get_caret_x(&self, caret_index: usize) -> f32 {
+        let line = match self.lines.get(self.get_line_index(caret_index)) {
+            Some(line) => line,
+            None => return 0.0,
+        };
+        let column = caret_index.saturating_sub(line.start).min(line.caret_offsets.len().saturating_sub(1));
+
+        line.caret_offsets.get(column).copied().unwrap_or(0.0)
+    }
+
+    // Closest caret position to a horizontal offset within a line
+    fn get_caret_index_in_line(&self, line_index: usize, caret_x: f32) -> usize {
+        let line = match self.lines.get(line_index) {
+            Some(line) => line,
+            None => return 0,
+        };
+        let column_count = self.get_line_caret_end(line_index) - line.start + 1;
+
+        line.caret_offsets.iter()
+            .take(column_count)
+            .enumerate()
+            .min_by(|(_, first), (_, second)| (*first - caret_x).abs().total_cmp(&(*second - caret_x).abs()))
+            .map_or(line.start, |(column, _)| line.start + column)
+    }
+
+    fn get_caret_index_at(&self, cursor_position: Point) -> usize {
+        if self.lines.is_empty() {
+            return 0;
+        }
+
+        let (content_position, _) = ElementRenderer::get_content_box(self.position, self.size, &self.styles);
+        let line_height = self.line_metrics.1.max(1.0);
+        let line_position = (cursor_position.y - content_position.y + self.editing_state.scroll_offset) / line_height;
+        let line_index = (line_position.max(0.0) as usize).min(self.lines.len() - 1);
+
+        self.get_caret_index_in_line(line_index, cursor_position.x - content_position.x)
+    }
+
+    // Scrolling
+    fn get_visible_rows(&self) -> usize {
+        let (_, content_size) = ElementRenderer::get_content_box(self.position, self.size, &self.styles);
+        ((content_size.height / self.line_metrics.1.max(1.0)) as usize).max(1)
+    }
+
+    fn set_scroll_offset(&mut self, scroll_offset: f32) {
+        let (_, content_size) = ElementRenderer::get_content_box(self.position, self.size, &self.styles);
+        let text_height = self.lines.len() as f32 * self.line_metrics.1;
+
+        self.editing_state.scroll_offset = scroll_offset.min(text_height - content_size.height).max(0.0);
+        self.render_cache.invalidate();
+    }
+
+    // Scrolls the text vertically so that the caret's line stays within the content box
+    fn scroll_to_caret(&mut self) {
+        let (_, content_size) = ElementRenderer::get_content_box(self.position, self.size, &self.styles);
+        let line_height = self.line_metrics.1;
+        let caret_top = self.get_line_index(self.editing_state.model.get_selection().caret) as f32 * line_height;
+        let mut scroll_offset = self.editing_state.scroll_offset;
+
+        if caret_top + line_height - scroll_offset > content_size.height {
+            scroll_offset = caret_top + line_height - content_size.height;
+        }
+        if caret_top < scroll_offset {
+            scroll_offset = caret_top;
+        }
+        self.set_scroll_offset(scroll_offset);
+    }
+
+    // Utils
+    fn is_position_within_bounds(&self, point: Point) -> bool {
+        point.x >= self.position.x && point.x <= self.position.x + self.size.width &&
+        point.y >= self.position.y && point.y <= self.position.y + self.size.height
+    }
+
+    // Displays the styles matching the current interaction state (eg. :focus rules)
+    fn apply_interaction_state(&mut self) {
+        if self.pseudo_class_styles.is_empty() {
+            return;
+        }
+        let styles = self.pseudo_class_styles.resolve(self.base_styles, &self.interaction_state);
+        if self.style_animator.is_target(&styles) {
+            return; // The matching rules leave the styles unchanged
+        }
+        let displayed_styles = self.style_animator.set_styles(&self.styles, styles);
+        self.apply_animated_styles(displayed_styles);
+    }
+
+    pub fn set_disabled(&mut self, is_disabled: bool) {
+        if self.interaction_state.is_disabled == is_disabled {
+            return;
+        }
+        self.interaction_state.is_disabled = is_disabled;
+        self.apply_interaction_state();
+    }
+
+    // Displays the styles computed by the style animator on this frame
+    fn apply_animated_styles(&mut self, styles: Styles) {
+        if interpolation::affects_layout(&self.styles, &styles) {
+            self.layout_cache.mark_dirty();
+        }
+        self.styles = styles;
+        self.render_cache.invalidate();
+    }
+}
+
+impl Element for Textarea {
+    fn render(&self, canvas: &Canvas) {
+        let save_count = ElementRenderer::begin_effects(canvas, self.position, self.size, &self.styles);
+
+        ElementRenderer::render_element(
+            canvas,
+            self.position,
+            self.size,
+            self.styles.background_color.unwrap_or(Color::TRANSPARENT),
+            &self.styles.border.unwrap_or_default(),
+            self.styles.text_color.unwrap_or(Color::BLACK),
+        );
+        ElementRenderer::render_inset_shadows(canvas, self.position, self.size, &self.styles);
+
+        let (content_position, content_size) = ElementRenderer::get_content_box(self.position, self.size, &self.styles);
+        ElementRenderer::clip(canvas, content_position, content_size);
+
+        let (ascent, line_height) = self.line_metrics;
+        let text_top = content_position.y - self.editing_state.scroll_offset;
+        let selection = self.editing_state.model.get_selection();
+        let (selection_start, selection_end) = selection.get_range();
+        let is_focused = self.interaction_state.is_focused;
+        let text_color = self.styles.text_color.unwrap_or(Color::BLACK);
+
+        if self.editing_state.model.get_char_count() == 0 {
+            ElementRenderer::render_multi_line_text(
+                canvas,
+                Position { x: content_position.x, y: text_top + ascent },
+                vec![self.placeholder.clone()],
+                Color::from_rgb(117, 117, 117),
+                self.get_font_size(),
+                self.styles.font_weight.unwrap_or_default(),
+                self.styles.font_family.unwrap_or_default(),
+                self.styles.font_style.unwrap_or_default(),
+            );
+        }
+
+        let value: Vec<char> = self.editing_state.model.get_value().chars().collect();
+        for (line_index, line) in self.lines.iter().enumerate() {
+            let line_top = text_top + line_index as f32 * line_height;
+            if line_top + line_height < content_position.y || line_top > content_position.y + content_size.height {
+                continue;
+            }
+
+            // Selected part of the line, including its line break if selected
+            let highlight_start = selection_start.max(line.start);
+            let highlight_end = selection_end.min(line.end);
+            let includes_line_break = selection_end > line.end && selection_start <= line.end;
+            if is_focused && (highlight_start < highlight_end || includes_line_break) {
+                let start_offset = line.caret_offsets.get(highlight_start.min(line.end) - line.start).copied().unwrap_or(0.0);
+                let end_offset = line.caret_offsets.get(highlight_end.max(highlight_start) - line.start).copied().unwrap_or(start_offset);
+                let line_break_width = if includes_line_break { line_height / 4.0 } else { 0.0 };
+
+                ElementRenderer::render_element(
+                    canvas,
+                    Position { x: content_position.x + start_offset, y: line_top },
+                    Size { width: end_offset - start_offset + line_break_width, height: line_height },
+                    Color::from_rgb(179, 215, 255),
+                    &Border::default(),
+                    self.styles.text_color.unwrap_or(Color::BLACK),
+                );
+            }
+
+            ElementRenderer::render_multi_line_text(
+                canvas,
+                Position { x: content_position.x, y: line_top + ascent },
+                vec![value[line.start..line.end].iter().collect()],
+                text_color,
+                self.get_font_size(),
+                self.styles.font_weight.unwrap_or_default(),
+                self.styles.font_family.unwrap_or_default(),
+                self.styles.font_style.unwrap_or_default(),
+            );
+        }
+
+        if is_focused {
+            let caret_line_index = self.get_line_index(selection.caret);
+            ElementRenderer::render_caret(
+                canvas,
+                Position {
+                    x: content_position.x + self.get_caret_x(selection.caret),
+                    y: text_top + caret_line_index as f32 * line_height,
+                },
+                line_height,
+                text_color,
+            );
+        }
+
+        ElementRenderer::end_effects(canvas, save_count);
+        self.render_cache.mark_painted(self.get_visual_bounds());
+    }
+
+    fn update(&mut self, dt: f32) {
+        if let Some(styles) = self.style_animator.update(dt) {
+            self.apply_animated_styles(styles);
+        }
+    }
+
+    // Places the caret on click, selects the text while dragging and scrolls with the mouse wheel
+    fn handle_event(&mut self, cursor_position: Point, event_type: &EventType) {
+        match event_type {
+            EventType::MouseDown if self.is_position_within_bounds(cursor_position) && !self.interaction_state.is_disabled => {
+                let caret_index = self.get_caret_index_at(cursor_position);
+                self.editing_state.model.set_caret(caret_index, false);
+                self.preferred_caret_x = None;
+                self.is_selecting = true;
+                self.render_cache.invalidate();
+            },
+            EventType::MouseDrag if self.is_selecting => {
+                let caret_index = self.get_caret_index_at(cursor_position);
+                self.editing_state.model.set_caret(caret_index, true);
+                self.scroll_to_caret();
+            },
+            EventType::MouseUp => {
+                self.is_selecting = false;
+            },
+            EventType::MouseRoll(delta) if self.is_position_within_bounds(cursor_position) => {
+                self.set_scroll_offset(self.editing_state.scroll_offset - delta * WHEEL_SCROLL_FACTOR);
+            },
+            _ => {}
+        }
+    }
+
+    fn propagate_event(&mut self, cursor_position: skia_safe::Point, event_type: &EventType) -> Vec<EventPropagationData> {
+        event_propagator::propagate_event(self, cursor_position, event_type)
+    }
+
+    fn propagate_targeted_event(&mut self, target_id: Option<&String>, event_type: &EventType) -> Option<Vec<EventPropagationData>> {
+        let mut event_targets = event_propagator::propagate_targeted_event(self, target_id, event_type)?;
+        if event_propagator::should_handle_targeted_event(self, target_id, event_type) {
+            event_targets.extend(self.event_handlers.get_propagation_data(event_type));
+        }
+        event_targets.extend(self.apply_default_action(event_type));
+
+        Some(event_targets)
+    }
+
+    fn dispatch_mouse_event(&mut self, path: &[String], mouse_event: &MouseEvent, phase: EventPhase) -> Vec<EventPropagationData> {
+        let event_targets = self.event_handlers.get_dispatch_data(&self._id, path, mouse_event, phase);
+        event_propagator::dispatch_mouse_event(self, path, mouse_event, phase, event_targets)
+    }
+
+    // Focus
+    fn get_tab_index(&self) -> Option<i32> {
+        self.tab_index.filter(|_| !self.interaction_state.is_disabled)
+    }
+
+    fn set_focused(&mut self, is_focused: bool) {
+        if self.interaction_state.is_focused == is_focused {
+            return;
+        }
+        self.interaction_state.is_focused = is_focused;
+        self.is_selecting = false;
+        self.apply_interaction_state();
+    }
+
+    // Pointer interaction
+    fn set_hovered(&mut self, is_hovered: bool) {
+        if self.interaction_state.is_hovered == is_hovered {
+            return;
+        }
+        self.interaction_state.is_hovered = is_hovered;
+        self.apply_interaction_state();
+    }
+
+    fn set_active(&mut self, is_active: bool) {
+        if self.interaction_state.is_active == is_active {
+            return;
+        }
+        self.interaction_state.is_active = is_active;
+        self.apply_interaction_state();
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.interaction_state.is_disabled
+    }
+
+    // Text editing
+    fn get_text_editing_state(&self) -> Option<&TextEditingState> {
+        Some(&self.editing_state)
+    }
+
+    fn inherit_text_editing_state(&mut self, previous_state: &TextEditingState) {
+        self.editing_state.inherit(previous_state);
+        self.update_lines();
+    }
+
+    // Animation
+    fn needs_animation_frame(&self) -> bool {
+        self.style_animator.is_animating()
+    }
+
+    fn get_style_animator(&self) -> Option<&StyleAnimator> {
+        Some(&self.style_animator)
+    }
+
+    fn inherit_style_animator(&mut self, previous_animator: &StyleAnimator, previous_styles: &Styles) {
+        if let Some(styles) = self.style_animator.inherit(previous_animator, previous_styles) {
+            self.apply_animated_styles(styles);
+        }
+    }
+
+    // Rendering cache
+    fn needs_repaint(&self) -> bool {
+        self.render_cache.needs_repaint()
+    }
+
+    fn get_visual_bounds(&self) -> Rect {
+        let visual_bounds = Rect::from_xywh(self.position.x, self.position.y, self.size.width, self.size.height);
+
+        ElementRenderer::transform_bounds(visual_bounds, self.position, self.size, &self.styles)
+    }
+
+    fn collect_damage(&self, damaged_rects: &mut Vec<Rect>) {
+        if self.render_cache.needs_repaint() {
+            self.render_cache.collect_own_damage(self.get_visual_bounds(), damaged_rects);
+        }
+    }
+
+    fn set_id(&mut self, id: String) {
+        self._id = id;
+    }
+
+    fn set_position(&mut self, position: Position) {
+        if self.position != position {
+            self.render_cache.invalidate();
+        }
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Size) {
+        if self.size != size {
+            self.render_cache.invalidate();
+        }
+        self.size = size;
+    }
+
+    fn set_styles(&mut self, styles: Styles) {
+        self.base_styles = styles;
+        let styles = self.pseudo_class_styles.resolve(styles, &self.interaction_state);
+        self.styles = self.style_animator.set_styles(&self.styles, styles);
+        self.layout_cache.mark_dirty();
+        self.render_cache.invalidate();
+    }
+
+    fn add_child(&mut self, _: Box<dyn Element>) {}
+
+    fn get_id(&self) -> String {
+        self._id.clone()
+    }
+
+    fn get_template_id(&self) -> Option<&String> {
+        self.template_id.as_ref()
+    }
+
+    fn get_element_type(&self) -> ElementType {
+        ElementType::Textarea
+    }
+
+    fn get_name(&self) -> String {
+        "textarea".to_string()
+    }
+
+    fn get_position(&self) -> Position {
+        self.position
+    }
+
+    fn get_size(&self) -> Size {
+        self.size
+    }
+
+    fn get_styles(&self) -> Styles {
+        self.styles
+    }
+
+    fn get_children_mut(&mut self) -> Option<&mut Vec<Box<dyn Element>>> { None }
+
+    // Component system
+    fn get_component_interface(&mut self) -> Option<&mut dyn ComponentInterface> {
+        None
+    }
+
+    fn initialize(&mut self, _: HashMap<String, Box<dyn Any>>) {
+        // Nothing for now (implemented for components only)
+    }
+
+    fn handle_route_change(&mut self, _: &String, _: &String) {
+        // Nothing for now (implemented for components only)
+    }
+
+    // Layout system
+    fn set_natural_size(&mut self, size: Size) {
+        self.natural_size = size;
+    }
+
+    fn set_requested_size(&mut self, requested_size: OptionalSize) {
+        self.requested_size = requested_size;
+    }
+
+    fn get_natural_size(&self) -> Size {
+        self.natural_size
+    }
+
+    fn get_requested_size(&self) -> OptionalSize {
+        self.requested_size
+    }
+
+    fn get_effective_size(&self) -> Size {
+        effective_size_estimator::estimate_effective_size(&self.get_requested_size(), &self.get_natural_size())
+    }
+
+    fn is_text_wrapper(&self) -> bool { false }
+
+    // A number of rows, regardless of the value (which scrolls)
+    fn estimate_sizes(&mut self) {
+        if !self.layout_cache.needs_estimation() {
+            if let Some((natural_size, requested_size)) = self.layout_cache.get_estimated_sizes() {
+                self.natural_size = natural_size;
+                self.requested_size = requested_size;
+            }
+            return;
+        }
+
+        self.update_line_metrics();
+
+        let border = self.styles.border.unwrap_or_default();
+        let padding = self.styles.padding.unwrap_or_default();
+        self.set_natural_size(Size {
+            width: DEFAULT_WIDTH + border.horizontal() + padding.horizontal(),
+            height: self.rows.max(1) as f32 * self.line_metrics.1 + border.vertical() + padding.vertical(),
+        });
+
+        let sizing_policy = self.get_styles().sizing_policy.unwrap_or_default();
+        self.set_requested_size(OptionalSize { width: sizing_policy.width, height: sizing_policy.height });
+
+        self.layout_cache.store_estimated_sizes(self.natural_size, self.requested_size);
+    }
+
+    fn allocate_space(&mut self, allocated_position: Position, allocated_size: Size) {
+        if self.layout_cache.get_allocation_action(allocated_position, allocated_size) != AllocationAction::Full {
+            return;
+        }
+
+        self.position = allocated_position;
+        self.size = allocated_size;
+        self.update_lines();
+        self.scroll_to_caret();
+
+        self.layout_cache.store_allocation(allocated_position, allocated_size);
+    }
+
+    fn layout(&mut self, allocated_position: Position, allocated_size: Size) {
+        self.estimate_sizes();
+        self.allocate_space(allocated_position, allocated_size);
+    }
+
+    // Incremental layout
+    fn needs_layout(&self) -> bool {
+        self.layout_cache.needs_layout()
+    }
+
+    fn mark_layout_dirty(&mut self) {
+        self.layout_cache.mark_dirty();
+    }
+
+    fn has_estimate_changed(&self) -> bool {
+        self.layout_cache.has_estimate_changed()
+    }
+
+    fn allocate_pending_space(&mut self) {
+        if let Some((allocated_position, allocated_size)) = self.layout_cache.get_allocation() {
+            self.allocate_space(allocated_position, allocated_size);
+        }
+    }
+
+    fn react_to_state_change(&mut self, _component_id: String) {}
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Lines of the value with characters 10 pixels wide
+    fn create_textarea(value: &str, line_ranges: &[(usize, usize)]) -> Textarea {
+        let mut textarea = Textarea::new(value.to_string(), None);
+        textarea.lines = line_ranges.iter()
+            .map(|&(start, end)| TextLine {
+                start,
+                end,
+                caret_offsets: (0..=end - start).map(|column| column as f32 * 10.0).collect(),
+            })
+            .collect();
+        textarea
+    }
+
+    #[test]
+    fn test_caret_at_wrap_point_shows_on_next_line() {
+        // Arrange
+        let textarea = create_textarea("hello world\nfoo", &[(0, 6), (6, 11), (12, 15)]);
+
+        // Act
+        let wrap_point_line_index = textarea.get_line_index(6);
+        let line_break_line_index = textarea.get_line_index(11);
+        let wrapped_line_end = textarea.get_line_caret_end(0);
+        let broken_line_end = textarea.get_line_caret_end(1);
+
+        // Assert
+        assert_eq!(wrap_point_line_index, 1);
+        assert_eq!(line_break_line_index, 1);
+        assert_eq!(wrapped_line_end, 5); // Before the trailing space, the end being displayed on the next line
+        assert_eq!(broken_line_end, 11);
+    }
+
+    #[test]
+    fn test_move_caret_vertically_keeps_column_and_stops_at_text_ends() {
+        // Arrange
+        let mut textarea = create_textarea("hello world\nfoo", &[(0, 6), (6, 11), (12, 15)]);
+        textarea.editing_state.model.set_caret(2, false);
+
+        // Act
+        textarea.move_caret_vertically(1, false);
+        let line_down_caret = textarea.editing_state.model.get_selection().caret;
+        textarea.move_caret_vertically(5, false);
+        let page_down_caret = textarea.editing_state.model.get_selection().caret;
+        textarea.move_caret_vertically(-5, false);
+        let page_up_caret = textarea.editing_state.model.get_selection().caret;
+
+        // Assert
+        assert_eq!(line_down_caret, 8);
+        assert_eq!(page_down_caret, 15); // Past the last line, at the end of the text
+        assert_eq!(page_up_caret, 0);
+    }
+}
@@ -0,0 +1,236 @@
+use skia_safe::Point;
+
+use crate::rendering::elements::{element::{Element, EventType}, event_propagator};
+
+
+/*
+ * Tracks the focused element by ID, notifying the elements (set_focused) and their handlers (focus and blur events)
+ * whenever the focus moves. Elements are focusable if they have a tab index; only those with a non-negative one
+ * are reached with Tab, as in the DOM.
+ */
+pub struct FocusManager {
+    focused_element_id: Option<String>,
+}
+
+impl FocusManager {
+    pub fn new() -> Self {
+        Self { focused_element_id: None }
+    }
+
+    pub fn get_focused_element_id(&self) -> Option<&String> {
+        self.focused_element_id.as_ref()
+    }
+
+    // Focuses the element with the given id attribute (see FocusProxy), returning whether it was found and is focusable
+    pub fn focus(&mut self, root_element: &mut dyn Element, template_id: &String) -> bool {
+        let element_id = match find_element_by_template_id_mut(root_element, template_id) {
+            Some(element) if element.get_tab_index().is_some() => element.get_id(),
+            _ => return false,
+        };
+
+        self.move_focus(root_element, Some(element_id));
+        true
+    }
+
+    pub fn blur(&mut self, root_element: &mut dyn Element) {
+        self.move_focus(root_element, None);
+    }
+
+    // Focuses the topmost focusable element under the cursor, blurring the current one if there is none
+    pub fn focus_at(&mut self, root_element: &mut dyn Element, cursor_position: Point) {
+        let element_id = find_focusable_element_at(root_element, cursor_position);
+        self.move_focus(root_element, element_id);
+    }
+
+    /*
+     * Moves the focus in tab order: elements with a positive tab index first (in ascending order), 
+     * then those with a tab index of 0, in document order. Wraps around at both ends.
+     */
+    pub fn focus_next(&mut self, root_element: &mut dyn Element, backwards: bool) {
+        let mut focusable_elements = Vec::new();
+        collect_tab_order(root_element, &mut focusable_elements);
+        focusable_elements.sort_by_key(|(_, tab_index)| if *tab_index == 0 { i32::MAX } else { *tab_index }); // Stable
+        if focusable_elements.is_empty() {
+            return;
+        }
+
+        let element_count = focusable_elements.len();
+        let current_index = self.focused_element_id.as_ref()
+            .and_then(|focused_id| focusable_elements.iter().position(|(id, _)| id == focused_id));
+        let next_index = match (current_index, backwards) {
+            (Some(index), false) => (index + 1) % element_count,
+            (Some(index), true) => (index + element_count - 1) % element_count,
+            (None, false) => 0,
+            (None, true) => element_count - 1,
+        };
+
+        let next_element_id = focusable_elements[next_index].0.clone();
+        self.move_focus(root_element, Some(next_element_id));
+    }
+
+    // Re-applies the focus once the tree has changed (eg. template reload), dropping it if the element is gone
+    pub fn restore_focus(&mut self, root_element: &mut dyn Element) {
+        if let Some(focused_id) = self.focused_element_id.clone() {
+            match find_element_mut(root_element, &focused_id) {
+                Some(element) => element.set_focused(true),
+                None => self.focused_element_id = None,
+            }
+        }
+    }
+
+    fn move_focus(&mut self, root_element: &mut dyn Element, element_id: Option<String>) {
+        if self.focused_element_id == element_id {
+            return;
+        }
+
+        if let Some(previous_id) = self.focused_element_id.take() {
+            if let Some(previous_element) = find_element_mut(root_element, &previous_id) {
+                previous_element.set_focused(false);
+            }
+            root_element.propagate_targeted_event(Some(&previous_id), &EventType::Blur);
+        }
+
+        if let Some(id) = &element_id {
+            if let Some(element) = find_element_mut(root_element, id) {
+                element.set_focused(true);
+            }
+            root_element.propagate_targeted_event(Some(id), &EventType::Focus);
+        }
+
+        self.focused_element_id = element_id;
+    }
+}
+
+impl Default for FocusManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn find_element_mut<'a>(element: &'a mut dyn Element, element_id: &String) -> Option<&'a mut dyn Element> {
+    if element.get_id() == *element_id {
+        return Some(element);
+    }
+
+    for child in element.get_children_mut()? {
+        if let Some(found_element) = find_element_mut(child.as_mut(), element_id) {
+            return Some(found_element);
+        }
+    }
+
+    None
+}
+
+// As with getElementById, the first element in document order if several share the id attribute
+pub fn find_element_by_template_id_mut<'a>(element: &'a mut dyn Element, template_id: &String) -> Option<&'a mut dyn Element> {
+    if element.get_template_id() == Some(template_id) {
+        return Some(element);
+    }
+
+    for child in element.get_children_mut()? {
+        if let Some(found_element) = find_element_by_template_id_mut(child.as_mut(), template_id) {
+            return Some(found_element);
+        }
+    }
+
+    None
+}
+
+// Children are painted after their parent and previous siblings, so the last match is the topmost
+fn find_focusable_element_at(element: &mut dyn Element, cursor_position: Point) -> Option<String> {
+    let mut element_id = None;
+
+    let position = element.get_position();
+    let size = element.get_size();
+    let is_within_bounds = cursor_position.x >= position.x && cursor_position.x <= position.x + size.width &&
+        cursor_position.y >= position.y && cursor_position.y <= position.y + size.height;
+    if is_within_bounds && element.get_tab_index().is_some() {
+        element_id = Some(element.get_id());
+    }
+    if event_propagator::is_clipped_at(element, cursor_position) {
+        return element_id;
+    }
+
+    for child in element.get_children_mut().unwrap_or(&mut vec![]) {
+        if let Some(child_element_id) = find_focusable_element_at(child.as_mut(), cursor_position) {
+            element_id = Some(child_element_id);
+        }
+    }
+
+    element_id
+}
+
+fn collect_tab_order(element: &mut dyn Element, focusable_elements: &mut Vec<(String, i32)>) {
+    if let Some(tab_index) = element.get_tab_index().filter(|tab_index| *tab_index >= 0) {
+        focusable_elements.push((element.get_id(), tab_index));
+    }
+
+    for child in element.get_children_mut().unwrap_or(&mut vec![]) {
+        collect_tab_order(child.as_mut(), focusable_elements);
+    }
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use crate::rendering::elements::container::Container;
+
+    use super::*;
+
+    fn create_focusable_container(id: &str, tab_index: i32) -> Box<dyn Element> {
+        let mut container = Container::new();
+        container.set_id(id.to_string());
+        container.tab_index = Some(tab_index);
+        Box::new(container)
+    }
+
+    #[test]
+    fn test_focus_next_follows_tab_order() {
+        // Arrange
+        let mut root_element = Container::new();
+        root_element.add_child(create_focusable_container("first", 0));
+        root_element.add_child(create_focusable_container("skipped", -1));
+        root_element.add_child(create_focusable_container("positive", 2));
+        root_element.add_child(create_focusable_container("last", 0));
+        let mut focus_manager = FocusManager::new();
+
+        // Act
+        let mut focus_order = Vec::new();
+        for _ in 0..4 {
+            focus_manager.focus_next(&mut root_element, false);
+            focus_order.push(focus_manager.get_focused_element_id().cloned().unwrap());
+        }
+        focus_manager.focus_next(&mut root_element, true);
+
+        // Assert
+        assert_eq!(focus_order, vec!["positive", "first", "last", "positive"]);
+        assert_eq!(focus_manager.get_focused_element_id(), Some(&"last".to_string()));
+    }
+
+    #[test]
+    fn test_focus_by_template_id_keeps_duplicates_apart() {
+        // Arrange
+        let mut root_element = Container::new();
+        for _ in 0..2 {
+            let mut container = Container::new();
+            container.template_id = Some(String::from("field"));
+            container.tab_index = Some(0);
+            root_element.add_child(Box::new(container));
+        }
+        let first_id = root_element.children[0].get_id();
+        let second_id = root_element.children[1].get_id();
+        let mut focus_manager = FocusManager::new();
+
+        // Act
+        let is_focused = focus_manager.focus(&mut root_element, &String::from("field"));
+        let focused_id = focus_manager.get_focused_element_id().cloned();
+        focus_manager.focus_next(&mut root_element, false);
+
+        // Assert
+        assert!(is_focused);
+        assert_ne!(first_id, second_id);
+        assert_eq!(focused_id, Some(first_id));
+        assert_eq!(focus_manager.get_focused_element_id(), Some(&second_id));
+    }
+}
@@ -0,0 +1,32 @@
+use crate::application::event_loop_proxy::{get_event_loop_proxy, ApplicationEvent};
+
+/*
+ * Proxy allowing components to move the focus, eg. to focus a search field on init.
+ * Elements are referenced by the id attribute given in their template.
+ */
+pub fn get_focus_manager() -> FocusProxy {
+    FocusProxy
+}
+
+pub struct FocusProxy;
+
+impl FocusProxy {
+    pub fn focus(&self, element_id: &str) {
+        self.send_focus_change(Some(element_id.to_string()));
+    }
+
+    pub fn blur(&self) {
+        self.send_focus_change(None);
+    }
+
+    fn send_focus_change(&self, element_id: Option<String>) {
+        let event_proxy_option = get_event_loop_proxy();
+        if event_proxy_option.is_none() {
+            println!("Event proxy is None");
+            return;
+        }
+
+        event_proxy_option.unwrap().send_event(ApplicationEvent::FocusChange(element_id))
+            .expect("Failed to send event");
+    }
+}
@@ -0,0 +1,7 @@
+/*
+ * Keyboard focus: the focused element receives the keyboard events and matches :focus rules.
+ * Focus moves on click, with Tab/Shift+Tab, or through the focus proxy from component code.
+ */
+
+pub mod focus_manager;
+pub mod focus_proxy;
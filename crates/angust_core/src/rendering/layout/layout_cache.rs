@@ -0,0 +1,177 @@
+use crate::rendering::elements::common_types::{OptionalSize, Position, Size};
+
+
+/*
+ * Per-element bookkeeping for incremental layout.
+ * An element is marked dirty when its content or styles change (e.g. a component reloading its template),
+ * and its ancestors are notified that they have dirty descendants.
+ * Clean elements skip the first pass (restoring their cached estimates instead)
+ * and skip the second pass when they are allocated the same space as last time.
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct LayoutCache {
+    is_dirty: bool,
+    has_dirty_descendants: bool,
+    needs_allocation: bool,
+    has_estimate_changed: bool,
+    estimated_sizes: Option<(Size, OptionalSize)>,
+    allocation: Option<(Position, Size)>,
+}
+
+impl Default for LayoutCache {
+    fn default() -> Self {
+        Self {
+            is_dirty: true,
+            has_dirty_descendants: false,
+            needs_allocation: true,
+            has_estimate_changed: true,
+            estimated_sizes: None,
+            allocation: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AllocationAction {
+    Skip, // Same allocation and nothing changed below
+    DescendantsOnly, // Same allocation and estimates, but some descendants still need to be laid out
+    Full,
+}
+
+impl LayoutCache {
+    // Invalidation
+    pub fn mark_dirty(&mut self) {
+        self.is_dirty = true;
+        self.needs_allocation = true;
+    }
+
+    pub fn mark_descendants_dirty(&mut self) {
+        self.has_dirty_descendants = true;
+    }
+
+    pub fn needs_layout(&self) -> bool {
+        self.is_dirty || self.has_dirty_descendants || self.needs_allocation
+    }
+
+    // First pass
+    pub fn needs_estimation(&self) -> bool {
+        self.is_dirty || self.has_dirty_descendants || self.estimated_sizes.is_none()
+    }
+
+    pub fn get_estimated_sizes(&self) -> Option<(Size, OptionalSize)> {
+        self.estimated_sizes
+    }
+
+    /*
+     * Called at the end of the first pass. Elements whose estimates changed have to be reallocated,
+     * and so do their parents, since the siblings may move.
+     */
+    pub fn store_estimated_sizes(&mut self, natural_size: Size, requested_size: OptionalSize) {
+        self.has_estimate_changed = self.estimated_sizes != Some((natural_size, requested_size));
+        if self.has_estimate_changed {
+            self.needs_allocation = true;
+        }
+
+        self.estimated_sizes = Some((natural_size, requested_size));
+        self.is_dirty = false;
+    }
+
+    pub fn has_estimate_changed(&self) -> bool {
+        self.has_estimate_changed
+    }
+
+    pub fn mark_allocation_needed(&mut self) {
+        self.needs_allocation = true;
+    }
+
+    // Second pass
+    pub fn get_allocation_action(&self, allocated_position: Position, allocated_size: Size) -> AllocationAction {
+        if self.needs_allocation || self.allocation != Some((allocated_position, allocated_size)) {
+            return AllocationAction::Full;
+        }
+        if self.has_dirty_descendants {
+            return AllocationAction::DescendantsOnly;
+        }
+
+        AllocationAction::Skip
+    }
+
+    pub fn get_allocation(&self) -> Option<(Position, Size)> {
+        self.allocation
+    }
+
+    pub fn store_allocation(&mut self, allocated_position: Position, allocated_size: Size) {
+        self.allocation = Some((allocated_position, allocated_size));
+        self.needs_allocation = false;
+        self.has_dirty_descendants = false;
+        self.has_estimate_changed = false;
+    }
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_element_skips_same_allocation() {
+        // Arrange
+        let mut layout_cache = LayoutCache::default();
+        let position = Position { x: 0.0, y: 0.0 };
+        let size = Size { width: 100.0, height: 50.0 };
+
+        layout_cache.store_estimated_sizes(size, OptionalSize::default());
+        layout_cache.store_allocation(position, size);
+
+        // Act
+        let same_allocation_action = layout_cache.get_allocation_action(position, size);
+        let new_allocation_action = layout_cache.get_allocation_action(position, Size { width: 120.0, height: 50.0 });
+
+        // Assert
+        assert!(!layout_cache.needs_layout());
+        assert!(!layout_cache.needs_estimation());
+        assert_eq!(same_allocation_action, AllocationAction::Skip);
+        assert_eq!(new_allocation_action, AllocationAction::Full);
+    }
+
+    #[test]
+    fn test_dirty_descendants_with_unchanged_estimate() {
+        // Arrange
+        let mut layout_cache = LayoutCache::default();
+        let position = Position { x: 0.0, y: 0.0 };
+        let size = Size { width: 100.0, height: 50.0 };
+
+        layout_cache.store_estimated_sizes(size, OptionalSize::default());
+        layout_cache.store_allocation(position, size);
+
+        // Act
+        layout_cache.mark_descendants_dirty();
+        let needs_estimation = layout_cache.needs_estimation();
+        layout_cache.store_estimated_sizes(size, OptionalSize::default());
+
+        // Assert
+        assert!(needs_estimation);
+        assert!(!layout_cache.has_estimate_changed());
+        assert_eq!(layout_cache.get_allocation_action(position, size), AllocationAction::DescendantsOnly);
+    }
+
+    #[test]
+    fn test_changed_estimate_requires_allocation() {
+        // Arrange
+        let mut layout_cache = LayoutCache::default();
+        let position = Position { x: 0.0, y: 0.0 };
+        let size = Size { width: 100.0, height: 50.0 };
+
+        layout_cache.store_estimated_sizes(size, OptionalSize::default());
+        layout_cache.store_allocation(position, size);
+
+        // Act
+        layout_cache.mark_dirty();
+        layout_cache.store_estimated_sizes(Size { width: 100.0, height: 80.0 }, OptionalSize::default());
+
+        // Assert
+        assert!(layout_cache.has_estimate_changed());
+        assert_eq!(layout_cache.get_allocation_action(position, size), AllocationAction::Full);
+    }
+}
@@ -6,4 +6,5 @@
  */
 
 pub mod space_allocation_system;
-pub mod size_estimation_system;
\ No newline at end of file
+pub mod size_estimation_system;
+pub mod layout_cache;
\ No newline at end of file
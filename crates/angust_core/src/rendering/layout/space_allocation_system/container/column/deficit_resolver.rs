@@ -1,9 +1,9 @@
 use crate::rendering::{
     elements::{
-        common_types::{OptionalSize, Size}, 
+        common_types::{OptionalSize, ScrollbarAxis, Size}, 
         container::Container, 
         element::Element, 
-        styles::{Dimension, Directions, Overflow, Unit}
+        styles::{Dimension, Margin, Overflow, Unit}
     }, 
     layout::size_estimation_system::child_size_estimator, 
 };
@@ -19,7 +19,9 @@ pub fn resolve_deficits_column(
     requested_height: f32,
     deficit: &mut f32,
 ) -> f32 {
+    container.scrollbar_state.is_overflowing.vertical = false;
     if *deficit <= 0.0 {
+        container.scrollbar_state.set_max_scroll_offset(ScrollbarAxis::Vertical, 0.0);
         return 0.0;
     }
 
@@ -35,11 +37,36 @@ pub fn resolve_deficits_column(
         handle_overflow(container, effective_vertical_space, deficit, &mut new_requested_height);
     }
 
-    let current_scroll_position_y = container.scrollbar_state.current_scroll_position.y;
-    let overflow_height = new_requested_height - allocated_size.height;
-    let scrollbar_offset = overflow_height * current_scroll_position_y;
+    let overflow_height = if container.scrollbar_state.is_overflowing.vertical { new_requested_height - allocated_size.height } else { 0.0 };
+    container.scrollbar_state.set_max_scroll_offset(ScrollbarAxis::Vertical, overflow_height);
 
-    scrollbar_offset
+    container.scrollbar_state.get_scroll_offset().y
+}
+
+/*
+ * Function to handle the children overflowing the container horizontally (the cross axis),
+ * returning the offset by which they are scrolled.
+ */
+pub fn resolve_cross_axis_overflow_column(
+    container: &mut Container,
+    allocated_size: Size,
+    children_max_width: f32,
+    max_width_child_margin: Margin,
+) -> f32 {
+    let overflow = container.get_styles().overflow.unwrap_or_default();
+    let padding = container.get_styles().padding.unwrap_or_default();
+    let requested_width = children_max_width + max_width_child_margin.horizontal() + padding.horizontal();
+    if !matches!(overflow, Overflow::Auto | Overflow::Scroll) || requested_width <= allocated_size.width {
+        container.scrollbar_state.is_overflowing.horizontal = false;
+        container.scrollbar_state.set_max_scroll_offset(ScrollbarAxis::Horizontal, 0.0);
+        return 0.0;
+    }
+
+    container.scrollbar_state.thumb_scrollbar_width_ratio = (allocated_size.width - padding.horizontal()) / requested_width;
+    container.scrollbar_state.is_overflowing.horizontal = true;
+
+    container.scrollbar_state.set_max_scroll_offset(ScrollbarAxis::Horizontal, requested_width - allocated_size.width);
+    container.scrollbar_state.get_scroll_offset().x
 }
 
 /*
@@ -89,10 +116,7 @@ fn handle_overflow(
             
             container.scrollbar_state.thumb_scrollbar_height_ratio =
                 effective_vertical_space / new_requested_height.clone();
-            container.scrollbar_state.is_overflowing = Directions {
-                horizontal: false,
-                vertical: true,
-            };
+            container.scrollbar_state.is_overflowing.vertical = true;
         },
         Overflow::Hidden | Overflow::Visible => {},
     }
@@ -1,9 +1,9 @@
 use crate::rendering::{
     elements::{
-        common_types::{OptionalSize, Size}, 
+        common_types::{OptionalSize, ScrollbarAxis, Size}, 
         container::Container, 
         element::Element, 
-        styles::{Dimension, Directions, Overflow, Unit, WhiteSpace}
+        styles::{Dimension, Margin, Overflow, Unit, WhiteSpace}
     }, 
     layout::size_estimation_system::child_size_estimator, 
 };
@@ -18,7 +18,9 @@ pub fn resolve_deficits_row(
     requested_width: f32,
     deficit: &mut f32,
 ) -> f32 {
+    container.scrollbar_state.is_overflowing.horizontal = false;
     if *deficit <= 0.0 {
+        container.scrollbar_state.set_max_scroll_offset(ScrollbarAxis::Horizontal, 0.0);
         return 0.0;
     }
 
@@ -34,11 +36,36 @@ pub fn resolve_deficits_row(
         handle_overflow(container, effective_horizontal_space, deficit, &mut new_requested_width);
     }
 
-    let current_scroll_position_x = container.scrollbar_state.current_scroll_position.x;
-    let overflow_width = new_requested_width - allocated_size.width;
-    let scrollbar_offset = overflow_width * current_scroll_position_x;
+    let overflow_width = if container.scrollbar_state.is_overflowing.horizontal { new_requested_width - allocated_size.width } else { 0.0 };
+    container.scrollbar_state.set_max_scroll_offset(ScrollbarAxis::Horizontal, overflow_width);
 
-    scrollbar_offset
+    container.scrollbar_state.get_scroll_offset().x
+}
+
+/*
+ * Function to handle the children overflowing the container vertically (the cross axis),
+ * returning the offset by which they are scrolled.
+ */
+pub fn resolve_cross_axis_overflow_row(
+    container: &mut Container,
+    allocated_size: Size,
+    children_max_height: f32,
+    max_height_child_margin: Margin,
+) -> f32 {
+    let overflow = container.get_styles().overflow.unwrap_or_default();
+    let padding = container.get_styles().padding.unwrap_or_default();
+    let requested_height = children_max_height + max_height_child_margin.vertical() + padding.vertical();
+    if !matches!(overflow, Overflow::Auto | Overflow::Scroll) || requested_height <= allocated_size.height {
+        container.scrollbar_state.is_overflowing.vertical = false;
+        container.scrollbar_state.set_max_scroll_offset(ScrollbarAxis::Vertical, 0.0);
+        return 0.0;
+    }
+
+    container.scrollbar_state.thumb_scrollbar_height_ratio = (allocated_size.height - padding.vertical()) / requested_height;
+    container.scrollbar_state.is_overflowing.vertical = true;
+
+    container.scrollbar_state.set_max_scroll_offset(ScrollbarAxis::Vertical, requested_height - allocated_size.height);
+    container.scrollbar_state.get_scroll_offset().y
 }
 
 /*
@@ -88,10 +115,7 @@ fn handle_overflow(
             
             container.scrollbar_state.thumb_scrollbar_width_ratio =
                 effective_horizontal_space / new_requested_width.clone();
-            container.scrollbar_state.is_overflowing = Directions {
-                horizontal: true,
-                vertical: false,
-            };
+            container.scrollbar_state.is_overflowing.horizontal = true;
         },
         Overflow::Hidden | Overflow::Visible => {},
     }
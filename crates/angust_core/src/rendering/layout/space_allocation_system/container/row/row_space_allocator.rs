@@ -42,7 +42,7 @@ pub fn allocate_space_to_children_row_flex(
     // Identify and resolve horizontal surplus according to justify-content
     let (mut cursor_position, justify_content_spacing) = surplus_resolver::resolve_horizontal_space_surplus(container, allocated_position, - horizontal_deficit);
     
-    if overflow == Overflow::Auto {
+    if matches!(overflow, Overflow::Auto | Overflow::Scroll) {
         cursor_position.x -= scrollbar_offset;
     }
     
@@ -51,6 +51,12 @@ pub fn allocate_space_to_children_row_flex(
     let (children_max_height, max_height_child_margin) = 
         child_size_estimator::get_max_height_child_properties(container, &all_indices);
 
+    // Scroll the children vertically when they are taller than the container
+    let cross_axis_scrollbar_offset = deficit_resolver::resolve_cross_axis_overflow_row(
+        container, allocated_size, children_max_height, max_height_child_margin
+    );
+    cursor_position.y -= cross_axis_scrollbar_offset;
+
     for (index, child) in container.children.iter_mut().enumerate() {
         let child_effective_size = child.get_effective_size();
         let child_margin = child.get_styles().margin.unwrap_or_default();
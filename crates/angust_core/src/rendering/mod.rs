@@ -5,4 +5,7 @@ mod rendering_interface;
 
 pub mod router;
 pub mod elements;
+pub mod animation;
+pub mod focus;
+pub mod scrolling;
 mod layout;
\ No newline at end of file
@@ -1,6 +1,6 @@
 use skia_safe::{
     gpu::{self, gl::FramebufferInfo, SurfaceOrigin},
-    ColorType, Surface,
+    region::RegionOp, surfaces, AlphaType, ColorType, EncodedImageFormat, IRect, ImageInfo, Rect, Region, RoundOut, SamplingOptions, Surface,
 };
 use winit::window::Window;
 use skia_safe::gpu::DirectContext;
@@ -9,32 +9,66 @@ use super::{
     elements::{
         button::EventPropagationData, 
         common_types::{Position, Size}, 
-        element::{Element, EventType}
+        element::{Element, EventType}, 
+        mouse_event::MouseEvent, 
+        styles::Cursor
     }, 
+    scrolling::scroll_manager::ScrollRequest, 
     ui_manager::UIManager
 };
 
 pub struct Renderer {
     pub surface: Surface,
-    pub screen_size: Size,
+    pub screen_size: Size, // In logical pixels, the surface being screen_size * scale_factor device pixels
+    scale_factor: f32,
     ui_manager: UIManager,
+    back_buffer: Surface, // Retains the previous frame, so that only damaged regions are redrawn
+    needs_full_redraw: bool,
 }
 
 impl Renderer {
     pub fn new(window: &Window, gr_context: &mut DirectContext, fb_info: FramebufferInfo, sample_count: usize, stencil_bits: usize, ui_body: Box<dyn Element>) -> Self {
-        let surface = Self::create_surface(
+        let mut surface = Self::create_surface(
             window,
             fb_info,
             gr_context,
             sample_count,
             stencil_bits,
         );
-        let screen_size = window.inner_size();
+        let back_buffer = Self::create_back_buffer(&mut surface);
+        let scale_factor = window.scale_factor() as f32;
+        let screen_size = Self::get_logical_size(&surface, scale_factor);
 
         Self { 
             surface,
-            screen_size: Size { width: screen_size.width as f32, height: screen_size.height as f32 },
+            screen_size,
+            scale_factor,
             ui_manager: UIManager::new(ui_body),
+            back_buffer,
+            needs_full_redraw: true,
+        }
+    }
+
+    /*
+     * Renders onto a CPU raster surface instead of a GL backed one, 
+     * so the UI can be laid out and drawn without a window or GPU (e.g. in CI).
+     */
+    pub fn new_headless(screen_size: Size, ui_body: Box<dyn Element>) -> Self {
+        Self::new_headless_with_scale_factor(screen_size, 1.0, ui_body)
+    }
+
+    // Forces a device scale factor, e.g. to check HiDPI rendering without a HiDPI display
+    pub fn new_headless_with_scale_factor(screen_size: Size, scale_factor: f32, ui_body: Box<dyn Element>) -> Self {
+        let mut surface = Self::create_raster_surface(screen_size, scale_factor);
+        let back_buffer = Self::create_back_buffer(&mut surface);
+
+        Self {
+            surface,
+            screen_size,
+            scale_factor,
+            ui_manager: UIManager::new(ui_body),
+            back_buffer,
+            needs_full_redraw: true,
         }
     }
 
@@ -45,11 +79,99 @@ impl Renderer {
         );
     }
 
-    pub fn render_frame(&mut self, _gr_context: &mut DirectContext) {
+    /*
+     * Redraws only the damaged regions of the back buffer (everything on the first frame and after a resize),
+     * then presents the back buffer onto the window surface. 
+     * Unchanged element subtrees are replayed from their cached pictures.
+     * Elements are laid out and drawn in logical pixels, the canvas being scaled to device pixels.
+     */
+    pub fn render_frame(&mut self) {
+        let damaged_region = self.compute_damaged_region();
+
+        if !damaged_region.is_empty() {
+            let canvas = self.back_buffer.canvas();
+            canvas.save();
+            canvas.clip_region(&damaged_region, None);
+            canvas.clear(skia_safe::Color::TRANSPARENT);
+            canvas.scale((self.scale_factor, self.scale_factor));
+
+            self.ui_manager.render(canvas);
+
+            canvas.restore();
+            self.needs_full_redraw = false;
+        }
+
         let canvas = self.surface.canvas();
         canvas.clear(skia_safe::Color::TRANSPARENT);
+        self.back_buffer.draw(canvas, (0, 0), SamplingOptions::default(), None);
+    }
+
+    fn compute_damaged_region(&self) -> Region {
+        let surface_bounds = IRect::from_wh(self.back_buffer.width(), self.back_buffer.height());
+        let mut damaged_region = Region::new();
+
+        if self.needs_full_redraw {
+            damaged_region.op_rect(surface_bounds, RegionOp::Union);
+            return damaged_region;
+        }
+
+        for damaged_rect in self.ui_manager.collect_damage() {
+            let damaged_rect: IRect = self.to_device_rect(damaged_rect).round_out();
+            damaged_region.op_rect(damaged_rect, RegionOp::Union);
+        }
+        damaged_region.op_rect(surface_bounds, RegionOp::Intersect);
+
+        damaged_region
+    }
 
-        self.ui_manager.render(canvas);
+    // Scale factor
+    pub fn get_scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    /*
+     * Called when the window moves to a display with a different scale factor.
+     * The window surface is resized separately (on the Resized event that follows).
+     */
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        self.scale_factor = scale_factor;
+        self.screen_size = Self::get_logical_size(&self.surface, scale_factor);
+        self.needs_full_redraw = true;
+    }
+
+    pub fn set_headless_scale_factor(&mut self, scale_factor: f32) {
+        self.scale_factor = scale_factor;
+        self.resize_headless_surface(self.screen_size);
+    }
+
+    // Converts a physical position (e.g. of the cursor) into the logical space the elements are laid out in
+    pub fn to_logical_point(&self, physical_point: skia_safe::Point) -> skia_safe::Point {
+        skia_safe::Point::new(physical_point.x / self.scale_factor, physical_point.y / self.scale_factor)
+    }
+
+    fn to_device_rect(&self, logical_rect: Rect) -> Rect {
+        Rect::new(
+            logical_rect.left * self.scale_factor,
+            logical_rect.top * self.scale_factor,
+            logical_rect.right * self.scale_factor,
+            logical_rect.bottom * self.scale_factor,
+        )
+    }
+
+    fn get_logical_size(surface: &Surface, scale_factor: f32) -> Size {
+        Size {
+            width: surface.width() as f32 / scale_factor,
+            height: surface.height() as f32 / scale_factor,
+        }
+    }
+
+    // Animation
+    pub fn update(&mut self, dt: f32) {
+        self.ui_manager.update(dt);
+    }
+
+    pub fn needs_animation_frame(&self) -> bool {
+        self.ui_manager.needs_animation_frame()
     }
 
     pub fn handle_event(&mut self, cursor_position: skia_safe::Point, event_type: EventType) {
@@ -60,6 +182,73 @@ impl Renderer {
         self.ui_manager.propagate_event(cursor_position, event_type)
     }
 
+    pub fn propagate_overlay_event(&mut self, cursor_position: skia_safe::Point, event_type: &EventType) -> bool {
+        self.ui_manager.propagate_overlay_event(cursor_position, event_type)
+    }
+
+    pub fn dispatch_mouse_event(&mut self, mouse_event: &MouseEvent) -> bool {
+        self.ui_manager.dispatch_mouse_event(mouse_event)
+    }
+
+    pub fn get_cursor(&mut self) -> Cursor {
+        self.ui_manager.get_cursor()
+    }
+
+    pub fn dispatch_keyboard_event(&mut self, event_type: &EventType) {
+        self.ui_manager.dispatch_keyboard_event(event_type);
+    }
+
+    // Scrolling
+    pub fn handle_scroll_request(&mut self, request: &ScrollRequest) -> bool {
+        self.ui_manager.handle_scroll_request(request)
+    }
+
+    // Focus
+    pub fn get_focused_element_id(&self) -> Option<&String> {
+        self.ui_manager.get_focused_element_id()
+    }
+
+    pub fn focus_element(&mut self, template_id: &String) -> bool {
+        self.ui_manager.focus_element(template_id)
+    }
+
+    pub fn blur(&mut self) {
+        self.ui_manager.blur();
+    }
+
+    pub fn focus_at(&mut self, cursor_position: skia_safe::Point) {
+        self.ui_manager.focus_at(cursor_position);
+    }
+
+    // Pixel readback
+    /*
+     * Returns the current surface contents as tightly packed RGBA8888 (unpremultiplied) pixels, 
+     * independently of the platform's native surface color type.
+     */
+    pub fn read_pixels(&mut self) -> Option<Vec<u8>> {
+        let image_info = ImageInfo::new(
+            (self.surface.width(), self.surface.height()), 
+            ColorType::RGBA8888,
+            AlphaType::Unpremul,
+            None
+        );
+        let row_bytes = image_info.min_row_bytes();
+        let mut pixels = vec![0u8; row_bytes * self.surface.height() as usize];
+
+        if !self.surface.read_pixels(&image_info, &mut pixels, row_bytes, (0, 0)) {
+            return None;
+        }
+
+        Some(pixels)
+    }
+
+    pub fn encode_png(&mut self) -> Option<Vec<u8>> {
+        let image = self.surface.image_snapshot();
+        let data = image.encode_to_data(EncodedImageFormat::PNG)?;
+
+        Some(data.as_bytes().to_vec())
+    }
+
     pub fn react_to_state_change(&mut self, component_id: String) {
         self.ui_manager.react_to_state_change(component_id);
     }
@@ -80,6 +269,32 @@ impl Renderer {
 
     pub fn resize_surface(&mut self, window: &Window, gr_context: &mut DirectContext, fb_info: FramebufferInfo, sample_count: usize, stencil_bits: usize) {
         self.surface = Renderer::create_or_resize_surface(window, gr_context, fb_info, sample_count, stencil_bits);
+        self.back_buffer = Self::create_back_buffer(&mut self.surface);
+        self.screen_size = Self::get_logical_size(&self.surface, self.scale_factor);
+        self.needs_full_redraw = true;
+    }
+
+    pub fn resize_headless_surface(&mut self, screen_size: Size) {
+        self.surface = Renderer::create_raster_surface(screen_size, self.scale_factor);
+        self.back_buffer = Self::create_back_buffer(&mut self.surface);
+        self.screen_size = screen_size;
+        self.needs_full_redraw = true;
+    }
+
+    // Compatible with the surface (same backend and color type)
+    fn create_back_buffer(surface: &mut Surface) -> Surface {
+        let size = (surface.width(), surface.height());
+
+        surface.new_surface_with_dimensions(size).expect("Failed to create Skia back buffer")
+    }
+
+    fn create_raster_surface(screen_size: Size, scale_factor: f32) -> Surface {
+        let size = (
+            ((screen_size.width * scale_factor).round() as i32).max(1),
+            ((screen_size.height * scale_factor).round() as i32).max(1),
+        );
+
+        surfaces::raster_n32_premul(size).expect("Failed to create Skia raster surface")
     }
 
     fn create_or_resize_surface(
@@ -110,4 +325,28 @@ impl Renderer {
             None,
         ).expect("Failed to create or resize Skia surface")
     }
-}
\ No newline at end of file
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use crate::rendering::elements::container::Container;
+
+    use super::*;
+
+    #[test]
+    fn test_headless_scale_factor_uses_device_pixels() {
+        // Arrange
+        let screen_size = Size { width: 200.0, height: 100.0 };
+
+        // Act
+        let renderer = Renderer::new_headless_with_scale_factor(screen_size, 2.0, Box::new(Container::new()));
+        let logical_point = renderer.to_logical_point(skia_safe::Point::new(100.0, 50.0));
+
+        // Assert
+        assert_eq!((renderer.surface.width(), renderer.surface.height()), (400, 200));
+        assert_eq!(renderer.screen_size, screen_size);
+        assert_eq!(logical_point, skia_safe::Point::new(50.0, 25.0));
+    }
+}
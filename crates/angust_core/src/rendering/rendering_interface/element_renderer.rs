@@ -1,9 +1,14 @@
-use image::DynamicImage;
-use skia_safe::{Canvas, Color, Paint, PaintStyle, Point, Rect, TextBlob};
+use skia_safe::{
+    canvas::SaveLayerRec, color_filters, image_filters::{self, CropRect}, rrect::Corner, BlurStyle, Canvas, ClipOp, Color, ColorMatrix, 
+    ImageFilter, MaskFilter, Matrix, Paint, PaintCap, PaintStyle, Path, PathEffect, PathFillType, Point, RRect, Rect, TextBlob, TileMode, Vector
+};
 
-use crate::rendering::elements::{common_types::{Position, Size}, styles::{Dimension, Directions, FontFamily, FontStyle as CustomFontStyle, FontWeight}};
+use crate::rendering::elements::{
+    common_types::{Position, Size}, 
+    styles::{Border, BorderSide, BorderStyle, BoxShadow, Dimension, Directions, Filter, Filters, FontFamily, FontStyle as CustomFontStyle, FontWeight, Overflow, Styles, Unit}
+};
 
-use super::skia_boundary::{self, get_skia_font_by_styles};
+use super::skia_boundary::get_skia_font_by_styles;
 
 
 pub struct ElementRenderer {
@@ -11,82 +16,499 @@ pub struct ElementRenderer {
 }
 
 impl ElementRenderer {
+    // Effects
+    /*
+     * Applies the element's transform, backdrop filter, opacity and filters to the canvas before the element (and its children) are drawn,
+     * then draws its outer box shadows. Returns the save count to pass to end_effects afterwards.
+     */
+    pub fn begin_effects(canvas: &Canvas, position: Position, size: Size, styles: &Styles) -> usize {
+        let save_count = canvas.save();
+
+        if let Some(matrix) = Self::get_transform_matrix(position, size, styles) {
+            canvas.concat(&matrix);
+        }
+
+        if let Some(backdrop_filter) = styles.backdrop_filters.and_then(|filters| Self::get_image_filter(&filters)) {
+            Self::render_backdrop_filter(canvas, position, size, styles, &backdrop_filter);
+        }
+
+        // The element is drawn into a layer, which is blended back with the opacity and filters on restore
+        let opacity = styles.opacity.unwrap_or(1.0);
+        let image_filter = styles.filters.and_then(|filters| Self::get_image_filter(&filters));
+        if opacity < 1.0 || image_filter.is_some() {
+            let mut layer_paint = Paint::default();
+            layer_paint.set_alpha_f(opacity);
+            layer_paint.set_image_filter(image_filter);
+            canvas.save_layer(&SaveLayerRec::default().paint(&layer_paint));
+        }
+
+        Self::render_outer_shadows(canvas, position, size, styles);
+
+        save_count
+    }
+
+    pub fn end_effects(canvas: &Canvas, save_count: usize) {
+        canvas.restore_to_count(save_count);
+    }
+
+    // Maps the bounds of an element's content through its effects: grown by its outer shadows and blur, then transformed
+    pub fn transform_bounds(bounds: Rect, position: Position, size: Size, styles: &Styles) -> Rect {
+        let bounds = Self::outset_by_effects(bounds, position, size, styles);
+
+        match Self::get_transform_matrix(position, size, styles) {
+            Some(matrix) => matrix.map_rect(bounds).0,
+            None => bounds,
+        }
+    }
+
+    fn outset_by_effects(bounds: Rect, position: Position, size: Size, styles: &Styles) -> Rect {
+        let mut effect_bounds = bounds;
+
+        if let Some(box_shadows) = styles.box_shadows {
+            let border_box = Rect::from_xywh(position.x, position.y, size.width, size.height);
+            for box_shadow in box_shadows.iter().filter(|box_shadow| !box_shadow.inset) {
+                let extent = box_shadow.spread_radius.value + Self::get_blur_extent(box_shadow.blur_radius.value / 2.0);
+                effect_bounds.join(border_box
+                    .with_outset((extent, extent))
+                    .with_offset((box_shadow.offset_x.value, box_shadow.offset_y.value)));
+            }
+        }
+
+        if let Some(filters) = styles.filters {
+            let blur_extent: f32 = filters.iter()
+                .map(|filter| match filter {
+                    Filter::Blur(deviation) => Self::get_blur_extent(deviation.value),
+                    _ => 0.0,
+                })
+                .sum();
+            effect_bounds = effect_bounds.with_outset((blur_extent, blur_extent));
+        }
+
+        effect_bounds
+    }
+
+    // Distance a Gaussian blur visibly spreads over
+    fn get_blur_extent(sigma: f32) -> f32 {
+        3.0 * sigma.max(0.0)
+    }
+
+    // Chains the filters into a Skia image filter, each one taking the previous one's output. None if there's nothing to apply
+    fn get_image_filter(filters: &Filters) -> Option<ImageFilter> {
+        let mut image_filter: Option<ImageFilter> = None;
+
+        for filter in filters.iter() {
+            let mut color_matrix = ColorMatrix::default();
+            image_filter = match filter {
+                Filter::Blur(deviation) => {
+                    image_filters::blur((deviation.value, deviation.value), TileMode::Decal, image_filter, CropRect::NO_CROP_RECT)
+                },
+                Filter::Grayscale(amount) => {
+                    color_matrix.set_saturation(1.0 - amount);
+                    image_filters::color_filter(color_filters::matrix(&color_matrix, None), image_filter, CropRect::NO_CROP_RECT)
+                },
+                Filter::Brightness(amount) => {
+                    color_matrix.set_scale(*amount, *amount, *amount, None);
+                    image_filters::color_filter(color_filters::matrix(&color_matrix, None), image_filter, CropRect::NO_CROP_RECT)
+                },
+            };
+        }
+
+        image_filter
+    }
+
+    // Replaces what's behind the element's border box by its filtered version (eg. frosted glass)
+    fn render_backdrop_filter(canvas: &Canvas, position: Position, size: Size, styles: &Styles, backdrop_filter: &ImageFilter) {
+        let border_rrect = Self::get_border_rrect(position, size, &styles.border.unwrap_or_default());
+
+        canvas.save();
+        canvas.clip_rrect(border_rrect, None, true);
+        canvas.save_layer(&SaveLayerRec::default().backdrop(backdrop_filter));
+        canvas.restore();
+        canvas.restore();
+    }
+
+    /*
+     * Draws the shadows cast around the element's border box, the first one on top as in CSS.
+     * They are clipped out of the border box, so that they don't show through a transparent background.
+     */
+    fn render_outer_shadows(canvas: &Canvas, position: Position, size: Size, styles: &Styles) {
+        if let Some(box_shadows) = styles.box_shadows {
+            let border_rrect = Self::get_border_rrect(position, size, &styles.border.unwrap_or_default());
+
+            canvas.save();
+            canvas.clip_rrect(border_rrect, ClipOp::Difference, true);
+            for box_shadow in box_shadows.iter().rev().filter(|box_shadow| !box_shadow.inset) {
+                let spread = box_shadow.spread_radius.value;
+                let shadow_rrect = border_rrect
+                    .with_outset((spread, spread))
+                    .with_offset((box_shadow.offset_x.value, box_shadow.offset_y.value));
+
+                canvas.draw_rrect(shadow_rrect, &Self::get_shadow_paint(box_shadow, styles));
+            }
+            canvas.restore();
+        }
+    }
+
+    /*
+     * Draws the inset shadows within the element's padding box, over its background but under its content.
+     * Each one is cast by the area around the padding box, offset and shrunk by the spread radius.
+     */
+    pub fn render_inset_shadows(canvas: &Canvas, position: Position, size: Size, styles: &Styles) {
+        if let Some(box_shadows) = styles.box_shadows {
+            let border = styles.border.unwrap_or_default();
+            let padding_rrect = Self::inset_rrect(&Self::get_border_rrect(position, size, &border), border.get_sides().map(|side| side.get_width()));
+
+            canvas.save();
+            canvas.clip_rrect(padding_rrect, None, true);
+            for box_shadow in box_shadows.iter().rev().filter(|box_shadow| box_shadow.inset) {
+                let spread = box_shadow.spread_radius.value;
+                let offset = Vector::new(box_shadow.offset_x.value, box_shadow.offset_y.value);
+                let hole_rrect = padding_rrect.with_outset((-spread, -spread)).with_offset(offset);
+
+                // Large enough for the blur not to fade at the outer edges
+                let extent = spread.abs() + offset.x.abs() + offset.y.abs() + Self::get_blur_extent(box_shadow.blur_radius.value / 2.0);
+                let mut shadow_path = Path::new();
+                shadow_path.add_rect(padding_rrect.rect().with_outset((extent, extent)), None);
+                shadow_path.add_rrect(hole_rrect, None);
+                shadow_path.set_fill_type(PathFillType::EvenOdd);
+
+                canvas.draw_path(&shadow_path, &Self::get_shadow_paint(box_shadow, styles));
+            }
+            canvas.restore();
+        }
+    }
+
+    // The blur radius being twice the standard deviation, as in CSS. Without a color, the shadow takes the text color
+    fn get_shadow_paint(box_shadow: &BoxShadow, styles: &Styles) -> Paint {
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_style(PaintStyle::Fill);
+        paint.set_color(box_shadow.color.unwrap_or(styles.text_color.unwrap_or(Color::BLACK)));
+        paint.set_mask_filter(MaskFilter::blur(BlurStyle::Normal, box_shadow.blur_radius.value / 2.0, None));
+        paint
+    }
+
+    // Translation, then rotation, then scaling, around the element's center (percentages being relative to its size)
+    fn get_transform_matrix(position: Position, size: Size, styles: &Styles) -> Option<Matrix> {
+        let transform = styles.transform?;
+        let resolve = |dimension: Dimension, reference: f32| match dimension.unit {
+            Unit::Percent => dimension.value / 100.0 * reference,
+            _ => dimension.value,
+        };
+
+        let center = Point::new(position.x + size.width / 2.0, position.y + size.height / 2.0);
+        let translation = Point::new(resolve(transform.translate_x, size.width), resolve(transform.translate_y, size.height));
+
+        let mut matrix = Matrix::translate(center + translation);
+        matrix.pre_rotate(transform.rotate, None);
+        matrix.pre_scale((transform.scale_x, transform.scale_y), None);
+        matrix.pre_translate(-center);
+
+        Some(matrix)
+    }
+
+    /*
+     * Draws an element's background and border, both rounded by the border radius.
+     * A uniform border is stroked in one go, so that dashes run continuously around the corners;
+     * otherwise each side is drawn within its own trapezoid of the border area, with its own width, color and style.
+     * Sides without a color take the text color, as in CSS.
+     */
     pub fn render_element(
         canvas: &Canvas,
         position: Position, 
         size: Size, 
         background_color: Color,
-        border_width: Dimension,
-        border_color: Color
+        border: &Border,
+        text_color: Color,
     ) {
         if (size.width <= 0.0) || (size.height <= 0.0) {
             return;
         }
 
-        let row_rect = Rect::from_point_and_size(
-            Point::new(position.x,
-                       position.y),
-            (size.width,
-             size.height)
-        );
+        let outer_rrect = Self::get_border_rrect(position, size, border);
         let mut paint = Paint::default();
         paint.set_anti_alias(true);
         paint.set_style(PaintStyle::Fill);
         paint.set_color(background_color);
-        canvas.draw_rect(row_rect, &paint);
-         
-        if border_width.value == 0.0 {
-            return; // Draw all borders for now for debugging
+        canvas.draw_rrect(outer_rrect, &paint);
+
+        if border.horizontal() == 0.0 && border.vertical() == 0.0 {
+            return;
+        }
+        let center_rrect = Self::inset_rrect(&outer_rrect, border.get_sides().map(|side| side.get_width() / 2.0));
+
+        if border.is_uniform() {
+            Self::render_border_side(canvas, &outer_rrect, &center_rrect, border.top, text_color);
+            return;
+        }
+
+        let inner_rect = *Self::inset_rrect(&outer_rrect, border.get_sides().map(|side| side.get_width())).rect();
+        let outer_rect = outer_rrect.rect();
+        let corners = [
+            (Point::new(outer_rect.left, outer_rect.top), Point::new(inner_rect.left, inner_rect.top)),
+            (Point::new(outer_rect.right, outer_rect.top), Point::new(inner_rect.right, inner_rect.top)),
+            (Point::new(outer_rect.right, outer_rect.bottom), Point::new(inner_rect.right, inner_rect.bottom)),
+            (Point::new(outer_rect.left, outer_rect.bottom), Point::new(inner_rect.left, inner_rect.bottom)),
+        ];
+
+        for (index, side) in border.get_sides().into_iter().enumerate() {
+            if side.get_width() == 0.0 {
+                continue;
+            }
+
+            // Trapezoid between the side's outer and inner corners
+            let (outer_start, inner_start) = corners[index];
+            let (outer_end, inner_end) = corners[(index + 1) % 4];
+            let mut side_path = Path::new();
+            side_path.move_to(outer_start);
+            side_path.line_to(outer_end);
+            side_path.line_to(inner_end);
+            side_path.line_to(inner_start);
+            side_path.close();
+
+            canvas.save();
+            canvas.clip_path(&side_path, None, true);
+            Self::render_border_side(canvas, &outer_rrect, &center_rrect, side, text_color);
+            canvas.restore();
         }
+    }
+
+    // Strokes the border's center line with the side's width
+    fn render_border_side(canvas: &Canvas, outer_rrect: &RRect, center_rrect: &RRect, side: BorderSide, text_color: Color) {
+        if side.get_width() == 0.0 {
+            return;
+        }
+        let paint = Self::get_border_paint(&side, text_color);
+
+        // Keeps the round caps of the dots within the border box
+        canvas.save();
+        canvas.clip_rrect(outer_rrect, None, true);
+        canvas.draw_rrect(center_rrect, &paint);
+        canvas.restore();
+    }
+
+    // Dashed or dotted with Skia path effects. Without a color, the side takes the text color
+    fn get_border_paint(side: &BorderSide, text_color: Color) -> Paint {
+        let width = side.get_width();
+        let mut paint = Paint::default();
         paint.set_anti_alias(true);
         paint.set_style(PaintStyle::Stroke);
-        paint.set_stroke_width(border_width.value);
+        paint.set_stroke_width(width);
+        paint.set_color(side.color.unwrap_or(text_color));
+
+        match side.style {
+            BorderStyle::Dashed => {
+                paint.set_path_effect(PathEffect::dash(&[3.0 * width, 2.0 * width], 0.0));
+            },
+            BorderStyle::Dotted => {
+                // Zero-length dashes with round caps draw dots as wide as the border
+                paint.set_stroke_cap(PaintCap::Round);
+                paint.set_path_effect(PathEffect::dash(&[0.0, 2.0 * width], 0.0));
+            },
+            _ => {}
+        }
+
+        paint
+    }
+
+    // Border box of an element, with the corners rounded by its border radius (percentages being relative to its size)
+    pub fn get_border_rrect(position: Position, size: Size, border: &Border) -> RRect {
+        let resolve = |dimension: Dimension, reference: f32| match dimension.unit {
+            Unit::Percent => dimension.value / 100.0 * reference,
+            _ => dimension.value,
+        };
+        let radius = border.radius;
+        let radii = [radius.top_left, radius.top_right, radius.bottom_right, radius.bottom_left]
+            .map(|corner_radius| Vector::new(resolve(corner_radius, size.width), resolve(corner_radius, size.height)));
+
+        RRect::new_rect_radii(Rect::from_xywh(position.x, position.y, size.width, size.height), &radii)
+    }
+
+    /*
+     * Shrinks a rounded rectangle by the given top, right, bottom and left insets.
+     * The corners' radii shrink by the adjacent insets, as the inner corners of a CSS border.
+     */
+    fn inset_rrect(rrect: &RRect, insets: [f32; 4]) -> RRect {
+        let [top, right, bottom, left] = insets;
+        let rect = rrect.rect();
+        let inner_rect = Rect::new(
+            rect.left + left,
+            rect.top + top,
+            (rect.right - right).max(rect.left + left),
+            (rect.bottom - bottom).max(rect.top + top),
+        );
+
+        let corners = [
+            (Corner::UpperLeft, left, top),
+            (Corner::UpperRight, right, top),
+            (Corner::LowerRight, right, bottom),
+            (Corner::LowerLeft, left, bottom),
+        ];
+        let radii = corners.map(|(corner, inset_x, inset_y)| {
+            let corner_radii = rrect.radii(corner);
+            Vector::new((corner_radii.x - inset_x).max(0.0), (corner_radii.y - inset_y).max(0.0))
+        });
+
+        RRect::new_rect_radii(inner_rect, &radii)
+    }
+
+    // Area within the border and the padding
+    pub fn get_content_box(position: Position, size: Size, styles: &Styles) -> (Position, Size) {
+        let border = styles.border.unwrap_or_default();
+        let padding = styles.padding.unwrap_or_default();
+
+        let content_position = Position {
+            x: position.x + border.left.get_width() + padding.left.value,
+            y: position.y + border.top.get_width() + padding.top.value,
+        };
+        let content_size = Size {
+            width: (size.width - border.horizontal() - padding.horizontal()).max(0.0),
+            height: (size.height - border.vertical() - padding.vertical()).max(0.0),
+        };
+
+        (content_position, content_size)
+    }
+
+    // Restricts the drawing to a rectangle, until the canvas is restored (eg. by end_effects)
+    pub fn clip(canvas: &Canvas, position: Position, size: Size) {
+        let clip_rect = Rect::from_xywh(position.x, position.y, size.width, size.height);
+        canvas.clip_rect(clip_rect, None, true);
+    }
+
+    /*
+     * Clip of the children of an element whose overflow isn't visible: its padding box (within the border),
+     * with the rounded corners of its border radius. None if the overflow is visible.
+     */
+    pub fn get_overflow_clip(position: Position, size: Size, styles: &Styles) -> Option<RRect> {
+        if styles.overflow.unwrap_or_default() == Overflow::Visible {
+            return None;
+        }
+
+        // The inner corners are rounded by the outer radii minus the border widths, as in CSS
+        let border = styles.border.unwrap_or_default();
+        let border_rrect = Self::get_border_rrect(position, size, &border);
+
+        Some(Self::inset_rrect(&border_rrect, border.get_sides().map(|side| side.get_width())))
+    }
+
+    // Restricts the drawing to an overflow clip, until the canvas is restored
+    pub fn clip_overflow(canvas: &Canvas, clip: &RRect) {
+        canvas.clip_rrect(clip, None, true);
+    }
+
+    // Whether a point is within an overflow clip, the areas outside its rounded corners excluded
+    pub fn is_point_in_clip(clip: &RRect, point: Point) -> bool {
+        let rect = clip.rect();
+        if point.x < rect.left || point.x > rect.right || point.y < rect.top || point.y > rect.bottom {
+            return false;
+        }
+
+        let corners = [
+            (Corner::UpperLeft, rect.left, rect.top, 1.0, 1.0),
+            (Corner::UpperRight, rect.right, rect.top, -1.0, 1.0),
+            (Corner::LowerRight, rect.right, rect.bottom, -1.0, -1.0),
+            (Corner::LowerLeft, rect.left, rect.bottom, 1.0, -1.0),
+        ];
+        for (corner, corner_x, corner_y, direction_x, direction_y) in corners {
+            let radii = clip.radii(corner);
+            if radii.x <= 0.0 || radii.y <= 0.0 {
+                continue;
+            }
+
+            // Within the corner's box, the point must be inside the ellipse's quarter
+            let center = Point::new(corner_x + direction_x * radii.x, corner_y + direction_y * radii.y);
+            let is_in_corner_box = (point.x - center.x) * direction_x < 0.0 && (point.y - center.y) * direction_y < 0.0;
+            let normalized_x = (point.x - center.x) / radii.x;
+            let normalized_y = (point.y - center.y) / radii.y;
+            if is_in_corner_box && normalized_x * normalized_x + normalized_y * normalized_y > 1.0 {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    pub fn render_caret(canvas: &Canvas, position: Position, height: f32, color: Color) {
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_style(PaintStyle::Stroke);
+        paint.set_stroke_width(1.0);
+        paint.set_color(color);
+
+        canvas.draw_line(Point::new(position.x, position.y), Point::new(position.x, position.y + height), &paint);
+    }
+
+    // Form controls
+    pub fn render_circle(canvas: &Canvas, center: Point, radius: f32, fill_color: Color, border_width: f32, border_color: Color) {
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_style(PaintStyle::Fill);
+        paint.set_color(fill_color);
+        canvas.draw_circle(center, radius, &paint);
+
+        if border_width == 0.0 {
+            return;
+        }
+        paint.set_style(PaintStyle::Stroke);
+        paint.set_stroke_width(border_width);
         paint.set_color(border_color);
-        canvas.draw_rect(row_rect, &paint);
+        canvas.draw_circle(center, radius - border_width / 2.0, &paint);
     }
 
+    // Check mark of a checked checkbox, within the given box
+    pub fn render_check_mark(canvas: &Canvas, position: Position, size: Size, color: Color) {
+        let mut path = Path::new();
+        path.move_to(Point::new(position.x + size.width * 0.2, position.y + size.height * 0.5));
+        path.line_to(Point::new(position.x + size.width * 0.42, position.y + size.height * 0.72));
+        path.line_to(Point::new(position.x + size.width * 0.8, position.y + size.height * 0.28));
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_style(PaintStyle::Stroke);
+        paint.set_stroke_width((size.width * 0.15).max(1.5));
+        paint.set_color(color);
+        canvas.draw_path(&path, &paint);
+    }
+
+    // Downward arrow of a select, centered on the given point
+    pub fn render_dropdown_arrow(canvas: &Canvas, center: Point, width: f32, color: Color) {
+        let mut path = Path::new();
+        path.move_to(Point::new(center.x - width / 2.0, center.y - width / 4.0));
+        path.line_to(Point::new(center.x, center.y + width / 4.0));
+        path.line_to(Point::new(center.x + width / 2.0, center.y - width / 4.0));
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_style(PaintStyle::Stroke);
+        paint.set_stroke_width(1.5);
+        paint.set_color(color);
+        canvas.draw_path(&path, &paint);
+    }
+
+    /*
+     * Draws a scrollbar's track and thumb, the thumb being inset across the scrollbar's thickness.
+     * The rectangles are computed by the scrollbar movement handler, so that they match the ones it hit-tests.
+     */
     pub fn render_scrollbar(
         canvas: &Canvas,
-        position: Position,
-        size: Size,
-        _: Directions,
-        current_position: f32, // Between 0.0 and 1.0
-        thumb_scrollbar_width_ratio: f32,
+        track_rect: Rect,
+        thumb_rect: Rect,
+        direction: Directions,
     ) {
         // Draw outer rectangle
         let mut paint = Paint::default();
-        let scrollbar_rect = Rect::from_point_and_size(
-            Point::new(position.x,
-                       position.y),
-            (size.width,
-             size.height)
-        );
         paint.set_style(PaintStyle::Fill);
         paint.set_color(Color::from_argb(255, 200, 200, 200));
 
-        canvas.draw_rect(scrollbar_rect, &paint);
+        canvas.draw_rect(track_rect, &paint);
 
         // Draw thumb
-        let thumb_width = size.width * thumb_scrollbar_width_ratio;
-        let thumb_size = Size {
-            width: thumb_width,
-            height: size.height * 0.8,
-        };
-        let max_left_position = position.x;
-        let max_right_position = position.x + size.width - thumb_width;
-
-        let thumb_position = Position {
-            x: max_left_position + (current_position * (max_right_position - max_left_position)),
-            y: position.y + size.height * 0.1,
+        let thumb_rect = if direction.vertical {
+            thumb_rect.with_inset((thumb_rect.width() * 0.1, 0.0))
+        } else {
+            thumb_rect.with_inset((0.0, thumb_rect.height() * 0.1))
         };
 
-        let thumb_rect = Rect::from_point_and_size(
-            Point::new(thumb_position.x,
-                       thumb_position.y),
-            (thumb_size.width,
-             thumb_size.height)
-        );
-
         paint.set_color(Color::from_argb(255, 100, 100, 100));
         canvas.draw_rect(thumb_rect, &paint);
     }
@@ -118,29 +540,111 @@ impl ElementRenderer {
     }
 
     pub fn render_image(
-        image: &DynamicImage,
+        skia_image: &skia_safe::Image,
         canvas: &Canvas,
         position: Position,
         size: Size,
     ) {
-        let skia_image = skia_boundary::dynamic_image_to_skia_image(&image);
-        if let Some(skia_image) = skia_image {
-            // Calculate the drawing destination based on position and size
-            let src_rect = skia_safe::Rect::from_wh(skia_image.width() as f32, skia_image.height() as f32);
-            let dst_rect = skia_safe::Rect::from_xywh(
-                position.x, 
-                position.y, 
-                size.width, 
-                size.height
-            );
-
-            // Draw the image
-            canvas.draw_image_rect(
-                &skia_image, 
-                Some((&src_rect, skia_safe::canvas::SrcRectConstraint::Fast)), 
-                dst_rect, 
-                &skia_safe::Paint::default()
-            );
-        }
+        // Calculate the drawing destination based on position and size
+        let src_rect = skia_safe::Rect::from_wh(skia_image.width() as f32, skia_image.height() as f32);
+        let dst_rect = skia_safe::Rect::from_xywh(
+            position.x, 
+            position.y, 
+            size.width, 
+            size.height
+        );
+
+        // Draw the image, filtered since it is scaled to device pixels
+        let sampling = skia_safe::SamplingOptions::new(skia_safe::FilterMode::Linear, skia_safe::MipmapMode::Linear);
+        canvas.draw_image_rect_with_sampling_options(
+            skia_image, 
+            Some((&src_rect, skia_safe::canvas::SrcRectConstraint::Fast)), 
+            dst_rect, 
+            sampling,
+            &skia_safe::Paint::default()
+        );
+    }
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use crate::rendering::elements::styles::{BorderRadius, BoxShadows};
+
+    use super::*;
+
+    #[test]
+    fn test_overflow_clip_excludes_border_and_rounded_corners() {
+        // Arrange
+        let radius = Dimension { value: 20.0, unit: Unit::Px };
+        let styles = Styles {
+            overflow: Some(Overflow::Hidden),
+            border: Some(Border {
+                radius: BorderRadius { top_left: radius, top_right: radius, bottom_right: radius, bottom_left: radius },
+                ..Border::uniform(BorderSide { width: Dimension { value: 2.0, unit: Unit::Px }, ..Default::default() })
+            }),
+            ..Default::default()
+        };
+
+        // Act
+        let clip = ElementRenderer::get_overflow_clip(Position { x: 0.0, y: 0.0 }, Size { width: 100.0, height: 100.0 }, &styles).unwrap();
+
+        // Assert
+        assert_eq!(*clip.rect(), Rect::from_xywh(2.0, 2.0, 96.0, 96.0));
+        assert!(ElementRenderer::is_point_in_clip(&clip, Point::new(50.0, 3.0)));
+        assert!(!ElementRenderer::is_point_in_clip(&clip, Point::new(4.0, 4.0))); // Outside the top-left corner's curve
+        assert!(!ElementRenderer::is_point_in_clip(&clip, Point::new(50.0, 1.0))); // On the border
+        assert!(ElementRenderer::is_point_in_clip(&clip, Point::new(20.0, 20.0)));
+    }
+
+    #[test]
+    fn test_bounds_grow_by_outer_shadows_and_blur() {
+        // Arrange
+        let px = |value: f32| Dimension { value, unit: Unit::Px };
+        let mut box_shadows = BoxShadows::default();
+        box_shadows.push(BoxShadow { offset_x: px(10.0), blur_radius: px(4.0), spread_radius: px(2.0), ..Default::default() }).unwrap();
+        box_shadows.push(BoxShadow { offset_x: px(-50.0), blur_radius: px(20.0), inset: true, ..Default::default() }).unwrap();
+        let mut filters = Filters::default();
+        filters.push(Filter::Blur(px(1.0))).unwrap();
+
+        let styles = Styles { box_shadows: Some(box_shadows), filters: Some(filters), ..Default::default() };
+        let (position, size) = (Position { x: 0.0, y: 0.0 }, Size { width: 100.0, height: 50.0 });
+
+        // Act
+        let bounds = ElementRenderer::transform_bounds(Rect::from_xywh(0.0, 0.0, 100.0, 50.0), position, size, &styles);
+
+        // Assert
+        assert_eq!(bounds, Rect::new(-3.0, -11.0, 121.0, 61.0)); // Shadow: spread 2 and 3 sigmas of 2, offset by 10; then 3 sigmas of the filter
+    }
+
+    #[test]
+    fn test_shadow_without_color_takes_final_text_color() {
+        // Arrange
+        let styles = Styles { text_color: Some(Color::BLUE), ..Default::default() };
+        let colored_shadow = BoxShadow { color: Some(Color::RED), ..Default::default() };
+
+        // Act
+        let paint = ElementRenderer::get_shadow_paint(&BoxShadow::default(), &styles);
+        let colored_paint = ElementRenderer::get_shadow_paint(&colored_shadow, &styles);
+
+        // Assert
+        assert_eq!(paint.color(), Color::BLUE);
+        assert_eq!(colored_paint.color(), Color::RED);
+    }
+
+    #[test]
+    fn test_border_without_color_takes_text_color() {
+        // Arrange
+        let side = BorderSide { width: Dimension { value: 1.0, unit: Unit::Px }, ..Default::default() };
+        let colored_side = BorderSide { color: Some(Color::RED), ..side };
+
+        // Act
+        let paint = ElementRenderer::get_border_paint(&side, Color::BLUE);
+        let colored_paint = ElementRenderer::get_border_paint(&colored_side, Color::BLUE);
+
+        // Assert
+        assert_eq!(paint.color(), Color::BLUE);
+        assert_eq!(colored_paint.color(), Color::RED);
     }
 }
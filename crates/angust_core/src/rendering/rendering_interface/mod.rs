@@ -1,2 +1,3 @@
 pub mod element_renderer;
-pub mod skia_boundary;
\ No newline at end of file
+pub mod skia_boundary;
+pub mod render_cache;
\ No newline at end of file
@@ -0,0 +1,117 @@
+use std::cell::{Cell, RefCell};
+
+use skia_safe::{Canvas, Picture, PictureRecorder, Rect};
+
+
+/*
+ * Retained display list of an element subtree.
+ * The subtree is recorded into a Skia Picture, which is replayed on later frames
+ * until the element is invalidated (styles, layout or content changes) or one of its descendants is.
+ * The bounds painted last time are kept, so that the renderer can compute the damaged regions of a frame.
+ */
+pub struct RenderCache {
+    picture: RefCell<Option<Picture>>,
+    painted_bounds: Cell<Option<Rect>>,
+    is_stale: Cell<bool>,
+    has_stale_descendants: Cell<bool>,
+}
+
+impl Default for RenderCache {
+    fn default() -> Self {
+        Self {
+            picture: RefCell::new(None),
+            painted_bounds: Cell::new(None),
+            is_stale: Cell::new(true),
+            has_stale_descendants: Cell::new(false),
+        }
+    }
+}
+
+impl RenderCache {
+    // Invalidation
+    pub fn invalidate(&self) {
+        self.is_stale.set(true);
+    }
+
+    pub fn mark_descendants_stale(&self) {
+        self.has_stale_descendants.set(true);
+    }
+
+    pub fn is_stale(&self) -> bool {
+        self.is_stale.get()
+    }
+
+    pub fn needs_repaint(&self) -> bool {
+        self.is_stale.get() || self.has_stale_descendants.get()
+    }
+
+    // Rendering
+    /*
+     * Replays the cached picture, recording it first with the given draw function if needed.
+     * The visual bounds should cover everything the draw function paints.
+     */
+    pub fn render(&self, canvas: &Canvas, visual_bounds: Rect, draw: impl FnOnce(&Canvas)) {
+        if self.needs_repaint() || self.picture.borrow().is_none() {
+            let mut recorder = PictureRecorder::new();
+            let recording_canvas = recorder.begin_recording(visual_bounds, None);
+            draw(recording_canvas);
+
+            *self.picture.borrow_mut() = recorder.finish_recording_as_picture(None);
+            self.mark_painted(visual_bounds);
+        }
+
+        if let Some(picture) = self.picture.borrow().as_ref() {
+            canvas.draw_picture(picture, None, None);
+        }
+    }
+
+    // For elements drawn directly, without recording a picture
+    pub fn mark_painted(&self, visual_bounds: Rect) {
+        self.painted_bounds.set(Some(visual_bounds));
+        self.is_stale.set(false);
+        self.has_stale_descendants.set(false);
+    }
+
+    pub fn get_painted_bounds(&self) -> Option<Rect> {
+        self.painted_bounds.get()
+    }
+
+    // Damage tracking
+    /*
+     * Adds both the previously painted bounds and the new visual bounds of an invalidated element,
+     * so that the old content is erased and the new one drawn.
+     */
+    pub fn collect_own_damage(&self, visual_bounds: Rect, damaged_rects: &mut Vec<Rect>) {
+        if let Some(painted_bounds) = self.painted_bounds.get() {
+            damaged_rects.push(painted_bounds);
+        }
+        damaged_rects.push(visual_bounds);
+    }
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalidated_cache_damages_old_and_new_bounds() {
+        // Arrange
+        let render_cache = RenderCache::default();
+        let old_bounds = Rect::from_xywh(0.0, 0.0, 100.0, 50.0);
+        let new_bounds = Rect::from_xywh(0.0, 20.0, 100.0, 50.0);
+        render_cache.mark_painted(old_bounds);
+
+        // Act
+        let needs_repaint_before = render_cache.needs_repaint();
+        render_cache.invalidate();
+        let mut damaged_rects = Vec::new();
+        render_cache.collect_own_damage(new_bounds, &mut damaged_rects);
+
+        // Assert
+        assert!(!needs_repaint_before);
+        assert!(render_cache.needs_repaint());
+        assert_eq!(damaged_rects, vec![old_bounds, new_bounds]);
+    }
+}
@@ -27,6 +27,7 @@ pub fn estimate_text_size(
     }
 }
 
+// Lines of displayed text, its whitespace collapsing into single spaces between the words (as in HTML)
 pub fn determine_text_lines(
     text_content: &str,
     font_size: f32, 
@@ -39,42 +40,126 @@ pub fn determine_text_lines(
     let mut paint = Paint::default();
     paint.set_anti_alias(true);
 
-    let lines = calculate_text_lines(text_content, &font, &paint, max_width);
+    let words: Vec<&str> = text_content.split_whitespace().collect();
+    let chars: Vec<char> = words.join(" ").chars().collect();
 
-    lines
+    calculate_text_lines(&chars, &font, &paint, max_width).into_iter()
+        .map(|(line_start, line_end)| chars[line_start..line_end].iter().collect::<String>().trim_end().to_string())
+        .collect()
 }
 
-fn calculate_text_lines(
+/*
+ * Wraps editable text like determine_text_lines, but keeps its whitespace (trailing the words) and line breaks,
+ * returning the range of characters of each line, line breaks excluded, so that the lines map back to the text.
+ */
+pub fn determine_editable_text_lines(
     text_content: &str,
+    font_size: f32, 
+    font_weight: CustomFontWeight, 
+    font_family: FontFamily, 
+    font_style: CustomFontStyle,
+    max_width: f32,
+) -> Vec<(usize, usize)> {
+    let font = get_skia_font_by_styles(font_size, font_weight, font_family, font_style);
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+
+    let mut line_ranges = Vec::new();
+    let mut paragraph_start = 0;
+
+    for paragraph in text_content.split('\n') {
+        let chars: Vec<char> = paragraph.chars().collect();
+        let paragraph_line_ranges = calculate_text_lines(&chars, &font, &paint, max_width);
+        if paragraph_line_ranges.is_empty() {
+            line_ranges.push((paragraph_start, paragraph_start)); // Empty lines are kept, eg. to place the caret on them
+        }
+
+        for (line_start, line_end) in paragraph_line_ranges {
+            line_ranges.push((paragraph_start + line_start, paragraph_start + line_end));
+        }
+        paragraph_start += chars.len() + 1;
+    }
+
+    line_ranges
+}
+
+/*
+ * Ranges of characters of the lines of a paragraph wrapped to max_width, each word keeping the whitespace after it.
+ * Lines are measured without their trailing whitespace, and words aren't broken: a word wider than max_width overflows its own line.
+ */
+fn calculate_text_lines(
+    chars: &[char],
     font: &Font,
     paint: &Paint,
     max_width: f32,
-) -> Vec<String> {
-    let mut lines = Vec::new();
-    let mut current_line = String::new();
-
-    for word in text_content.split_whitespace() {
-        let test_line = if current_line.is_empty() {
-            word.to_string()
-        } else {
-            format!("{} {}", current_line, word)
-        };
-
-        let (_, rect) = font.measure_str(&test_line, Some(paint));
-
-        if rect.width() > max_width {
-            lines.push(current_line);
-            current_line = word.to_string();
-        } else {
-            current_line = test_line;
+) -> Vec<(usize, usize)> {
+    let mut line_ranges = Vec::new();
+    let mut line_start = 0;
+    let mut line_end = 0; // After the last word fitting on the line
+
+    while line_end < chars.len() {
+        let mut word_end = line_end;
+        while word_end < chars.len() && !chars[word_end].is_whitespace() {
+            word_end += 1;
+        }
+        while word_end < chars.len() && chars[word_end].is_whitespace() {
+            word_end += 1;
+        }
+
+        let test_line: String = chars[line_start..word_end].iter().collect();
+        let (_, rect) = font.measure_str(test_line.trim_end(), Some(paint));
+
+        if rect.width() > max_width && line_end > line_start {
+            line_ranges.push((line_start, line_end));
+            line_start = line_end;
         }
+        line_end = word_end;
     }
 
-    if !current_line.is_empty() {
-        lines.push(current_line);
+    if line_start < chars.len() {
+        line_ranges.push((line_start, chars.len()));
     }
 
-    lines
+    line_ranges
+}
+
+// Horizontal offset of each caret position within a line of text, from before the first character to after the last
+pub fn get_caret_offsets(
+    text: &str,
+    font_size: f32, 
+    font_weight: CustomFontWeight, 
+    font_family: FontFamily, 
+    font_style: CustomFontStyle
+) -> Vec<f32> {
+    let font = get_skia_font_by_styles(font_size, font_weight, font_family, font_style);
+
+    // One glyph per character
+    let glyphs = font.str_to_glyphs_vec(text);
+    let mut widths = vec![0.0; glyphs.len()];
+    font.get_widths(&glyphs, &mut widths);
+
+    let mut caret_offsets = Vec::with_capacity(widths.len() + 1);
+    let mut offset = 0.0;
+    caret_offsets.push(offset);
+    for width in widths {
+        offset += width;
+        caret_offsets.push(offset);
+    }
+
+    caret_offsets
+}
+
+// Distance from the top of a line to its baseline, and height of the line
+pub fn get_line_metrics(
+    font_size: f32, 
+    font_weight: CustomFontWeight, 
+    font_family: FontFamily, 
+    font_style: CustomFontStyle
+) -> (f32, f32) {
+    let font = get_skia_font_by_styles(font_size, font_weight, font_family, font_style);
+    let (_, font_metrics) = font.metrics();
+
+    (-font_metrics.ascent, font_metrics.descent - font_metrics.ascent + font_metrics.leading)
 }
 
 pub fn get_skia_font_by_styles(
@@ -88,10 +173,15 @@ pub fn get_skia_font_by_styles(
     let weight = map_custom_to_skia_font_weight(&font_weight);
     let font_style = FontStyle::new(weight, Width::from(20), slant);
     
+    // Fall back to the default typeface when the family is missing (e.g. headless CI containers)
     let typeface = font_mgr.match_family_style(font_family.to_string(), font_style)
+        .or_else(|| font_mgr.legacy_make_typeface(None, font_style))
         .expect("Unable to create typeface");
 
-    Font::new(typeface, font_size)
+    // Subpixel positioning keeps measured and drawn text consistent once the canvas is scaled to device pixels
+    let mut font = Font::new(typeface, font_size);
+    font.set_subpixel(true);
+    font
 }
 
 pub fn map_custom_to_skia_font_style(font_style: &CustomFontStyle) -> Slant {
@@ -128,4 +218,87 @@ pub fn dynamic_image_to_skia_image(image: &DynamicImage) -> Option<skia_safe::Im
     
     // Convert the bitmap to a Skia image
     skia_safe::images::raster_from_data(&image_info, skia_safe::Data::new_copy(&pixel_data), row_bytes)
-}
\ No newline at end of file
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_text_width(text: &str) -> f32 {
+        estimate_text_size(text, 16.0, CustomFontWeight::default(), FontFamily::default(), CustomFontStyle::default()).width
+    }
+
+    fn get_line_ranges(text: &str, max_width: f32) -> Vec<(usize, usize)> {
+        determine_editable_text_lines(text, 16.0, CustomFontWeight::default(), FontFamily::default(), CustomFontStyle::default(), max_width)
+    }
+
+    fn get_lines(text: &str, max_width: f32) -> Vec<String> {
+        determine_text_lines(text, 16.0, CustomFontWeight::default(), FontFamily::default(), CustomFontStyle::default(), max_width)
+    }
+
+    #[test]
+    fn test_text_lines_collapse_whitespace() {
+        // Arrange
+        let text = "  hello \n world   again ";
+        let max_width = get_text_width("hello world");
+
+        // Act
+        let lines = get_lines(text, max_width);
+
+        // Assert
+        assert_eq!(lines, vec!["hello world", "again"]);
+    }
+
+    #[test]
+    fn test_text_lines_start_with_long_word() {
+        // Arrange
+        let text = "verylongword a";
+        let max_width = get_text_width("a");
+
+        // Act
+        let lines = get_lines(text, max_width);
+
+        // Assert
+        assert_eq!(lines, vec!["verylongword", "a"]); // No empty line before the word
+    }
+
+    #[test]
+    fn test_editable_text_lines_split_paragraphs() {
+        // Arrange
+        let text = "ab\n\ncd";
+
+        // Act
+        let line_ranges = get_line_ranges(text, 1000.0);
+
+        // Assert
+        assert_eq!(line_ranges, vec![(0, 2), (3, 3), (4, 6)]); // The line breaks belong to no line
+    }
+
+    #[test]
+    fn test_editable_text_lines_wrap_after_whitespace() {
+        // Arrange
+        let text = "hello world again";
+        let max_width = get_text_width("hello world");
+
+        // Act
+        let line_ranges = get_line_ranges(text, max_width);
+
+        // Assert
+        assert_eq!(line_ranges, vec![(0, 12), (12, 17)]); // The space stays at the end of the first line
+    }
+
+    #[test]
+    fn test_editable_text_lines_keep_long_word_whole() {
+        // Arrange
+        let text = "a verylongword b";
+        let max_width = get_text_width("a");
+
+        // Act
+        let line_ranges = get_line_ranges(text, max_width);
+
+        // Assert
+        assert_eq!(line_ranges, vec![(0, 2), (2, 15), (15, 16)]);
+    }
+}
@@ -1,11 +1,15 @@
 use std::{any::Any, collections::HashMap, sync::Arc};
 
+use skia_safe::Rect;
+
 use crate::{
     application::event_loop_proxy::{get_event_loop_proxy, ApplicationEvent},
     rendering::{
         elements::{
-            button::EventPropagationData, common_types::{OptionalSize, Position, Size}, component::{component::ComponentInterface, component_factory_registry::create_component}, container::Container, element::{Element, ElementType, EventType}, element_id_generator::ElementIDGenerator, styles::Styles
-        }, layout::size_estimation_system::effective_size_estimator, 
+            button::EventPropagationData, common_types::{OptionalSize, Position, Size}, component::{component::ComponentInterface, component_factory_registry::create_component}, container::Container, element::{Element, ElementType, EventType}, element_id_generator::ElementIDGenerator, mouse_event::{EventPhase, MouseEvent}, styles::Styles
+        }, 
+        layout::{layout_cache::{AllocationAction, LayoutCache}, size_estimation_system::effective_size_estimator}, 
+        rendering_interface::render_cache::RenderCache,
     }
 };
 
@@ -19,6 +23,8 @@ pub struct RouterComponent {
     natural_size: Size,
     requested_size: OptionalSize,
     styles: Styles,
+    layout_cache: LayoutCache,
+    render_cache: RenderCache,
 
     current_component: Box<dyn Element>,
     router: RouterProxy,
@@ -36,6 +42,8 @@ impl RouterComponent {
             natural_size: Size::default(),
             requested_size: OptionalSize::default(),
             styles: Styles::default(),
+            layout_cache: LayoutCache::default(),
+            render_cache: RenderCache::default(),
             current_component: Box::new(Container::new()),
             router: get_router(),
             cached_routes: HashMap::new(),
@@ -77,6 +85,8 @@ impl RouterComponent {
         component_box.initialize(inputs);
 
         self.current_component = component_box;
+        self.layout_cache.mark_dirty();
+        self.render_cache.invalidate();
     }
 }
 
@@ -84,10 +94,14 @@ impl Element for RouterComponent {
     
     fn render(&self, canvas: &skia_safe::Canvas) {
         self.current_component.render(canvas);
+        self.render_cache.mark_painted(self.current_component.get_visual_bounds());
     }
 
-    fn update(&mut self) {
-        self.current_component.update();
+    fn update(&mut self, dt: f32) {
+        self.current_component.update(dt);
+        if self.current_component.needs_layout() {
+            self.layout_cache.mark_descendants_dirty();
+        }
     }
     
     fn handle_event(&mut self, cursor_position: skia_safe::Point, event_type: &EventType) {
@@ -98,8 +112,53 @@ impl Element for RouterComponent {
         self.current_component.propagate_event(cursor_position, event_type)
     }
 
+    fn propagate_targeted_event(&mut self, target_id: Option<&String>, event_type: &EventType) -> Option<Vec<EventPropagationData>> {
+        if target_id.map_or(true, |id| *id == self.get_id()) {
+            return Some(vec![]);
+        }
+        self.current_component.propagate_targeted_event(target_id, event_type)
+    }
+
+    fn dispatch_mouse_event(&mut self, path: &[String], mouse_event: &MouseEvent, phase: EventPhase) -> Vec<EventPropagationData> {
+        self.current_component.dispatch_mouse_event(path, mouse_event, phase)
+    }
+
+    // Overlays
+    fn render_overlay(&self, canvas: &skia_safe::Canvas) {
+        self.current_component.render_overlay(canvas);
+    }
+
+    fn propagate_overlay_event(&mut self, cursor_position: skia_safe::Point, event_type: &EventType) -> Option<Vec<EventPropagationData>> {
+        self.current_component.propagate_overlay_event(cursor_position, event_type)
+    }
+
+    // Animation
+    fn needs_animation_frame(&self) -> bool {
+        self.current_component.needs_animation_frame()
+    }
+
+    // Rendering cache
+    fn needs_repaint(&self) -> bool {
+        self.render_cache.is_stale() || self.current_component.needs_repaint()
+    }
+
+    fn get_visual_bounds(&self) -> Rect {
+        self.current_component.get_visual_bounds()
+    }
+
+    // Replaced content (template reload, route change) damages both the old and the new bounds
+    fn collect_damage(&self, damaged_rects: &mut Vec<Rect>) {
+        if self.render_cache.is_stale() {
+            self.render_cache.collect_own_damage(self.get_visual_bounds(), damaged_rects);
+            return;
+        }
+
+        self.current_component.collect_damage(damaged_rects);
+    }
+
     fn add_child(&mut self, child: Box<dyn Element>) {
         self.current_component.add_child(child);
+        self.layout_cache.mark_dirty();
     }
 
     fn set_id(&mut self, id: String) {
@@ -125,6 +184,7 @@ impl Element for RouterComponent {
     fn set_styles(&mut self, styles: Styles) {
         self.styles = styles;
         self.current_component.set_styles(styles);
+        self.layout_cache.mark_dirty();
     }
 
     fn is_text_wrapper(&self) -> bool {
@@ -192,18 +252,44 @@ impl Element for RouterComponent {
 
     // Layout system
     fn estimate_sizes(&mut self) {
+        if !self.layout_cache.needs_estimation() {
+            if let Some((natural_size, requested_size)) = self.layout_cache.get_estimated_sizes() {
+                self.natural_size = natural_size;
+                self.requested_size = requested_size;
+            }
+            return;
+        }
+
         self.current_component.estimate_sizes();
+        if self.current_component.has_estimate_changed() {
+            self.layout_cache.mark_allocation_needed();
+        }
         self.set_natural_size(self.current_component.get_natural_size());
 
         let sizing_policy = self.get_styles().sizing_policy.unwrap_or_default();
         self.set_requested_size(OptionalSize { width: sizing_policy.width, height: sizing_policy.height }); 
+
+        self.layout_cache.store_estimated_sizes(self.natural_size, self.requested_size);
     }
 
     fn allocate_space(&mut self, allocated_position: Position, allocated_size: Size) {
-        self.current_component.set_position(allocated_position);
-        self.current_component.set_size(allocated_size);
+        match self.layout_cache.get_allocation_action(allocated_position, allocated_size) {
+            AllocationAction::Skip => return,
+            AllocationAction::DescendantsOnly => {
+                if self.current_component.needs_layout() {
+                    self.current_component.allocate_pending_space();
+                }
+            },
+            AllocationAction::Full => {
+                self.current_component.estimate_sizes();
+                self.current_component.set_position(allocated_position);
+                self.current_component.set_size(allocated_size);
+
+                self.current_component.allocate_space(allocated_position, allocated_size);
+            }
+        }
 
-        self.current_component.allocate_space(allocated_position, allocated_size);
+        self.layout_cache.store_allocation(allocated_position, allocated_size);
     }
 
     fn layout(&mut self, allocated_position: Position, allocated_size: Size) {
@@ -211,9 +297,31 @@ impl Element for RouterComponent {
         self.allocate_space(allocated_position, allocated_size);
     }
 
+    // Incremental layout
+    fn needs_layout(&self) -> bool {
+        self.layout_cache.needs_layout()
+    }
+
+    fn mark_layout_dirty(&mut self) {
+        self.layout_cache.mark_dirty();
+    }
+
+    fn has_estimate_changed(&self) -> bool {
+        self.layout_cache.has_estimate_changed()
+    }
+
+    fn allocate_pending_space(&mut self) {
+        if let Some((allocated_position, allocated_size)) = self.layout_cache.get_allocation() {
+            self.allocate_space(allocated_position, allocated_size);
+        }
+    }
+
     // Reactivity
     fn react_to_state_change(&mut self, component_id: String) {
         self.current_component.react_to_state_change(component_id);
+        if self.current_component.needs_layout() {
+            self.layout_cache.mark_descendants_dirty();
+        }
     }
     
     
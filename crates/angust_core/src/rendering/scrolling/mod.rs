@@ -0,0 +1,8 @@
+/*
+ * Programmatic scrolling of overflowing containers: components request it through the scroll proxy,
+ * and the scroll manager applies the requests to the element tree.
+ * Containers report their scroll offset through @onscroll handlers, however they were scrolled.
+ */
+
+pub mod scroll_manager;
+pub mod scroll_proxy;
@@ -0,0 +1,203 @@
+use crate::rendering::{
+    elements::{
+        common_types::{Position, ScrollBehavior},
+        element::{Element, ScrollEvent},
+    },
+    focus::focus_manager::{find_element_by_template_id_mut, find_element_mut},
+};
+
+
+/*
+ * Scroll requests sent by components, elements being referenced by the id attribute given in their template.
+ * Offsets are in logical pixels: To scrolls to an offset from the top-left of the content, By scrolls by a delta,
+ * and IntoView scrolls the element's scrollable ancestors until it is visible.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScrollRequest {
+    To(String, Position, ScrollBehavior),
+    By(String, Position, ScrollBehavior),
+    IntoView(String, ScrollBehavior),
+}
+
+// Returns whether the element was found
+pub fn handle_scroll_request(root_element: &mut dyn Element, request: &ScrollRequest) -> bool {
+    match request {
+        ScrollRequest::To(template_id, offset, behavior) => {
+            match find_element_by_template_id_mut(root_element, template_id) {
+                Some(element) => { element.scroll_to(*offset, *behavior); true },
+                None => false,
+            }
+        },
+        ScrollRequest::By(template_id, delta, behavior) => {
+            match find_element_by_template_id_mut(root_element, template_id) {
+                Some(element) => {
+                    let offset = get_scroll_offset(element);
+                    element.scroll_to(Position { x: offset.x + delta.x, y: offset.y + delta.y }, *behavior);
+                    true
+                },
+                None => false,
+            }
+        },
+        ScrollRequest::IntoView(template_id, behavior) => {
+            match find_element_by_template_id_mut(root_element, template_id).map(|element| element.get_id()) {
+                Some(element_id) => scroll_into_view(root_element, &element_id, *behavior),
+                None => false,
+            }
+        },
+    }
+}
+
+/*
+ * Scrolls the overflowing ancestors of the element (by its generated ID), from the innermost one outwards, so that it becomes visible.
+ * The target offsets are all computed before scrolling, the element moving by each inner ancestor's scroll
+ * (outer scrolls moving the inner ancestors along with it), so that only the final offsets are reported to @onscroll.
+ */
+pub fn scroll_into_view(root_element: &mut dyn Element, element_id: &String, behavior: ScrollBehavior) -> bool {
+    let mut path = Vec::new();
+    if !find_element_path(root_element, element_id, &mut path) {
+        return false;
+    }
+    let (mut element_position, element_size) = match find_element_mut(root_element, element_id) {
+        Some(element) => (element.get_position(), element.get_size()),
+        None => return false,
+    };
+
+    let mut scrolls = Vec::new();
+    for ancestor_id in path.iter().rev().skip(1) {
+        let ancestor = match find_element_mut(root_element, ancestor_id) {
+            Some(ancestor) => ancestor,
+            None => continue,
+        };
+        let scrollbar_state = match ancestor.get_scrollbar_state() {
+            Some(scrollbar_state) if scrollbar_state.is_overflowing.horizontal || scrollbar_state.is_overflowing.vertical => *scrollbar_state,
+            _ => continue,
+        };
+
+        // The scrollbars cover the right and bottom edges of the viewport
+        let ancestor_position = ancestor.get_position();
+        let ancestor_size = ancestor.get_size();
+        let viewport_width = ancestor_size.width - if scrollbar_state.is_overflowing.vertical { scrollbar_state.scrollbar_thickness } else { 0.0 };
+        let viewport_height = ancestor_size.height - if scrollbar_state.is_overflowing.horizontal { scrollbar_state.scrollbar_thickness } else { 0.0 };
+
+        let initial_offset = scrollbar_state.get_scroll_offset();
+        let target_offset = Position {
+            x: (initial_offset.x + get_reveal_delta(element_position.x, element_size.width, ancestor_position.x, viewport_width))
+                .clamp(0.0, scrollbar_state.max_scroll_offset.x),
+            y: (initial_offset.y + get_reveal_delta(element_position.y, element_size.height, ancestor_position.y, viewport_height))
+                .clamp(0.0, scrollbar_state.max_scroll_offset.y),
+        };
+        element_position.x -= target_offset.x - initial_offset.x;
+        element_position.y -= target_offset.y - initial_offset.y;
+        scrolls.push((ancestor_id.clone(), target_offset));
+    }
+
+    for (ancestor_id, target_offset) in scrolls {
+        if let Some(ancestor) = find_element_mut(root_element, &ancestor_id) {
+            ancestor.scroll_to(target_offset, behavior);
+        }
+    }
+
+    true
+}
+
+// Scroll events of the containers scrolled since the previous call, with their IDs
+pub fn collect_scroll_events(element: &mut dyn Element, scroll_events: &mut Vec<(String, ScrollEvent)>) {
+    if let Some(scroll_event) = element.take_scroll_event() {
+        scroll_events.push((element.get_id(), scroll_event));
+    }
+
+    for child in element.get_children_mut().unwrap_or(&mut vec![]) {
+        collect_scroll_events(child.as_mut(), scroll_events);
+    }
+}
+
+fn get_scroll_offset(element: &dyn Element) -> Position {
+    element.get_scrollbar_state()
+        .map(|scrollbar_state| scrollbar_state.get_scroll_offset())
+        .unwrap_or_default()
+}
+
+// IDs of the elements from the root to the one with the given ID
+fn find_element_path(element: &mut dyn Element, element_id: &String, path: &mut Vec<String>) -> bool {
+    path.push(element.get_id());
+    if element.get_id() == *element_id {
+        return true;
+    }
+
+    for child in element.get_children_mut().unwrap_or(&mut vec![]) {
+        if find_element_path(child.as_mut(), element_id, path) {
+            return true;
+        }
+    }
+
+    path.pop();
+    false
+}
+
+/*
+ * Distance to scroll along an axis so that a span of the content is visible in the viewport:
+ * aligning its start with the viewport's if it is before it, or its end with the viewport's if it is after it
+ * (its start if it is longer than the viewport).
+ */
+fn get_reveal_delta(start: f32, length: f32, viewport_start: f32, viewport_length: f32) -> f32 {
+    let viewport_end = viewport_start + viewport_length;
+    if start < viewport_start {
+        return start - viewport_start;
+    }
+    if start + length > viewport_end {
+        return (start + length - viewport_end).min(start - viewport_start);
+    }
+
+    0.0
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use crate::rendering::elements::{common_types::Size, container::Container};
+
+    use super::*;
+
+    #[test]
+    fn test_reveal_delta_scrolls_least_distance() {
+        // Arrange
+        let (viewport_start, viewport_length) = (100.0, 200.0);
+
+        // Act
+        let above_delta = get_reveal_delta(40.0, 20.0, viewport_start, viewport_length);
+        let visible_delta = get_reveal_delta(150.0, 20.0, viewport_start, viewport_length);
+        let below_delta = get_reveal_delta(320.0, 50.0, viewport_start, viewport_length);
+        let longer_delta = get_reveal_delta(350.0, 400.0, viewport_start, viewport_length);
+
+        // Assert
+        assert_eq!(above_delta, -60.0);
+        assert_eq!(visible_delta, 0.0);
+        assert_eq!(below_delta, 70.0);
+        assert_eq!(longer_delta, 250.0);
+    }
+
+    #[test]
+    fn test_smooth_scroll_into_view_reports_no_offset_until_animated() {
+        // Arrange
+        let mut list = Container::new();
+        list.set_size(Size { width: 100.0, height: 100.0 });
+        list.scrollbar_state.is_overflowing.vertical = true;
+        list.scrollbar_state.max_scroll_offset = Position { x: 0.0, y: 300.0 };
+
+        let mut item = Container::new();
+        item.template_id = Some(String::from("item"));
+        item.set_position(Position { x: 0.0, y: 250.0 });
+        item.set_size(Size { width: 50.0, height: 20.0 });
+        list.add_child(Box::new(item));
+
+        // Act
+        let is_found = handle_scroll_request(&mut list, &ScrollRequest::IntoView(String::from("item"), ScrollBehavior::Smooth));
+
+        // Assert
+        assert!(is_found);
+        assert!(list.take_scroll_event().is_none());
+        assert_eq!(list.scrollbar_state.get_scroll_offset(), Position { x: 0.0, y: 0.0 });
+        assert!(list.needs_animation_frame());
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{
+    application::event_loop_proxy::{get_event_loop_proxy, ApplicationEvent},
+    rendering::elements::common_types::{Position, ScrollBehavior},
+};
+
+use super::scroll_manager::ScrollRequest;
+
+/*
+ * Proxy allowing components to scroll overflowing containers, eg. to scroll a list back to the top.
+ * Elements are referenced by the id attribute given in their template, and offsets are in logical pixels.
+ */
+pub fn get_scroll_manager() -> ScrollProxy {
+    ScrollProxy
+}
+
+pub struct ScrollProxy;
+
+impl ScrollProxy {
+    pub fn scroll_to(&self, element_id: &str, offset: Position, behavior: ScrollBehavior) {
+        self.send_scroll_request(ScrollRequest::To(element_id.to_string(), offset, behavior));
+    }
+
+    pub fn scroll_by(&self, element_id: &str, delta: Position, behavior: ScrollBehavior) {
+        self.send_scroll_request(ScrollRequest::By(element_id.to_string(), delta, behavior));
+    }
+
+    // Scrolls the containers around the element until it is visible
+    pub fn scroll_into_view(&self, element_id: &str, behavior: ScrollBehavior) {
+        self.send_scroll_request(ScrollRequest::IntoView(element_id.to_string(), behavior));
+    }
+
+    fn send_scroll_request(&self, request: ScrollRequest) {
+        let event_proxy_option = get_event_loop_proxy();
+        if event_proxy_option.is_none() {
+            println!("Event proxy is None");
+            return;
+        }
+
+        event_proxy_option.unwrap().send_event(ApplicationEvent::ScrollRequest(request))
+            .expect("Failed to send event");
+    }
+}
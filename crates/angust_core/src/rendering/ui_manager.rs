@@ -1,29 +1,216 @@
-use super::elements::{button::EventPropagationData, common_types::{Position, Size}, element::{Element, EventType}};
+use skia_safe::Rect;
+
+use super::elements::{
+    button::EventPropagationData, 
+    common_types::{Position, Size}, 
+    element::{Element, EventType}, 
+    event_propagator, 
+    mouse_event::{EventPhase, MouseEvent}, 
+    radio_groups, 
+    styles::Cursor
+};
+use super::focus::focus_manager::FocusManager;
+use super::scrolling::scroll_manager::{self, ScrollRequest};
 
 pub struct UIManager {
     root_element: Box<dyn Element>,
+    focus_manager: FocusManager,
+    hovered_path: Vec<String>, // Elements under the cursor, from the root down
+    active_path: Vec<String>, // Elements under the cursor when the mouse button was pressed, until it's released
 }
 
 impl UIManager {
     pub fn new(root_element: Box<dyn Element>) -> Self {
-        Self { root_element }
+        Self {
+            root_element,
+            focus_manager: FocusManager::new(),
+            hovered_path: Vec::new(),
+            active_path: Vec::new(),
+        }
     }
 
     pub fn render(&mut self, canvas: &skia_safe::Canvas) {
         self.root_element.render(canvas);
+        self.root_element.render_overlay(canvas);
+    }
+
+    pub fn collect_damage(&self) -> Vec<Rect> {
+        let mut damaged_rects = Vec::new();
+        self.root_element.collect_damage(&mut damaged_rects);
+        damaged_rects
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.root_element.update(dt);
+        self.dispatch_scroll_events(); // Of smooth scrolling
     }
 
-    #[allow(dead_code)]
-    pub fn update(&mut self) {
-        self.root_element.update();
+    pub fn needs_animation_frame(&self) -> bool {
+        self.root_element.needs_animation_frame()
     }
 
     pub fn handle_event(&mut self, cursor_position: skia_safe::Point, event_type: &EventType) {
         self.root_element.handle_event(cursor_position, event_type);
+        self.dispatch_scroll_events(); // Scrolling with the wheel or the scrollbars
     }
 
     pub fn propagate_event(&mut self, cursor_position: skia_safe::Point, event_type: &EventType) -> Vec<EventPropagationData> {
-        self.root_element.propagate_event(cursor_position, event_type)
+        let event_targets = self.root_element.propagate_event(cursor_position, event_type);
+        radio_groups::update_radio_groups(self.root_element.as_mut());
+        event_targets
+    }
+
+    // Gives a mouse event to the open overlay (eg. a select's popup), returning whether one took it
+    pub fn propagate_overlay_event(&mut self, cursor_position: skia_safe::Point, event_type: &EventType) -> bool {
+        let is_handled = self.root_element.propagate_overlay_event(cursor_position, event_type).is_some();
+        radio_groups::update_radio_groups(self.root_element.as_mut());
+        is_handled
+    }
+
+    /*
+     * Dispatches a mouse event to the topmost element under the cursor, through the capture and bubble phases (see MouseEvent),
+     * returning whether a handler prevented its default action. Moving the cursor also fires mouseleave and mouseenter
+     * on the elements it left and entered.
+     */
+    pub fn dispatch_mouse_event(&mut self, mouse_event: &MouseEvent) -> bool {
+        let cursor_position = skia_safe::Point::new(mouse_event.position.x, mouse_event.position.y);
+        let path = event_propagator::hit_test(self.root_element.as_mut(), cursor_position);
+        if matches!(mouse_event.event_type, EventType::MouseMove | EventType::MouseDrag) {
+            self.update_hovered_path(&path, mouse_event);
+        }
+        self.update_active_path(&path, &mouse_event.event_type);
+
+        let is_default_prevented = self.dispatch_along_path(&path, mouse_event, &mouse_event.event_type);
+        radio_groups::update_radio_groups(self.root_element.as_mut());
+        is_default_prevented
+    }
+
+    // Cursor shape of the hovered element, set by the deepest element of the hovered path with a cursor style
+    pub fn get_cursor(&mut self) -> Cursor {
+        let mut cursor = Cursor::default();
+        event_propagator::for_each_on_path(self.root_element.as_mut(), &self.hovered_path, 0, &mut |element| {
+            if let Some(element_cursor) = element.get_styles().cursor {
+                cursor = element_cursor;
+            }
+        });
+        cursor
+    }
+
+    // Left elements get mouseleave from the deepest up, then entered ones get mouseenter from the root down
+    fn update_hovered_path(&mut self, path: &Vec<String>, mouse_event: &MouseEvent) {
+        let previous_path = std::mem::replace(&mut self.hovered_path, path.clone());
+        let common_depth = previous_path.iter().zip(path.iter()).take_while(|(previous_id, element_id)| previous_id == element_id).count();
+        self.set_elements_hovered(&previous_path, common_depth, false);
+        self.set_elements_hovered(path, common_depth, true);
+
+        for (index, element_id) in previous_path.iter().enumerate().rev() {
+            if !path.contains(element_id) {
+                self.dispatch_along_path(&previous_path[..=index], mouse_event, &EventType::MouseLeave);
+            }
+        }
+        for (index, element_id) in path.iter().enumerate() {
+            if !previous_path.contains(element_id) {
+                self.dispatch_along_path(&path[..=index], mouse_event, &EventType::MouseEnter);
+            }
+        }
+    }
+
+    fn update_active_path(&mut self, path: &Vec<String>, event_type: &EventType) {
+        match event_type {
+            EventType::MouseDown => {
+                self.active_path = path.clone();
+                self.set_elements_active(path, true);
+            },
+            EventType::MouseUp => {
+                let active_path = std::mem::take(&mut self.active_path);
+                self.set_elements_active(&active_path, false);
+            },
+            _ => (),
+        }
+    }
+
+    // Elements of the path from the given depth down, the ones above it keeping their state
+    fn set_elements_hovered(&mut self, path: &[String], start_depth: usize, is_hovered: bool) {
+        event_propagator::for_each_on_path(self.root_element.as_mut(), path, start_depth, &mut |element| element.set_hovered(is_hovered));
+    }
+
+    fn set_elements_active(&mut self, path: &[String], is_active: bool) {
+        event_propagator::for_each_on_path(self.root_element.as_mut(), path, 0, &mut |element| element.set_active(is_active));
+    }
+
+    // Reloaded templates keep their element IDs (see state_transfer), so the new elements take over the pointer state
+    fn restore_pointer_state(&mut self) {
+        let hovered_path = self.hovered_path.clone();
+        self.set_elements_hovered(&hovered_path, 0, true);
+        let active_path = self.active_path.clone();
+        self.set_elements_active(&active_path, true);
+    }
+
+    fn dispatch_along_path(&mut self, path: &[String], mouse_event: &MouseEvent, event_type: &EventType) -> bool {
+        let target_id = match path.last() {
+            Some(target_id) => target_id.clone(),
+            None => return false,
+        };
+        let target_position = event_propagator::get_target_position(self.root_element.as_mut(), path).unwrap_or_default();
+        let mouse_event = mouse_event.dispatched_to(event_type.clone(), target_id, target_position);
+
+        self.root_element.dispatch_mouse_event(path, &mouse_event, EventPhase::Capture);
+        if !mouse_event.is_propagation_stopped() {
+            self.root_element.dispatch_mouse_event(path, &mouse_event, EventPhase::Bubble);
+        }
+
+        mouse_event.is_default_prevented()
+    }
+
+    /*
+     * Dispatches a keyboard event to the focused element, from which it bubbles up to the root.
+     * If nothing is focused, the root is the target. Tab and Shift+Tab then move the focus.
+     */
+    pub fn dispatch_keyboard_event(&mut self, event_type: &EventType) {
+        let focused_element_id = self.focus_manager.get_focused_element_id().cloned();
+        self.root_element.propagate_targeted_event(focused_element_id.as_ref(), event_type);
+        radio_groups::update_radio_groups(self.root_element.as_mut());
+
+        if let EventType::KeyDown(keyboard_event) = event_type {
+            let modifiers = keyboard_event.modifiers;
+            if keyboard_event.key == "Tab" && !modifiers.ctrl && !modifiers.alt && !modifiers.meta {
+                self.focus_manager.focus_next(self.root_element.as_mut(), modifiers.shift);
+            }
+        }
+    }
+
+    // Scrolling
+    pub fn handle_scroll_request(&mut self, request: &ScrollRequest) -> bool {
+        let is_found = scroll_manager::handle_scroll_request(self.root_element.as_mut(), request);
+        self.dispatch_scroll_events();
+        is_found
+    }
+
+    // Runs the @onscroll handlers of the containers scrolled since the last call
+    fn dispatch_scroll_events(&mut self) {
+        let mut scroll_events = Vec::new();
+        scroll_manager::collect_scroll_events(self.root_element.as_mut(), &mut scroll_events);
+
+        for (element_id, scroll_event) in scroll_events {
+            self.root_element.propagate_targeted_event(Some(&element_id), &EventType::Scroll(scroll_event));
+        }
+    }
+
+    // Focus
+    pub fn get_focused_element_id(&self) -> Option<&String> {
+        self.focus_manager.get_focused_element_id()
+    }
+
+    pub fn focus_element(&mut self, template_id: &String) -> bool {
+        self.focus_manager.focus(self.root_element.as_mut(), template_id)
+    }
+
+    pub fn blur(&mut self) {
+        self.focus_manager.blur(self.root_element.as_mut());
+    }
+
+    pub fn focus_at(&mut self, cursor_position: skia_safe::Point) {
+        self.focus_manager.focus_at(self.root_element.as_mut(), cursor_position);
     }
 
     pub fn layout(&mut self, allocated_position: Position, allocated_size: Size) {
@@ -32,9 +219,91 @@ impl UIManager {
 
     pub fn react_to_state_change(&mut self, component_id: String) {
         self.root_element.react_to_state_change(component_id);
+        self.focus_manager.restore_focus(self.root_element.as_mut());
+        self.restore_pointer_state();
     }
 
     pub fn handle_route_change(&mut self, route: &String, component_name: &String) {
         self.root_element.handle_route_change(route, component_name);
+        self.focus_manager.restore_focus(self.root_element.as_mut());
+        self.restore_pointer_state();
+    }
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use skia_safe::Color;
+
+    use crate::parsing::css::stylesheet_parser::Property;
+    use crate::rendering::{
+        elements::{container::Container, element::KeyModifiers, pseudo_class_styles::PseudoClass, styles::Styles},
+        focus::focus_manager::find_element_mut,
+    };
+
+    use super::*;
+
+    fn create_container(id: &str, position: Position, size: Size, cursor: Option<Cursor>) -> Container {
+        let mut container = Container::new();
+        container.set_id(id.to_string());
+        container.set_position(position);
+        container.set_size(size);
+        container.set_styles(Styles { cursor, ..Default::default() });
+        container
+    }
+
+    fn move_mouse(ui_manager: &mut UIManager, x: f32, y: f32) {
+        let mouse_event = MouseEvent::new(EventType::MouseMove, Position { x, y }, None, KeyModifiers::default());
+        ui_manager.dispatch_mouse_event(&mouse_event);
+    }
+
+    fn is_hovered(ui_manager: &mut UIManager, element_id: &str) -> bool {
+        let element = find_element_mut(ui_manager.root_element.as_mut(), &element_id.to_string()).unwrap();
+        element.get_styles().background_color == Some(Color::RED)
+    }
+
+    #[test]
+    fn test_get_cursor_follows_hovered_element() {
+        // Arrange
+        let mut root_element = create_container("root", Position { x: 0.0, y: 0.0 }, Size { width: 200.0, height: 200.0 }, None);
+        let link = create_container("link", Position { x: 10.0, y: 10.0 }, Size { width: 50.0, height: 20.0 }, Some(Cursor::Pointer));
+        root_element.add_child(Box::new(link));
+        let mut ui_manager = UIManager::new(Box::new(root_element));
+
+        // Act
+        move_mouse(&mut ui_manager, 20.0, 20.0);
+        let link_cursor = ui_manager.get_cursor();
+        move_mouse(&mut ui_manager, 150.0, 150.0);
+        let root_cursor = ui_manager.get_cursor();
+
+        // Assert
+        assert_eq!(link_cursor, Cursor::Pointer);
+        assert_eq!(root_cursor, Cursor::Default);
+    }
+
+    #[test]
+    fn test_hover_moves_along_path() {
+        // Arrange
+        let mut root_element = create_container("root", Position { x: 0.0, y: 0.0 }, Size { width: 200.0, height: 200.0 }, None);
+        let mut card = create_container("card", Position { x: 0.0, y: 0.0 }, Size { width: 100.0, height: 100.0 }, None);
+        let mut link = create_container("link", Position { x: 10.0, y: 10.0 }, Size { width: 50.0, height: 20.0 }, None);
+        for container in [&mut card, &mut link] {
+            let properties = vec![Property { name: String::from("background-color"), value: String::from("rgb(255, 0, 0)") }];
+            container.pseudo_class_styles.add_rule(PseudoClass::Hover, properties);
+        }
+        card.add_child(Box::new(link));
+        root_element.add_child(Box::new(card));
+        let mut ui_manager = UIManager::new(Box::new(root_element));
+
+        // Act
+        move_mouse(&mut ui_manager, 20.0, 20.0);
+        let is_link_hovered = is_hovered(&mut ui_manager, "link");
+        move_mouse(&mut ui_manager, 80.0, 80.0);
+
+        // Assert
+        assert!(is_link_hovered);
+        assert!(!is_hovered(&mut ui_manager, "link"));
+        assert!(is_hovered(&mut ui_manager, "card"));
     }
 }
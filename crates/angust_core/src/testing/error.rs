@@ -0,0 +1,30 @@
+#[derive(Debug, Clone)]
+pub enum SnapshotError {
+    ComponentNotFound(String),
+    RenderingError(String),
+
+    ReferenceNotFound(String),
+    ReferenceCreated(String),
+    IOError(String),
+
+    SizeMismatch(String),
+    PixelMismatch(String),
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SnapshotError::ComponentNotFound(msg) => write!(f, "Component not found: {}", msg),
+            SnapshotError::RenderingError(msg) => write!(f, "Rendering error: {}", msg),
+
+            SnapshotError::ReferenceNotFound(msg) => write!(f, "Reference snapshot not found: {}", msg),
+            SnapshotError::ReferenceCreated(msg) => write!(f, "Reference snapshot created, review it and rerun: {}", msg),
+            SnapshotError::IOError(msg) => write!(f, "IO error: {}", msg),
+
+            SnapshotError::SizeMismatch(msg) => write!(f, "Snapshot size mismatch: {}", msg),
+            SnapshotError::PixelMismatch(msg) => write!(f, "Snapshot pixel mismatch: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
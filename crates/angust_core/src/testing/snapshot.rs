@@ -0,0 +1,235 @@
+use std::{collections::HashMap, env, path::{Path, PathBuf}};
+
+use image::{Rgba, RgbaImage};
+
+use crate::{
+    application::resource_loader::path_navigator::identify_project_root_path,
+    rendering::{
+        elements::{common_types::Size, component::component_factory_registry::create_component},
+        renderer::Renderer
+    }
+};
+
+use super::error::SnapshotError;
+
+
+/*
+ * Golden-image snapshot testing for registered components.
+ * The component is mounted on a headless raster surface, rendered and compared pixel by pixel
+ * against a reference PNG (relative paths are resolved from the project root).
+ * On failure, the actual render and a diff image (mismatched pixels in red) are written next to the reference.
+ * Set ANGUST_UPDATE_SNAPSHOTS=1 to (re)write the reference images instead of comparing.
+ */
+pub const UPDATE_SNAPSHOTS_ENV_VAR: &str = "ANGUST_UPDATE_SNAPSHOTS";
+
+#[derive(Clone, Copy, Debug)]
+pub struct SnapshotOptions {
+    pub tolerance: u8, // Maximum allowed difference on any color channel of a pixel
+    pub max_mismatched_pixels: usize, // Number of pixels allowed to exceed the tolerance
+    pub scale_factor: f32, // Forced device scale factor, the reference image being viewport_size * scale_factor pixels
+}
+
+impl Default for SnapshotOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: 0,
+            max_mismatched_pixels: 0,
+            scale_factor: 1.0,
+        }
+    }
+}
+
+pub struct ImageComparison {
+    pub mismatched_pixels: usize,
+    pub diff_image: RgbaImage,
+}
+
+pub fn assert_component_snapshot(component_name: &str, viewport_size: Size, reference_path: &str, options: SnapshotOptions) {
+    if let Err(e) = compare_component_snapshot(component_name, viewport_size, reference_path, options) {
+        panic!("Snapshot test failed for component {}: {}", component_name, e);
+    }
+}
+
+pub fn compare_component_snapshot(
+    component_name: &str,
+    viewport_size: Size,
+    reference_path: &str,
+    options: SnapshotOptions
+) -> Result<(), SnapshotError> {
+    let actual = render_component(component_name, viewport_size, options.scale_factor)?;
+    let reference_path = resolve_snapshot_path(reference_path);
+
+    if should_update_snapshots() {
+        return save_image(&actual, &reference_path);
+    }
+    if !reference_path.exists() {
+        save_image(&actual, &reference_path)?;
+        return Err(SnapshotError::ReferenceCreated(reference_path.display().to_string()));
+    }
+
+    let expected = image::open(&reference_path)
+        .map_err(|e| SnapshotError::ReferenceNotFound(format!("{}: {}", reference_path.display(), e)))?
+        .to_rgba8();
+
+    let actual_path = get_sibling_path(&reference_path, "actual");
+    if actual.dimensions() != expected.dimensions() {
+        save_image(&actual, &actual_path)?;
+        return Err(SnapshotError::SizeMismatch(format!(
+            "expected {:?}, got {:?}, actual render written to {}",
+            expected.dimensions(), actual.dimensions(), actual_path.display()
+        )));
+    }
+
+    let comparison = compare_images(&actual, &expected, options.tolerance);
+    if comparison.mismatched_pixels <= options.max_mismatched_pixels {
+        return Ok(());
+    }
+
+    let diff_path = get_sibling_path(&reference_path, "diff");
+    save_image(&actual, &actual_path)?;
+    save_image(&comparison.diff_image, &diff_path)?;
+
+    Err(SnapshotError::PixelMismatch(format!(
+        "{} pixels differ by more than {} (allowed: {}), diff written to {}",
+        comparison.mismatched_pixels, options.tolerance, options.max_mismatched_pixels, diff_path.display()
+    )))
+}
+
+pub fn render_component(component_name: &str, viewport_size: Size, scale_factor: f32) -> Result<RgbaImage, SnapshotError> {
+    let mut component = create_component(component_name)
+        .ok_or_else(|| SnapshotError::ComponentNotFound(component_name.to_string()))?;
+    component.initialize(HashMap::new());
+
+    let mut renderer = Renderer::new_headless_with_scale_factor(viewport_size, scale_factor, component);
+    renderer.layout();
+    renderer.render_frame();
+
+    let width = renderer.surface.width() as u32;
+    let height = renderer.surface.height() as u32;
+    let pixels = renderer.read_pixels()
+        .ok_or_else(|| SnapshotError::RenderingError(String::from("Failed to read surface pixels")))?;
+
+    RgbaImage::from_raw(width, height, pixels)
+        .ok_or_else(|| SnapshotError::RenderingError(String::from("Unexpected pixel buffer size")))
+}
+
+/*
+ * Compares two images of the same dimensions. A pixel mismatches if any channel differs by more than the tolerance.
+ * The diff image shows mismatched pixels in red over a faded copy of the expected image.
+ */
+pub fn compare_images(actual: &RgbaImage, expected: &RgbaImage, tolerance: u8) -> ImageComparison {
+    let (width, height) = expected.dimensions();
+    let mut diff_image = RgbaImage::new(width, height);
+    let mut mismatched_pixels = 0;
+
+    for (x, y, expected_pixel) in expected.enumerate_pixels() {
+        let is_matching = actual.get_pixel_checked(x, y)
+            .map(|actual_pixel| get_max_channel_difference(actual_pixel, expected_pixel) <= tolerance)
+            .unwrap_or(false);
+
+        if is_matching {
+            diff_image.put_pixel(x, y, fade_pixel(expected_pixel));
+        } else {
+            mismatched_pixels += 1;
+            diff_image.put_pixel(x, y, Rgba([255, 0, 0, 255]));
+        }
+    }
+
+    ImageComparison { mismatched_pixels, diff_image }
+}
+
+fn get_max_channel_difference(first: &Rgba<u8>, second: &Rgba<u8>) -> u8 {
+    first.0.iter()
+        .zip(second.0.iter())
+        .map(|(a, b)| a.abs_diff(*b))
+        .max()
+        .unwrap_or(0)
+}
+
+fn fade_pixel(pixel: &Rgba<u8>) -> Rgba<u8> {
+    let [r, g, b, a] = pixel.0;
+    let luminance = ((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000) as u8;
+    let faded = 255 - (255 - luminance) / 4;
+
+    Rgba([faded, faded, faded, a.max(64)])
+}
+
+// Utils
+fn should_update_snapshots() -> bool {
+    env::var(UPDATE_SNAPSHOTS_ENV_VAR).map(|value| value == "1" || value == "true").unwrap_or(false)
+}
+
+fn resolve_snapshot_path(reference_path: &str) -> PathBuf {
+    let path = PathBuf::from(reference_path);
+    if path.is_absolute() {
+        return path;
+    }
+
+    PathBuf::from(identify_project_root_path()).join(path)
+}
+
+fn get_sibling_path(reference_path: &Path, suffix: &str) -> PathBuf {
+    let file_stem = reference_path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("snapshot");
+
+    reference_path.with_file_name(format!("{}.{}.png", file_stem, suffix))
+}
+
+fn save_image(image: &RgbaImage, path: &Path) -> Result<(), SnapshotError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| SnapshotError::IOError(e.to_string()))?;
+    }
+
+    image.save(path).map_err(|e| SnapshotError::IOError(format!("{}: {}", path.display(), e)))
+}
+
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_images_within_tolerance() {
+        // Arrange
+        let expected = RgbaImage::from_pixel(4, 4, Rgba([100, 100, 100, 255]));
+        let mut actual = expected.clone();
+        actual.put_pixel(1, 1, Rgba([102, 99, 100, 255]));
+
+        // Act
+        let comparison = compare_images(&actual, &expected, 2);
+
+        // Assert
+        assert_eq!(comparison.mismatched_pixels, 0);
+        assert_eq!(comparison.diff_image.dimensions(), (4, 4));
+    }
+
+    #[test]
+    fn test_compare_images_marks_mismatched_pixels() {
+        // Arrange
+        let expected = RgbaImage::from_pixel(4, 4, Rgba([100, 100, 100, 255]));
+        let mut actual = expected.clone();
+        actual.put_pixel(1, 1, Rgba([110, 100, 100, 255]));
+        actual.put_pixel(2, 3, Rgba([100, 100, 100, 0]));
+
+        // Act
+        let comparison = compare_images(&actual, &expected, 2);
+
+        // Assert
+        assert_eq!(comparison.mismatched_pixels, 2);
+        assert_eq!(*comparison.diff_image.get_pixel(1, 1), Rgba([255, 0, 0, 255]));
+        assert_eq!(*comparison.diff_image.get_pixel(2, 3), Rgba([255, 0, 0, 255]));
+        assert_ne!(*comparison.diff_image.get_pixel(0, 0), Rgba([255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn test_get_sibling_path() {
+        // Arrange
+        let reference_path = PathBuf::from("snapshots/header.png");
+
+        // Act
+        let diff_path = get_sibling_path(&reference_path, "diff");
+
+        // Assert
+        assert_eq!(diff_path, PathBuf::from("snapshots/header.diff.png"));
+    }
+}
@@ -12,10 +12,10 @@ use raw_window_handle::HasRawWindowHandle;
 use skia_safe::gpu::DirectContext;
 use std::num::NonZeroU32;
 use winit::dpi::LogicalSize;
-use winit::window::{Window, WindowAttributes};
+use winit::window::{Fullscreen, Window, WindowAttributes};
 use winit::event_loop::EventLoop;
 
-use crate::application::event_loop_proxy::ApplicationEvent;
+use crate::application::{angust_configuration::WindowConfiguration, event_loop_proxy::ApplicationEvent};
 
 pub struct WindowingSystem {
     pub window: Window,
@@ -26,24 +26,30 @@ pub struct WindowingSystem {
 }
 
 impl WindowingSystem {
-    pub fn new(event_loop: &EventLoop<ApplicationEvent>, app_title: String) -> Self {
-        let window_attributes = WindowAttributes::default()
-            .with_title(app_title)
-            .with_inner_size(LogicalSize::new(1200, 800));
+    pub fn new(event_loop: &EventLoop<ApplicationEvent>, window_config: &WindowConfiguration) -> Self {
+        let window_attributes = Self::create_window_attributes(window_config);
 
         let template = ConfigTemplateBuilder::new()
             .with_alpha_size(8)
-            .with_transparency(true);
+            .with_transparency(window_config.transparent);
+
+        let requested_transparency = window_config.transparent;
+        let requested_samples = window_config.msaa_samples.unwrap_or(0);
 
         let display_builder = DisplayBuilder::new().with_window_attributes(window_attributes.into());
         let (window, gl_config) = display_builder
             .build(event_loop, template, |configs| {
                 configs
                     .reduce(|accum, config| {
-                        let transparency_check = config.supports_transparency().unwrap_or(false)
-                            & !accum.supports_transparency().unwrap_or(false);
+                        let transparency_check = requested_transparency 
+                            && config.supports_transparency().unwrap_or(false)
+                            && !accum.supports_transparency().unwrap_or(false);
+
+                        // Prefer the sample count closest to the requested one (the lowest one by default)
+                        let samples_check = config.num_samples().abs_diff(requested_samples) 
+                            < accum.num_samples().abs_diff(requested_samples);
 
-                        if transparency_check || config.num_samples() < accum.num_samples() {
+                        if transparency_check || samples_check {
                             config
                         } else {
                             accum
@@ -115,4 +121,26 @@ impl WindowingSystem {
             gr_context,
         }
     }
+
+    fn create_window_attributes(window_config: &WindowConfiguration) -> WindowAttributes {
+        let mut window_attributes = WindowAttributes::default()
+            .with_title(window_config.title.clone().unwrap_or_else(|| String::from("Angust App")))
+            .with_inner_size(LogicalSize::new(window_config.width, window_config.height))
+            .with_resizable(window_config.resizable)
+            .with_decorations(window_config.decorations)
+            .with_transparent(window_config.transparent)
+            .with_maximized(window_config.maximized);
+
+        if let (Some(min_width), Some(min_height)) = (window_config.min_width, window_config.min_height) {
+            window_attributes = window_attributes.with_min_inner_size(LogicalSize::new(min_width, min_height));
+        }
+        if let (Some(max_width), Some(max_height)) = (window_config.max_width, window_config.max_height) {
+            window_attributes = window_attributes.with_max_inner_size(LogicalSize::new(max_width, max_height));
+        }
+        if window_config.fullscreen {
+            window_attributes = window_attributes.with_fullscreen(Some(Fullscreen::Borderless(None)));
+        }
+
+        window_attributes
+    }
 }